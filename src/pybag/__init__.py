@@ -2,7 +2,15 @@ from importlib.metadata import version
 
 __version__ = version("pybag-sdk")
 
-from .reader import DecodedMessage, Reader
+from .deserialize import decode_message
+from .reader import DecodedMessage, Reader, nanoseconds_to_datetime
 from .typestore import TypeStore
 
-__all__ = ['DecodedMessage', 'Reader', 'TypeStore', '__version__']
+__all__ = [
+    'DecodedMessage',
+    'Reader',
+    'TypeStore',
+    '__version__',
+    'decode_message',
+    'nanoseconds_to_datetime',
+]
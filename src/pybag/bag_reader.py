@@ -193,6 +193,17 @@ class BagFileReader:
         """Get the end time of the bag file in nanoseconds since epoch."""
         return max([ci.end_time for ci in self._chunk_infos], default=0)
 
+    def get_time_range(self) -> tuple[int, int, int] | None:
+        """Get the time range of the messages in the bag file.
+
+        Returns:
+            (start_time, end_time, duration) in nanoseconds, or None if the
+            bag has no messages.
+        """
+        if not self._chunk_infos:
+            return None
+        return self.start_time, self.end_time, self.end_time - self.start_time
+
     def _expand_topics(self, topic: str | list[str]) -> list[str]:
         """Expand topic patterns to list of concrete topic names.
 
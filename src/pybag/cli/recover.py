@@ -17,7 +17,7 @@ from pybag.cli.utils import (
 )
 from pybag.io.raw_reader import BytesReader, FileReader
 from pybag.io.raw_writer import FileWriter
-from pybag.mcap.chunk import decompress_chunk
+from pybag.mcap.chunk import ZSTD_DICTIONARY_ATTACHMENT, decompress_chunk
 from pybag.mcap.record_parser import McapRecordParser, McapRecordType
 from pybag.mcap.record_writer import (
     BaseMcapRecordWriter,
@@ -336,6 +336,8 @@ def recover_mcap(
 
     schemas: dict[int, SchemaRecord] = {}
     channels: dict[int, ChannelRecord] = {}
+    # Dictionary that zstd chunks were compressed with (written before any chunk)
+    zstd_dictionary: bytes | None = None
 
     with FileReader(input_path) as reader:
         # Parse magic bytes
@@ -390,7 +392,7 @@ def recover_mcap(
                         chunk = McapRecordParser.parse_chunk(reader)
                         try:
                             chunk_messages, schemas, channels = _process_chunk_records(
-                                decompress_chunk(chunk),
+                                decompress_chunk(chunk, zstd_dictionary=zstd_dictionary),
                                 schemas,
                                 channels,
                                 writer,
@@ -404,8 +406,12 @@ def recover_mcap(
                     elif record_type == McapRecordType.ATTACHMENT:
                         # TODO: Use crc to check data integrity?
                         attachment = McapRecordParser.parse_attachment(reader)
-                        attachments_recovered += 1
-                        writer.write_attachment(attachment)
+                        if attachment.name == ZSTD_DICTIONARY_ATTACHMENT:
+                            # Recovered chunks are recompressed without the dictionary
+                            zstd_dictionary = attachment.data
+                        else:
+                            attachments_recovered += 1
+                            writer.write_attachment(attachment)
 
                     elif record_type == McapRecordType.METADATA:
                         metadata = McapRecordParser.parse_metadata(reader)
@@ -1,41 +1,131 @@
-from typing import Callable
+from functools import lru_cache
+from typing import Any, Callable
 
 from pybag.encoding import MessageDecoder
-from pybag.encoding.cdr import CdrDecoder
+from pybag.encoding.cdr import CdrDecodeError, CdrDecoder
 from pybag.encoding.rosmsg import RosMsgDecoder
+from pybag.mcap.error import McapUnknownEncodingError
 from pybag.mcap.records import ChannelRecord, MessageRecord, SchemaRecord
-from pybag.schema import SchemaDecoder
+from pybag.schema import (
+    Array,
+    Complex,
+    Primitive,
+    Schema,
+    SchemaDecoder,
+    SchemaField,
+    SchemaFieldType,
+    Sequence,
+    String,
+    normalize_message_name
+)
 from pybag.schema.compiler import compile_schema
 from pybag.schema.ros1_compiler import compile_ros1_schema
 from pybag.schema.ros1msg import Ros1McapSchemaDecoder
+from pybag.schema.ros2idl import Ros2IdlSchemaDecoder
 from pybag.schema.ros2msg import Ros2MsgSchemaDecoder
 
 
+class _FieldDecodeError(Exception):
+    """Raised by ``_walk_message`` with the field that could not be decoded."""
+    def __init__(self, path: str, position: int, error: Exception):
+        super().__init__(f'Failed decoding {path} at byte {position}: {error}')
+
+
+def _walk_field(
+    decoder: MessageDecoder,
+    field_type: SchemaFieldType,
+    path: str,
+    sub_schemas: dict[str, Schema],
+) -> None:
+    if isinstance(field_type, Complex):
+        _walk_message(decoder, sub_schemas[field_type.type], sub_schemas, f'{path}.')
+    elif isinstance(field_type, (Array, Sequence)):
+        position = decoder.position()
+        try:
+            if isinstance(field_type, Array) and not field_type.is_bounded:
+                length = field_type.length
+            else:
+                length = decoder.uint32()
+        except Exception as e:
+            raise _FieldDecodeError(path, position, e) from e
+        for i in range(length):
+            _walk_field(decoder, field_type.type, f'{path}[{i}]', sub_schemas)
+    else:
+        position = decoder.position()
+        try:
+            if isinstance(field_type, String):
+                getattr(decoder, field_type.type)()
+            elif isinstance(field_type, Primitive):
+                decoder.parse(field_type.type)
+        except Exception as e:
+            raise _FieldDecodeError(path, position, e) from e
+
+
+def _walk_message(
+    decoder: MessageDecoder,
+    schema: Schema,
+    sub_schemas: dict[str, Schema],
+    prefix: str = '',
+) -> None:
+    """Decode a message field by field to find where decoding fails.
+
+    This is much slower than the compiled decoders and is only used to
+    describe an error after the compiled decoder has failed.
+
+    Raises:
+        _FieldDecodeError: With the path and byte offset of the failing field.
+    """
+    for name, entry in schema.fields.items():
+        if isinstance(entry, SchemaField):
+            _walk_field(decoder, entry.type, f'{prefix}{name}', sub_schemas)
+
+
 class MessageDeserializer:
     """
     This class caches compiled schema decoders and reuses a single decoder
     instance across all messages to minimize object allocation overhead.
+
+    Schemas are parsed by the decoder registered for their ``encoding``.
     """
 
     def __init__(
         self,
-        schema_decoder: SchemaDecoder,
+        schema_decoders: dict[str, SchemaDecoder],
         message_decoder: type[MessageDecoder],
         schema_compiler: Callable,
     ):
-        self._schema_decoder = schema_decoder
+        self._schema_decoders = schema_decoders
         self._message_decoder_class = message_decoder
         self._schema_compiler = schema_compiler
         self._compiled: dict[int, Callable[[MessageDecoder], type]] = {}
+        self._message_types: dict[str, Callable[..., Any]] = {}
         # Reusable decoder instance - created lazily on first use
         self._reusable_decoder: MessageDecoder | None = None
 
+    def register_message_type(self, schema_name: str, constructor: Callable[..., Any]) -> None:
+        """Decode messages named ``schema_name`` by calling ``constructor``.
+
+        The constructor is called with the decoded fields as keyword arguments.
+        """
+        self._message_types[normalize_message_name(schema_name)] = constructor
+        self._compiled.clear()  # Recompile so the new type is picked up
+
+    def parse_schema(self, schema: SchemaRecord) -> tuple[Schema, dict[str, Schema]]:
+        """Parse a schema record with the decoder registered for its encoding."""
+        if (schema_decoder := self._schema_decoders.get(schema.encoding)) is None:
+            raise McapUnknownEncodingError(f'Unknown schema encoding: {schema.encoding}')
+        return schema_decoder.parse_schema(schema)
+
     def deserialize_message(self, message: MessageRecord, schema: SchemaRecord) -> type:
         """Deserialize a message using the provided schema.
 
         This method reuses the decoder instance across calls when possible,
         which significantly reduces object allocation overhead when decoding
         many messages in sequence.
+
+        Raises:
+            CdrDecodeError: If CDR data cannot be decoded, naming the failing
+                field and its byte offset when they can be found.
         """
         # Reuse existing decoder if available, otherwise create new one
         if self._reusable_decoder is not None:
@@ -45,29 +135,128 @@ class MessageDeserializer:
             # Create new decoder and cache it for reuse
             decoder = self._message_decoder_class(message.data)
             self._reusable_decoder = decoder
+        return self.decode(decoder, schema)
+
+    def decode(self, decoder: MessageDecoder, schema: SchemaRecord) -> Any:
+        """Decode a message from a decoder positioned at its start.
 
+        Raises:
+            CdrDecodeError: If CDR data cannot be decoded, naming the failing
+                field and its byte offset when they can be found.
+        """
         # Compile schema decoder if not already cached
         if schema.id not in self._compiled:
-            msg_schema, schema_msgs = self._schema_decoder.parse_schema(schema)
-            self._compiled[schema.id] = self._schema_compiler(msg_schema, schema_msgs)
-        return self._compiled[schema.id](decoder)
+            msg_schema, schema_msgs = self.parse_schema(schema)
+            self._compiled[schema.id] = self._schema_compiler(
+                msg_schema, schema_msgs, self._message_types
+            )
+        try:
+            return self._compiled[schema.id](decoder)
+        except Exception as e:
+            if not isinstance(decoder, CdrDecoder):
+                raise
+            # Decode again field by field to report where the data is bad
+            msg_schema, schema_msgs = self.parse_schema(schema)
+            try:
+                _walk_message(decoder.reset(), msg_schema, schema_msgs)
+            except _FieldDecodeError as field_error:
+                raise CdrDecodeError(str(field_error)) from e
+            raise
 
 
 class MessageDeserializerFactory:
     """Factory for creating message deserializers."""
 
+    @staticmethod
+    def _ros2() -> MessageDeserializer:
+        schema_decoders: dict[str, SchemaDecoder] = {
+            "ros2msg": Ros2MsgSchemaDecoder(),
+            "ros2idl": Ros2IdlSchemaDecoder(),
+        }
+        return MessageDeserializer(schema_decoders, CdrDecoder, compile_schema)
+
+    @staticmethod
+    def _ros1() -> MessageDeserializer:
+        schema_decoders: dict[str, SchemaDecoder] = {"ros1msg": Ros1McapSchemaDecoder()}
+        return MessageDeserializer(schema_decoders, RosMsgDecoder, compile_ros1_schema)
+
     @staticmethod
     def from_profile(profile: str) -> MessageDeserializer | None:
         if profile == "ros2":
-            return MessageDeserializer(Ros2MsgSchemaDecoder(), CdrDecoder, compile_schema)
+            return MessageDeserializerFactory._ros2()
         if profile == "ros1":
-            return MessageDeserializer(Ros1McapSchemaDecoder(), RosMsgDecoder, compile_ros1_schema)
+            return MessageDeserializerFactory._ros1()
         return None
 
     @staticmethod
     def from_channel(channel: ChannelRecord, schema: SchemaRecord) -> MessageDeserializer | None:
-        if channel.message_encoding == "cdr" and schema.encoding == "ros2msg":
-            return MessageDeserializer(Ros2MsgSchemaDecoder(), CdrDecoder, compile_schema)
+        if channel.message_encoding == "cdr" and schema.encoding in ("ros2msg", "ros2idl"):
+            return MessageDeserializerFactory._ros2()
         if channel.message_encoding == "ros1" and schema.encoding == "ros1msg":
-            return MessageDeserializer(Ros1McapSchemaDecoder(), RosMsgDecoder, compile_ros1_schema)
+            return MessageDeserializerFactory._ros1()
         return None
+
+
+# Profiles that decode_message decodes each schema encoding with
+_DECODE_PROFILES = {"ros2msg": "ros2", "ros2idl": "ros2", "ros1msg": "ros1"}
+
+
+@lru_cache(maxsize=128)
+def _decode_message_deserializer(
+    schema_name: str,
+    schema_encoding: str,
+    schema_data: bytes,
+) -> tuple[MessageDeserializer, SchemaRecord]:
+    """Create the deserializer and schema record decode_message uses for a schema."""
+    deserializer = MessageDeserializerFactory.from_profile(_DECODE_PROFILES[schema_encoding])
+    assert deserializer is not None
+    schema = SchemaRecord(id=1, name=schema_name, encoding=schema_encoding, data=schema_data)
+    return deserializer, schema
+
+
+def decode_message(
+    schema_name: str,
+    schema_text: str,
+    data: bytes,
+    *,
+    schema_encoding: str = "ros2msg",
+    has_header: bool = True,
+    little_endian: bool = True,
+) -> Any:
+    """Decode a single serialized message outside of any file.
+
+    Parsed and compiled schemas of the most recently used definitions are
+    cached, so repeated calls with the same schema only decode the data.
+
+    Args:
+        schema_name: The message type name, e.g. ``geometry_msgs/msg/Point``.
+        schema_text: The message definition.
+        data: The serialized message (CDR for ROS 2, rosmsg for ROS 1).
+        schema_encoding: The encoding of the definition: ``ros2msg``,
+            ``ros2idl`` or ``ros1msg``.
+        has_header: Whether CDR data starts with the 4-byte encapsulation
+            header. Raw DDS payloads are often stored without it.
+        little_endian: The byte order of CDR data without a header.
+
+    Returns:
+        The decoded message.
+
+    Raises:
+        McapUnknownEncodingError: If the schema encoding is not supported.
+        ValueError: If ``has_header`` is False for a ROS 1 message.
+    """
+    if (profile := _DECODE_PROFILES.get(schema_encoding)) is None:
+        raise McapUnknownEncodingError(f'Unknown schema encoding: {schema_encoding}')
+    if not has_header and profile != "ros2":
+        raise ValueError('Only CDR data has an encapsulation header')
+    deserializer, schema = _decode_message_deserializer(
+        schema_name, schema_encoding, schema_text.encode('utf-8')
+    )
+    # A new decoder per call, as the deserializer may be shared between threads
+    if not has_header:
+        decoder: MessageDecoder = CdrDecoder.headerless(data, little_endian=little_endian)
+    elif profile == "ros2":
+        decoder = CdrDecoder(data)
+    else:
+        decoder = RosMsgDecoder(data)
+    return deserializer.decode(decoder, schema)
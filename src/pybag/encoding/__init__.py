@@ -22,6 +22,11 @@ class MessageDecoder(ABC):
         """
         ...  # pragma: no cover
 
+    @abstractmethod
+    def position(self) -> int:
+        """Get the offset of the next byte to decode within the message data."""
+        ...  # pragma: no cover
+
     # Primitive parsers -------------------------------------------------
 
     @abstractmethod
@@ -41,7 +46,7 @@ class MessageDecoder(ABC):
         ...  # pragma: no cover
 
     @abstractmethod
-    def char(self) -> str | int:  # ros1: int, ros2: str
+    def char(self) -> int:
         ...  # pragma: no cover
 
     @abstractmethod
@@ -125,7 +130,7 @@ class MessageEncoder(ABC):
         ...  # pragma: no cover
 
     @abstractmethod
-    def char(self, value: Any) -> None:  # ros1: int, ros2: str
+    def char(self, value: Any) -> None:  # ros2 also accepts a one-character str
         ...  # pragma: no cover
 
     @abstractmethod
@@ -11,33 +11,88 @@ logger = logging.getLogger(__name__)
 _UINT32_LE = struct.Struct('<I')
 _UINT32_BE = struct.Struct('>I')
 
+# Struct format and size of primitives that can be read in bulk
+_BATCH_FORMAT = {
+    'bool': ('?', 1),
+    'int8': ('b', 1),
+    'uint8': ('B', 1),
+    'int16': ('h', 2),
+    'uint16': ('H', 2),
+    'int32': ('i', 4),
+    'uint32': ('I', 4),
+    'int64': ('q', 8),
+    'uint64': ('Q', 8),
+    'float32': ('f', 4),
+    'float64': ('d', 8),
+}
+
+
+class CdrDecodeError(Exception):
+    """Exception raised when CDR data cannot be decoded."""
+    def __init__(self, message: str):
+        super().__init__(message)
+
+
 class CdrDecoder(MessageDecoder):
     """CDR (Common Data Representation) decoder for ROS2 messages."""
 
-    __slots__ = ('_is_little_endian', '_data')
+    __slots__ = ('_is_little_endian', '_data', '_header_size')
 
-    def __init__(self, data: bytes):
+    def __init__(self, data: bytes, *, alignment_origin: int = 4):
         """Create a new CDR decoder.
 
         Args:
             data: Optional CDR-encoded message data. If None, the decoder
                   must be initialized with reset() before use.
+            alignment_origin: Offset in the message data that primitives are
+                  aligned relative to. Classic CDR aligns from the end of the
+                  4-byte header, while XCDR2 streams with a delimiter header
+                  (DHEADER) align from after it.
         """
         assert len(data) >= 4, 'Data must be at least 4 bytes long (CDR header).'
+        if alignment_origin < 4:
+            raise ValueError(f'Alignment origin {alignment_origin} is inside the CDR header')
 
         # Get endianness from second byte
         self._is_little_endian = bool(data[1])
         self._data = BytesReader(data[4:])
+        self._data.alignment_origin = alignment_origin - 4
+        self._header_size = 4
+
+    @classmethod
+    def headerless(cls, data: bytes, *, little_endian: bool = True) -> 'CdrDecoder':
+        """Create a decoder for a CDR payload stored without the encapsulation header.
+
+        Positions and alignment are relative to the start of ``data``, and
+        reset() expects headerless data with the same endianness.
 
-    def reset(self, data: bytes) -> 'CdrDecoder':
+        Args:
+            data: The CDR payload.
+            little_endian: The byte order of the payload.
+        """
+        decoder = cls.__new__(cls)
+        decoder._is_little_endian = little_endian
+        decoder._data = BytesReader(data)
+        decoder._header_size = 0
+        return decoder
+
+    def reset(self, data: bytes | None = None) -> 'CdrDecoder':
         """Reset the decoder with new message data for reuse.
 
         Args:
-            data: CDR-encoded message data (must include 4-byte CDR header)
+            data: CDR-encoded message data (must include 4-byte CDR header).
+                  If None, rewind to the start of the current message instead.
 
         Returns:
             self, allowing for method chaining
         """
+        if data is None:
+            self._data.position = 0
+            return self
+        if not self._header_size:
+            self._data.reset(data)
+            return self
+
         assert len(data) >= 4, 'Data must be at least 4 bytes long (CDR header).'
 
         # Get endianness from second byte
@@ -48,6 +103,27 @@ class CdrDecoder(MessageDecoder):
     def parse(self, type_str: str) -> Any:
         return getattr(self, type_str)()
 
+    def position(self) -> int:
+        # Offsets are relative to the payload, which starts after the CDR header
+        return self._data.position + self._header_size
+
+    def seek(self, position: int) -> 'CdrDecoder':
+        """Move to an offset within the message data, as returned by position().
+
+        Args:
+            position: Offset from the start of the message, including the CDR header.
+
+        Returns:
+            self, allowing for method chaining
+
+        Raises:
+            ValueError: If the offset is in the CDR header or past the end of the data.
+        """
+        if not self._header_size <= position <= self._data.size() + self._header_size:
+            raise ValueError(f'Position {position} is outside the message payload')
+        self._data.position = position - self._header_size
+        return self
+
     # Primitive parsers -------------------------------------------------
 
     def bool(self) -> bool:
@@ -64,9 +140,9 @@ class CdrDecoder(MessageDecoder):
     def byte(self) -> bytes:
         return self._data.align(1).read(1)
 
-    def char(self) -> str:
-        fmt = '<c' if self._is_little_endian else '>c'
-        return self._data.align(1).unpack_one(fmt, 1).decode()
+    def char(self) -> int:
+        # ROS 2 char is an unsigned 8-bit integer, as in rclpy
+        return self._data.align(1).unpack_one('B', 1)
 
     def int16(self) -> int:
         fmt = '<h' if self._is_little_endian else '>h'
@@ -107,52 +183,73 @@ class CdrDecoder(MessageDecoder):
         pos = data.position
 
         # Align to 4-byte boundary
-        if remainder := pos & 3:
+        if remainder := (pos - data.alignment_origin) & 3:
             pos += 4 - remainder
 
         # Read length directly using pre-compiled struct
         fmt = _UINT32_LE if self._is_little_endian else _UINT32_BE
         length = fmt.unpack_from(data.view, pos)[0]
         pos += 4
+        if pos + length > data.size():
+            raise CdrDecodeError(
+                f'String length {length} exceeds the {data.size() - pos} bytes remaining'
+            )
         if length <= 1:
             data.position = pos + length
             return ''
 
-        # Read string bytes directly from memoryview and decode
+        # Decode straight from the buffer without copying the string bytes out first
         end = pos + length - 1
-        result = data.view[pos:end].tobytes().decode()
+        result = str(data.view[pos:end], 'utf-8')
         data.position = end + 1  # skip past string + null terminator
         return result
 
     def wstring(self) -> str:
-        # Wide strings use 4 bytes per character (uint32)
-        # Length is the number of characters including null terminator
-        length = self.uint32()
-        if length <= 1:
-            # Skip the null terminator if present
-            if length == 1:
-                self._data.align(4)
-                self._data.seek_from_current(4)
+        # Wide strings are UTF-16 code units (2 bytes each) without a null terminator
+        # The length is the number of code units, not characters
+        length = self.sequence_length(2)
+        if length == 0:
             return ''
-        # Read each character as a uint32 and convert to string
-        fmt = '<I' if self._is_little_endian else '>I'
-        chars = []
-        for _ in range(length - 1):  # Exclude null terminator
-            self._data.align(4)
-            char_code = self._data.unpack_one(fmt, 4)
-            chars.append(chr(char_code))
-        # Skip null terminator
-        self._data.align(4)
-        self._data.seek_from_current(4)
-        return ''.join(chars)
+        data = bytes(self._data.read(2 * length))
+        return data.decode('utf-16-le' if self._is_little_endian else 'utf-16-be')
 
     # Container parsers --------------------------------------------------
 
+    def sequence_length(self, element_size: int = 1) -> int:
+        """Read a sequence length prefix and check it fits in the remaining data.
+
+        Args:
+            element_size: Minimum number of bytes each element occupies.
+
+        Returns:
+            The number of elements in the sequence.
+        """
+        length = self.uint32()
+        remaining = self._data.size() - self._data.position
+        if length * element_size > remaining:
+            raise CdrDecodeError(
+                f'Sequence length {length} exceeds the {remaining} bytes remaining'
+            )
+        return length
+
+    def _read_batch(self, type: str, length: int) -> list:
+        # Primitive elements are contiguous, so align once and unpack them together
+        fmt, size = _BATCH_FORMAT[type]
+        prefix = '<' if self._is_little_endian else '>'
+        data = self._data.align(size) if length else self._data
+        values = list(struct.unpack_from(f'{prefix}{length}{fmt}', data.view, data.position))
+        data.position += size * length
+        return values
+
     def array(self, type: str, length: int) -> list:
+        if type in _BATCH_FORMAT:
+            return self._read_batch(type, length)
         return [getattr(self, f'{type}')() for _ in range(length)]
 
     def sequence(self, type: str) -> list:
-        length = self.uint32()
+        length = self.sequence_length(_BATCH_FORMAT.get(type, ('', 1))[1])
+        if type in _BATCH_FORMAT:
+            return self._read_batch(type, length)
         return [getattr(self, f'{type}')() for _ in range(length)]
 
 
@@ -207,10 +304,10 @@ class CdrEncoder(MessageEncoder):
         self._payload.align(1)
         self._payload.write(value)
 
-    def char(self, value: str) -> None:
+    def char(self, value: int | str) -> None:
+        # Single character strings are accepted for convenience
         self._payload.align(1)
-        fmt = "<c" if self._is_little_endian else ">c"
-        self._payload.write(struct.pack(fmt, value.encode()))
+        self._payload.write(struct.pack("B", ord(value) if isinstance(value, str) else value))
 
     def int16(self, value: int) -> None:
         self._payload.align(2)
@@ -259,16 +356,10 @@ class CdrEncoder(MessageEncoder):
         self._payload.write(encoded + b"\x00")
 
     def wstring(self, value: str) -> None:
-        # Wide strings use 4 bytes per character (uint32)
-        # Write length (including null terminator)
-        self.uint32(len(value) + 1)
-        fmt = "<I" if self._is_little_endian else ">I"
-        for char in value:
-            self._payload.align(4)
-            self._payload.write(struct.pack(fmt, ord(char)))
-        # Write null terminator
-        self._payload.align(4)
-        self._payload.write(struct.pack(fmt, 0))
+        # Wide strings are UTF-16 code units (2 bytes each) without a null terminator
+        encoded = value.encode('utf-16-le' if self._is_little_endian else 'utf-16-be')
+        self.uint32(len(encoded) // 2)
+        self._payload.write(encoded)
 
     # Container encoders -------------------------------------------------
 
@@ -77,6 +77,10 @@ class RosMsgDecoder(MessageDecoder):
         """Parse a value based on its type string."""
         return getattr(self, type_str)()
 
+    def position(self) -> int:
+        """Get the offset of the next byte to decode."""
+        return self._data.position
+
     # Primitive parsers -------------------------------------------------
     # These use unpack_from() to avoid creating intermediate bytes objects
 
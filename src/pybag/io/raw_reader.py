@@ -1,10 +1,11 @@
+import os
 import struct
 import zlib
 from abc import ABC, abstractmethod
 from enum import IntEnum
 from io import BufferedReader
 from pathlib import Path
-from typing import Any
+from typing import Any, BinaryIO
 
 
 class FilePosition(IntEnum):
@@ -44,6 +45,11 @@ class BaseReader(ABC):
         """Get the current position in the reader."""
         ...  # pragma: no cover
 
+    @abstractmethod
+    def size(self) -> int:
+        """Get the total number of bytes in the reader."""
+        ...  # pragma: no cover
+
     @abstractmethod
     def close(self) -> None:
         """Close the reader and release all resources."""
@@ -54,6 +60,8 @@ class FileReader(BaseReader):
     def __init__(self, file_path: Path | str, mode: str = 'rb'):
         self._file_path = Path(file_path).absolute()
         self._file: BufferedReader = open(self._file_path, mode)
+        # Cached since every record read checks its length against the size
+        self._size = os.fstat(self._file.fileno()).st_size
 
     def peek(self, size: int) -> bytes:
         # Returns empty bytes when end of file
@@ -74,6 +82,9 @@ class FileReader(BaseReader):
     def tell(self) -> int:
         return self._file.tell()
 
+    def size(self) -> int:
+        return self._size
+
     def close(self) -> None:
         self._file.close()
 
@@ -90,6 +101,8 @@ class BytesReader(BaseReader):
         self.view = memoryview(data)
         self.position = 0
         self._length = len(data)
+        # Offset that align() measures alignment from
+        self.alignment_origin = 0
 
     def reset(self, data: bytes):
         self._data = data
@@ -127,7 +140,7 @@ class BytesReader(BaseReader):
 
     def align(self, size: int) -> 'BytesReader':
         # Faster bit-based alignment for power-of-2 sizes only
-        if remainder := self.position & (size - 1):
+        if remainder := (self.position - self.alignment_origin) & (size - 1):
             self.position += size - remainder
         return self
 
@@ -177,6 +190,51 @@ class BytesReader(BaseReader):
         pass
 
 
+class StreamReader(BaseReader):
+    """Read from a seekable binary stream, e.g. ``sys.stdin.buffer`` or ``io.BytesIO``."""
+
+    def __init__(self, stream: BinaryIO):
+        if not stream.seekable():
+            raise ValueError('StreamReader requires a seekable stream')
+        self._stream = stream
+
+    def peek(self, size: int) -> bytes:
+        # Returns empty bytes when end of stream
+        data = self._stream.read(size)
+        self._stream.seek(-len(data), FilePosition.CURRENT)
+        return data
+
+    def read(self, size: int | None = None) -> bytes:
+        return self._stream.read(size)
+
+    def seek_from_start(self, offset: int) -> int:
+        return self._stream.seek(offset, FilePosition.START)
+
+    def seek_from_end(self, offset: int) -> int:
+        return self._stream.seek(-offset, FilePosition.END)
+
+    def seek_from_current(self, offset: int) -> int:
+        return self._stream.seek(offset, FilePosition.CURRENT)
+
+    def tell(self) -> int:
+        return self._stream.tell()
+
+    def size(self) -> int:
+        current = self._stream.tell()
+        size = self._stream.seek(0, FilePosition.END)
+        self._stream.seek(current, FilePosition.START)
+        return size
+
+    def close(self) -> None:
+        self._stream.close()
+
+    def __enter__(self) -> 'StreamReader':
+        return self
+
+    def __exit__(self, exc_type, exc_value, traceback) -> None:
+        self.close()
+
+
 class CrcReader(BaseReader):
     def __init__(self, reader: BaseReader):
         self._reader = reader
@@ -202,6 +260,9 @@ class CrcReader(BaseReader):
     def tell(self) -> int:
         return self._reader.tell()
 
+    def size(self) -> int:
+        return self._reader.size()
+
     def close(self) -> None:
         self._reader.close()
 
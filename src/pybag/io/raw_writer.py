@@ -48,6 +48,14 @@ class BaseWriter(ABC):
         """Truncate at current position"""
         ...  # pragma: no cover
 
+    def write_at(self, position: int, data: bytes) -> int:
+        """Overwrite bytes at a position without moving the current position."""
+        current = self.tell()
+        self.seek_from_start(position)
+        written = self.write(data)
+        self.seek_from_start(current)
+        return written
+
     @abstractmethod
     def close(self) -> None:
         """Close the writer."""
@@ -92,19 +100,24 @@ class BytesWriter(BaseWriter):
 
     def __init__(self):
         self._buffer = bytearray()
+        self._position = 0
 
     def write(self, data: bytes) -> int:
-        self._buffer.extend(data)
+        end = self._position + len(data)
+        if self._position == len(self._buffer):
+            self._buffer.extend(data)
+        else:
+            self._buffer[self._position:end] = data
+        self._position = end
         return len(data)
 
     def tell(self) -> int:
-        return len(self._buffer)
+        return self._position
 
     def align(self, size: int) -> None:
         # Faster bit-based alignment for power-of-2 sizes only
-        if remainder := len(self._buffer) & (size - 1):
-            padding = size - remainder
-            self._buffer.extend(b"\x00" * padding)
+        if remainder := self._position & (size - 1):
+            self.write(b"\x00" * (size - remainder))
 
     def size(self) -> int:
         return len(self._buffer)
@@ -114,40 +127,52 @@ class BytesWriter(BaseWriter):
 
     def clear(self) -> None:
         self._buffer.clear()
+        self._position = 0
+
+    def _seek(self, position: int) -> int:
+        if not 0 <= position <= len(self._buffer):
+            raise ValueError(f'Cannot seek to {position} in a buffer of {len(self._buffer)} bytes')
+        self._position = position
+        return position
 
     def seek_from_start(self, offset: int) -> int:
-        # TODO: Implement for BytesWriter
-        raise NotImplementedError("seek_from_start is not yet supported")
+        return self._seek(offset)
 
     def seek_from_end(self, offset: int) -> int:
-        # TODO: Implement for BytesWriter
-        raise NotImplementedError("seek_from_end is not yet supported")
+        return self._seek(len(self._buffer) - offset)
 
     def seek_from_current(self, offset: int) -> int:
-        # TODO: Implement for BytesWriter
-        raise NotImplementedError("seek_from_current is not yet supported")
+        return self._seek(self._position + offset)
 
     def read(self, size: int | None = None) -> bytes:
-        # TODO: Implement for BytesWriter
-        raise NotImplementedError("read is not yet supported")
+        if size is None or size < 0:
+            end = len(self._buffer)
+        else:
+            end = min(self._position + size, len(self._buffer))
+        result = bytes(self._buffer[self._position:end])
+        self._position = end
+        return result
 
     def truncate(self) -> None:
-        # TODO: Implement for BytesWriter
-        raise NotImplementedError("truncate is not yet supported")
+        """Truncate the buffer at the current position."""
+        del self._buffer[self._position:]
 
     def close(self) -> None:
-        self._buffer.clear()
+        # Keep the buffer so the written bytes can be retrieved after closing
+        pass
 
 
 class CrcWriter(BaseWriter):
     """Write binary data and track CRC32."""
 
-    def __init__(self, writer: BaseWriter, initial_crc: int = 0):
+    def __init__(self, writer: BaseWriter, initial_crc: int = 0, *, enabled: bool = True):
         self._writer = writer
-        self._crc = initial_crc
+        self._crc = initial_crc if enabled else 0
+        self._enabled = enabled
 
     def write(self, data: bytes) -> int:
-        self._crc = zlib.crc32(data, self._crc)
+        if self._enabled:
+            self._crc = zlib.crc32(data, self._crc)
         return self._writer.write(data)
 
     def tell(self) -> int:
@@ -168,11 +193,17 @@ class CrcWriter(BaseWriter):
     def truncate(self) -> None:
         self._writer.truncate()
 
+    def write_at(self, position: int, data: bytes) -> int:
+        """Overwrite bytes at a position. The bytes are not added to the CRC."""
+        return self._writer.write_at(position, data)
+
     def get_crc(self) -> int:
         return self._crc
 
     def clear_crc(self) -> None:
+        """Reset the CRC and track it from this point on, even if disabled."""
         self._crc = 0
+        self._enabled = True
 
     def close(self) -> None:
         self._writer.close()
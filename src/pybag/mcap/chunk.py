@@ -1,3 +1,4 @@
+import lz4.block
 import lz4.frame
 import zstandard as zstd
 
@@ -5,13 +6,44 @@ from pybag.mcap.crc import assert_crc
 from pybag.mcap.error import McapUnknownCompressionError
 from pybag.mcap.records import ChunkRecord
 
+# Magic number at the start of every LZ4 frame (0x184D2204, little endian)
+LZ4_FRAME_MAGIC = b'\x04\x22\x4d\x18'
 
-def decompress_chunk(chunk: ChunkRecord, *, check_crc: bool = False) -> bytes:
-    """Decompress the records field of a chunk."""
+# Attachment holding the dictionary that zstd chunks were compressed with
+ZSTD_DICTIONARY_ATTACHMENT = 'pybag/zstd_dictionary'
+ZSTD_DICTIONARY_MEDIA_TYPE = 'application/x-zstd-dictionary'
+
+
+def _decompress_lz4(chunk: ChunkRecord) -> bytes:
+    # The MCAP spec uses the LZ4 frame format, but some writers emit raw blocks
+    if chunk.records[:4] == LZ4_FRAME_MAGIC:
+        return lz4.frame.decompress(chunk.records)
+    return lz4.block.decompress(chunk.records, uncompressed_size=chunk.uncompressed_size)
+
+
+def decompress_chunk(
+    chunk: ChunkRecord,
+    *,
+    check_crc: bool = False,
+    zstd_dictionary: bytes | None = None,
+) -> bytes:
+    """Decompress the records field of a chunk.
+
+    Args:
+        chunk: The chunk to decompress.
+        check_crc: Whether to validate the CRC of the decompressed records.
+        zstd_dictionary: Dictionary that zstd chunks were compressed with, if any.
+    """
+    if chunk.uncompressed_size == 0 and not chunk.records:
+        return b''  # An empty chunk may be written without any compressed data
     if chunk.compression == 'zstd':
-        chunk_data = zstd.ZstdDecompressor().decompress(chunk.records)
+        if zstd_dictionary is None:
+            decompressor = zstd.ZstdDecompressor()
+        else:
+            decompressor = zstd.ZstdDecompressor(dict_data=zstd.ZstdCompressionDict(zstd_dictionary))
+        chunk_data = decompressor.decompress(chunk.records)
     elif chunk.compression == 'lz4':
-        chunk_data = lz4.frame.decompress(chunk.records)
+        chunk_data = _decompress_lz4(chunk)
     elif chunk.compression == '':
         chunk_data = chunk.records
     else:
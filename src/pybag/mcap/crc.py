@@ -7,7 +7,8 @@ from pybag.mcap.record_parser import (
     MAGIC_BYTES_SIZE,
     McapRecordParser
 )
-from pybag.mcap.records import ChunkRecord, FooterRecord
+from pybag.mcap.record_encoder import McapRecordWriter
+from pybag.mcap.records import AttachmentRecord, ChunkRecord, FooterRecord
 
 DEFAULT_CRC_CHUNK_SIZE = 8 * 1024 * 1024  # 8 MiB
 
@@ -30,6 +31,24 @@ def compute_crc(data: bytes, start_value: int = 0) -> int:
     return zlib.crc32(data, start_value)
 
 
+def compute_attachment_crc(attachment: AttachmentRecord) -> int:
+    """Compute the CRC of an attachment, which covers every field before the CRC."""
+    return zlib.crc32(McapRecordWriter.encode_attachment_fields(attachment))
+
+
+def assert_attachment_crc(attachment: AttachmentRecord) -> None:
+    """Assert the CRC of an attachment, skipping attachments without one (CRC of 0).
+
+    Earlier pybag releases computed the CRC over the data alone, so that is
+    accepted as well to keep their files readable.
+    """
+    if not attachment.crc or compute_attachment_crc(attachment) == attachment.crc:
+        return
+    if compute_crc(attachment.data) == attachment.crc:
+        return  # Written by an earlier pybag release
+    raise McapInvalidCrcError(f'Invalid CRC for attachment {attachment.name}')
+
+
 def compute_crc_batched(
     reader: BaseReader,
     num_bytes: int,
@@ -72,3 +72,27 @@ class McapUnknownTopicError(McapError):
     """Exception raised when a topic is not found in a MCAP file."""
     def __init__(self, message: str):
         super().__init__(message)
+
+
+class McapMessageTooLargeError(McapError):
+    """Exception raised when a message exceeds the allowed payload size."""
+    def __init__(self, message: str):
+        super().__init__(message)
+
+
+class McapNonMonotonicError(McapError):
+    """Exception raised when a message is written with an earlier log time than the previous one."""
+    def __init__(self, message: str):
+        super().__init__(message)
+
+
+class McapConcatenatedError(McapError):
+    """Exception raised when several MCAP files have been concatenated into one."""
+    def __init__(self, message: str):
+        super().__init__(message)
+
+
+class McapDecodeError(McapError):
+    """Exception raised when messages fail to decode."""
+    def __init__(self, message: str):
+        super().__init__(message)
@@ -185,16 +185,20 @@ class McapRecordWriter:
         cls._write_record(writer, RecordType.CHUNK_INDEX, payload)
 
     @classmethod
-    def write_attachment(cls, writer: BaseWriter, record: AttachmentRecord) -> None:
-        payload = (
+    def encode_attachment_fields(cls, record: AttachmentRecord) -> bytes:
+        """Encode the attachment fields covered by its CRC (all but the CRC itself)."""
+        return (
               cls._encode_timestamp(record.log_time)
             + cls._encode_timestamp(record.create_time)
             + cls._encode_string(record.name)
             + cls._encode_string(record.media_type)
             + cls._encode_uint64(len(record.data))
             + record.data
-            + cls._encode_uint32(record.crc)
         )
+
+    @classmethod
+    def write_attachment(cls, writer: BaseWriter, record: AttachmentRecord) -> None:
+        payload = cls.encode_attachment_fields(record) + cls._encode_uint32(record.crc)
         cls._write_record(writer, RecordType.ATTACHMENT, payload)
 
     @classmethod
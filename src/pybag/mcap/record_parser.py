@@ -26,6 +26,8 @@ from pybag.mcap.records import (
 
 logger = logging.getLogger(__name__)
 
+MAGIC_BYTES = b'\x89MCAP\x30\r\n'
+
 # Number of bytes of fixed-sized records
 MAGIC_BYTES_SIZE = 8
 FOOTER_SIZE = 29  # Includes the 1 byte record type and 8 bytes record length
@@ -36,6 +38,9 @@ DATA_END_SIZE = 13  # Includes the 1 byte record type and 8 bytes record length
 MESSAGE_HEADER_FORMAT = struct.Struct('<HIQQ')
 MESSAGE_HEADER_SIZE = 22  # Total: 2 + 4 + 8 + 8 = 22 bytes
 
+# Record lengths follow the 1 byte record type
+_UINT64 = struct.Struct('<Q')
+
 class _Readable(Protocol):
     """Minimal readable interface used when parsing from writer-backed files."""
 
@@ -66,6 +71,12 @@ class MalformedMCAP(Exception):
         super().__init__(error_msessage)
 
 
+class McapUnexpectedEofError(MalformedMCAP):
+    """The data ends part way through a record, e.g. in a truncated file."""
+    def __init__(self, message: str):
+        super().__init__(message)
+
+
 class McapRecordParser:
     @classmethod
     def peek_record(cls, file: BaseReader) -> int:
@@ -79,7 +90,11 @@ class McapRecordParser:
     def skip_record(cls, file: BaseReader) -> None:
         """Skip the next record in the MCAP file."""
         _ = file.read(1)  # Skip the record type
-        _, record_length = cls._parse_uint64(file)
+        record_length = cls._parse_record_length(file)
+        if record_length > (remaining := file.size() - file.tell()):
+            raise McapUnexpectedEofError(
+                f'Record length ({record_length} bytes) exceeds the remaining {remaining} bytes.'
+            )
         file.seek_from_current(record_length)
 
 
@@ -98,7 +113,7 @@ class McapRecordParser:
     def parse_magic_bytes(cls, file: BaseReader) -> str:
         """Parse the magic bytes at the begining/end of the MCAP file."""
         magic = file.read(8)
-        if magic != b'\x89MCAP\x30\r\n':  # TODO: Support multiple versions
+        if magic != MAGIC_BYTES:  # TODO: Support multiple versions
             raise MalformedMCAP(f'Invalid magic bytes: {str(magic)}')
         return chr(magic[5])  # Return the version
 
@@ -197,6 +212,31 @@ class McapRecordParser:
 
         return array_length_bytes + array_length, array
 
+    @classmethod
+    def _parse_record_length(cls, file: _Readable) -> int:
+        """Read the length following a record's opcode.
+
+        Raises:
+            McapUnexpectedEofError: If the data ends within the length.
+        """
+        if len(data := file.read(8)) < 8:
+            raise McapUnexpectedEofError(f'Data ends within a record header ({len(data)} of 8 length bytes).')
+        return _UINT64.unpack(data)[0]
+
+    @classmethod
+    def _read_record_data(cls, file: BaseReader, record_type: RecordType, record_length: int) -> bytes:
+        """Read the body of a record, checking its length fits in the remaining data.
+
+        Raises:
+            McapUnexpectedEofError: If the data ends before the end of the record.
+        """
+        if record_length > (remaining := file.size() - file.tell()):
+            raise McapUnexpectedEofError(
+                f'{record_type.name} record length ({record_length} bytes) '
+                f'exceeds the remaining {remaining} bytes.'
+            )
+        return file.read(record_length)
+
     # MCAP Record Handlers
 
     @classmethod
@@ -205,8 +245,8 @@ class McapRecordParser:
         if (record_type := file.read(1)) != b'\x01':
             raise MalformedMCAP(f'Unexpected record type ({record_type}).')
 
-        _, record_length = cls._parse_uint64(file)
-        bytes_reader = BytesReader(file.read(record_length))
+        record_length = cls._parse_record_length(file)
+        bytes_reader = BytesReader(cls._read_record_data(file, RecordType.HEADER, record_length))
 
         _, profile = cls._parse_string(bytes_reader)
         _, library = cls._parse_string(bytes_reader)
@@ -221,7 +261,7 @@ class McapRecordParser:
             raise MalformedMCAP(f'Unexpected record type ({record_type}).')
 
         # Footer record length is fixed to 20 bytes
-        _, record_length = cls._parse_uint64(file)
+        record_length = cls._parse_record_length(file)
         if record_length != 20:
             raise MalformedMCAP(f'Unexpected footer record length ({record_length} bytes).')
         bytes_reader = BytesReader(file.read(record_length))
@@ -238,8 +278,8 @@ class McapRecordParser:
         if (record_type := file.read(1)) != b'\x03':
             raise MalformedMCAP(f'Unexpected record type ({record_type}).')
 
-        _, record_length = cls._parse_uint64(file)
-        bytes_reader = BytesReader(file.read(record_length))
+        record_length = cls._parse_record_length(file)
+        bytes_reader = BytesReader(cls._read_record_data(file, RecordType.SCHEMA, record_length))
 
         _, id = cls._parse_uint16(bytes_reader)
         if id == 0:  # Invalid and should be ignored
@@ -258,8 +298,8 @@ class McapRecordParser:
         if (record_type := file.read(1)) != b'\x04':
             raise MalformedMCAP(f'Unexpected record type ({record_type}).')
 
-        _, record_length = cls._parse_uint64(file)
-        bytes_reader = BytesReader(file.read(record_length))
+        record_length = cls._parse_record_length(file)
+        bytes_reader = BytesReader(cls._read_record_data(file, RecordType.CHANNEL, record_length))
 
         _, id = cls._parse_uint16(bytes_reader)
         _, schema_id = cls._parse_uint16(bytes_reader)
@@ -275,11 +315,10 @@ class McapRecordParser:
         if (record_type := file.read(1)) != b'\x05':
             raise MalformedMCAP(f'Unexpected record type ({record_type}).')
 
-        # Read record length (8 bytes) - inlined for performance
-        record_length = struct.unpack('<Q', file.read(8))[0]
+        record_length = cls._parse_record_length(file)
 
         # Read entire record data at once
-        record_data = file.read(record_length)
+        record_data = cls._read_record_data(file, RecordType.MESSAGE, record_length)
 
         # Unpack all fixed fields in a single call (2 + 4 + 8 + 8 = 22 bytes)
         message_fields = MESSAGE_HEADER_FORMAT.unpack(record_data[:MESSAGE_HEADER_SIZE])
@@ -296,8 +335,8 @@ class McapRecordParser:
         if (record_type := file.read(1)) != b'\x06':
             raise MalformedMCAP(f'Unexpected record type ({record_type}).')
 
-        _, record_length = cls._parse_uint64(file)
-        bytes_reader = BytesReader(file.read(record_length))
+        record_length = cls._parse_record_length(file)
+        bytes_reader = BytesReader(cls._read_record_data(file, RecordType.CHUNK, record_length))
 
         _, message_start_time = cls._parse_timestamp(bytes_reader)
         _, message_end_time = cls._parse_timestamp(bytes_reader)
@@ -323,7 +362,7 @@ class McapRecordParser:
             raise MalformedMCAP(f'Unexpected record type ({record_type}).')
 
         _, message_index_length = cls._parse_uint64(file)
-        bytes_reader = BytesReader(file.read(message_index_length))
+        bytes_reader = BytesReader(cls._read_record_data(file, RecordType.MESSAGE_INDEX, message_index_length))
 
         _, channel_id = cls._parse_uint16(bytes_reader)
         _, records = cls._parse_array(bytes_reader, lambda file: cls._parse_tuple(file, "timestamp", "uint64"))
@@ -336,8 +375,8 @@ class McapRecordParser:
         if (record_type := file.read(1)) != b'\x08':
             raise MalformedMCAP(f'Unexpected record type ({record_type}).')
 
-        _, record_length = cls._parse_uint64(file)
-        bytes_reader = BytesReader(file.read(record_length))
+        record_length = cls._parse_record_length(file)
+        bytes_reader = BytesReader(cls._read_record_data(file, RecordType.CHUNK_INDEX, record_length))
 
         _, message_start_time = cls._parse_timestamp(bytes_reader)
         _, message_end_time = cls._parse_timestamp(bytes_reader)
@@ -367,8 +406,8 @@ class McapRecordParser:
         if (record_type := file.read(1)) != b'\x09':
             raise MalformedMCAP(f'Unexpected record type ({record_type}).')
 
-        _, record_length = cls._parse_uint64(file)
-        bytes_reader = BytesReader(file.read(record_length))
+        record_length = cls._parse_record_length(file)
+        bytes_reader = BytesReader(cls._read_record_data(file, RecordType.ATTACHMENT, record_length))
 
         _, log_time = cls._parse_timestamp(bytes_reader)
         _, create_time = cls._parse_timestamp(bytes_reader)
@@ -386,8 +425,8 @@ class McapRecordParser:
         if (record_type := file.read(1)) != b'\x0C':
             raise MalformedMCAP(f'Unexpected record type ({record_type}).')
 
-        _, record_length = cls._parse_uint64(file)
-        bytes_reader = BytesReader(file.read(record_length))
+        record_length = cls._parse_record_length(file)
+        bytes_reader = BytesReader(cls._read_record_data(file, RecordType.METADATA, record_length))
 
         _, name = cls._parse_string(bytes_reader)
         logger.debug(f'Parsing metadata for {name}...')
@@ -401,7 +440,7 @@ class McapRecordParser:
         if (record_type := file.read(1)) != b'\x0f':
             raise MalformedMCAP(f'Unexpected record type ({record_type}).')
 
-        _, record_length = cls._parse_uint64(file)
+        record_length = cls._parse_record_length(file)
         bytes_reader = BytesReader(file.read(record_length))
 
         _, data_section_crc = cls._parse_uint32(bytes_reader)
@@ -413,8 +452,8 @@ class McapRecordParser:
         if (record_type := file.read(1)) != b'\x0A':
             raise MalformedMCAP(f'Unexpected record type ({record_type}).')
 
-        _, record_length = cls._parse_uint64(file)
-        bytes_reader = BytesReader(file.read(record_length))
+        record_length = cls._parse_record_length(file)
+        bytes_reader = BytesReader(cls._read_record_data(file, RecordType.ATTACHMENT_INDEX, record_length))
 
         _, offset = cls._parse_uint64(bytes_reader)
         _, length = cls._parse_uint64(bytes_reader)
@@ -440,8 +479,8 @@ class McapRecordParser:
         if (record_type := file.read(1)) != b'\x0D':
             raise MalformedMCAP(f'Unexpected record type ({record_type}).')
 
-        _, record_length = cls._parse_uint64(file)
-        bytes_reader = BytesReader(file.read(record_length))
+        record_length = cls._parse_record_length(file)
+        bytes_reader = BytesReader(cls._read_record_data(file, RecordType.METADATA_INDEX, record_length))
 
         _, offset = cls._parse_uint64(bytes_reader)
         _, length = cls._parse_uint64(bytes_reader)
@@ -455,8 +494,8 @@ class McapRecordParser:
         if (record_type := file.read(1)) != b'\x0B':
             raise MalformedMCAP(f'Unexpected record type ({record_type}).')
 
-        _, record_length = cls._parse_uint64(file)
-        bytes_reader = BytesReader(file.read(record_length))
+        record_length = cls._parse_record_length(file)
+        bytes_reader = BytesReader(cls._read_record_data(file, RecordType.STATISTICS, record_length))
 
         _, message_count = cls._parse_uint64(bytes_reader)
         _, schema_count = cls._parse_uint16(bytes_reader)
@@ -486,8 +525,8 @@ class McapRecordParser:
         if (record_type := file.read(1)) != b'\x0E':
             raise MalformedMCAP(f'Unexpected record type ({record_type}).')
 
-        _, record_length = cls._parse_uint64(file)
-        bytes_reader = BytesReader(file.read(record_length))
+        record_length = cls._parse_record_length(file)
+        bytes_reader = BytesReader(cls._read_record_data(file, RecordType.SUMMARY_OFFSET, record_length))
 
         _, group_opcode = cls._parse_uint8(bytes_reader)
         _, group_start = cls._parse_uint64(bytes_reader)
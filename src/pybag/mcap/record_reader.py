@@ -1,14 +1,24 @@
+import bisect
 import heapq
+import itertools
 import logging
+import struct
 from abc import ABC, abstractmethod
+from dataclasses import dataclass
 from functools import lru_cache
 from pathlib import Path
-from typing import Generator, Iterator, Literal
+from typing import Any, BinaryIO, Generator, Iterator, Literal, NoReturn
 
-from pybag.io.raw_reader import BaseReader, BytesReader, FileReader
-from pybag.mcap.chunk import decompress_chunk
-from pybag.mcap.crc import assert_crc
+from pybag.io.raw_reader import (
+    BaseReader,
+    BytesReader,
+    FileReader,
+    StreamReader
+)
+from pybag.mcap.chunk import ZSTD_DICTIONARY_ATTACHMENT, decompress_chunk
+from pybag.mcap.crc import assert_attachment_crc
 from pybag.mcap.error import (
+    McapConcatenatedError,
     McapNoChunkError,
     McapNoChunkIndexError,
     McapNoStatisticsError,
@@ -18,10 +28,14 @@ from pybag.mcap.error import (
     McapUnknownSchemaError
 )
 from pybag.mcap.record_parser import (
+    DATA_END_SIZE,
     FOOTER_SIZE,
+    MAGIC_BYTES,
     MAGIC_BYTES_SIZE,
+    MalformedMCAP,
     McapRecordParser,
-    McapRecordType
+    McapRecordType,
+    McapUnexpectedEofError
 )
 from pybag.mcap.records import (
     AttachmentIndexRecord,
@@ -29,6 +43,7 @@ from pybag.mcap.records import (
     ChannelRecord,
     ChunkIndexRecord,
     ChunkRecord,
+    DataEndRecord,
     FooterRecord,
     HeaderRecord,
     MessageIndexRecord,
@@ -36,15 +51,169 @@ from pybag.mcap.records import (
     MetadataIndexRecord,
     MetadataRecord,
     SchemaRecord,
-    StatisticsRecord
+    StatisticsRecord,
+    SummaryOffsetRecord
 )
 from pybag.mcap.summary import McapChunkedSummary, McapNonChunkedSummary
 
 logger = logging.getLogger(__name__)
 
 
+def _parse_in_log_time_order(
+    reader: BaseReader,
+    entries: list[tuple[int, int, int]],
+    *,
+    in_reverse: bool = False,
+) -> Generator[MessageRecord, None, None]:
+    """Parse messages in (log_time, channel_id, sequence) order.
+
+    Args:
+        reader: The reader holding the messages.
+        entries: The (timestamp, channel_id, offset) of each message. Sorted in place.
+        in_reverse: If True, yield messages in reverse order.
+
+    Yields:
+        MessageRecord objects. Messages on the same channel with the same log time
+        are parsed together to order them by sequence number (then by offset).
+    """
+    entries.sort(reverse=in_reverse)
+    for _, group in itertools.groupby(entries, key=lambda x: (x[0], x[1])):
+        messages = []
+        for _, _, offset in group:
+            _ = reader.seek_from_start(offset)
+            messages.append(McapRecordParser.parse_message(reader))
+        if len(messages) > 1:
+            messages.sort(key=lambda m: m.sequence, reverse=in_reverse)
+        yield from messages
+
+
+def _find_appended_mcap(file: BaseReader) -> int | None:
+    """Find the start of an MCAP file appended to the end of another.
+
+    Only the record headers of the first file are read, up to its footer.
+    Record payloads are skipped, so MCAP files embedded in them are not found.
+
+    Returns:
+        The offset of the second file's magic bytes, or None if there is none.
+    """
+    file.seek_from_start(MAGIC_BYTES_SIZE)
+    try:
+        while (record_type := McapRecordParser.peek_record(file)) not in (0, McapRecordType.FOOTER):
+            McapRecordParser.skip_record(file)
+        if record_type == 0:
+            return None
+        McapRecordParser.skip_record(file)
+        McapRecordParser.parse_magic_bytes(file)
+    except (MalformedMCAP, struct.error):
+        return None  # Left to the summary to report
+
+    # The trailing magic of one file is directly followed by the leading magic of the next
+    offset = file.tell()
+    if file.read(MAGIC_BYTES_SIZE) != MAGIC_BYTES:
+        return None
+    if McapRecordParser.peek_record(file) != McapRecordType.HEADER:
+        return None
+    return offset
+
+
+def _assert_not_concatenated(file: BaseReader) -> None:
+    """Raise if the file is several MCAP files concatenated together.
+
+    The footer is only read from the end of the file, so the data of all but
+    the last file would otherwise be silently ignored. The summary section
+    runs up to the footer, so the records of the file are only walked when
+    the summary records starting at the footer's summary offset do not end there.
+
+    Raises:
+        McapConcatenatedError: If a second MCAP file starts after the first one's footer.
+    """
+    try:
+        footer_start = file.seek_from_end(FOOTER_SIZE + MAGIC_BYTES_SIZE)
+        footer = McapRecordParser.parse_footer(file)
+    except MalformedMCAP:
+        return  # Left to the summary to report
+    if footer.summary_start:
+        file.seek_from_start(footer.summary_start)
+        while McapRecordParser.peek_record(file) not in (0, McapRecordType.FOOTER):
+            McapRecordParser.skip_record(file)
+        if file.tell() == footer_start:
+            return
+    if (offset := _find_appended_mcap(file)) is not None:
+        raise McapConcatenatedError(
+            f'Concatenated MCAP detected: a second file starts at offset {offset}. '
+            'Split the file and read the parts with McapMultipleFileReader.'
+        )
+
+
+def read_summary_offsets(file: BaseReader) -> list[SummaryOffsetRecord]:
+    """Read the summary offset records the footer points to.
+
+    Returns:
+        The summary offsets, or an empty list if the file has none.
+    """
+    file.seek_from_end(FOOTER_SIZE + MAGIC_BYTES_SIZE)
+    footer = McapRecordParser.parse_footer(file)
+    if not footer.summary_offset_start:
+        return []
+    file.seek_from_start(footer.summary_offset_start)
+    offsets = []
+    while McapRecordParser.peek_record(file) == McapRecordType.SUMMARY_OFFSET:
+        offsets.append(McapRecordParser.parse_summary_offset(file))
+    return offsets
+
+
+def read_statistics(file: BaseReader) -> StatisticsRecord | None:
+    """Read only the statistics record from the summary section.
+
+    The statistics group is found through the summary offsets, so the
+    schemas, channels and chunk indexes are never parsed. Without summary
+    offsets the summary section is scanned for the statistics record.
+
+    Returns:
+        The statistics record, or None if the summary has none.
+    """
+    if offsets := read_summary_offsets(file):
+        for offset in offsets:
+            if offset.group_opcode == McapRecordType.STATISTICS:
+                file.seek_from_start(offset.group_start)
+                return McapRecordParser.parse_statistics(file)
+        return None
+
+    file.seek_from_end(FOOTER_SIZE + MAGIC_BYTES_SIZE)
+    if not (summary_start := McapRecordParser.parse_footer(file).summary_start):
+        return None
+    file.seek_from_start(summary_start)
+    while (record_type := McapRecordParser.peek_record(file)) not in (
+        0, McapRecordType.SUMMARY_OFFSET, McapRecordType.FOOTER
+    ):
+        if record_type == McapRecordType.STATISTICS:
+            return McapRecordParser.parse_statistics(file)
+        McapRecordParser.skip_record(file)
+    return None
+
+
+@dataclass(slots=True)
+class ValidationIssue:
+    severity: Literal['error', 'warning']
+    message: str
+
+
+@dataclass(slots=True)
+class FileLayout:
+    file_size: int
+    data_section_start: int
+    data_section_end: int  # The summary start, or the footer if there is no summary
+    summary_start: int  # 0 if there is no summary section
+    summary_size: int  # Includes the summary offset section
+    chunk_compressed_size: int
+    chunk_uncompressed_size: int
+
+
 # TODO: Is this the minimal set of methods needed?
 class BaseMcapRecordReader(ABC):
+    _file: BaseReader
+    _check_crc: bool
+
     @abstractmethod
     def __enter__(self) -> 'BaseMcapRecordReader':
         ...  # pragma: no cover
@@ -68,6 +237,11 @@ class BaseMcapRecordReader(ABC):
         """Get the footer record from the MCAP file."""
         ...  # pragma: no cover
 
+    @abstractmethod
+    def get_data_end(self) -> DataEndRecord | None:
+        """Get the data end record from the MCAP file."""
+        ...  # pragma: no cover
+
     @abstractmethod
     def get_statistics(self) -> StatisticsRecord:
         """Get the statistics record from the MCAP file."""
@@ -146,6 +320,10 @@ class BaseMcapRecordReader(ABC):
     ) -> MessageRecord | None:
         ...  # pragma: no cover
 
+    @abstractmethod
+    def get_nearest_message(self, channel_id: int, timestamp: int) -> MessageRecord | None:
+        ...  # pragma: no cover
+
     @abstractmethod
     def get_messages(
         self,
@@ -197,6 +375,136 @@ class BaseMcapRecordReader(ABC):
         """
         ...  # pragma: no cover
 
+    # Layout
+
+    def get_layout(self) -> FileLayout:
+        """Get the sizes and offsets of the sections of the MCAP file.
+
+        The chunk sizes are totalled from the chunk indexes.
+        """
+        file_size = self._file.size()
+        footer_start = file_size - MAGIC_BYTES_SIZE - FOOTER_SIZE
+        summary_start = self.get_footer().summary_start
+        chunk_indexes = self.get_chunk_indexes()
+        return FileLayout(
+            file_size=file_size,
+            data_section_start=MAGIC_BYTES_SIZE,
+            data_section_end=summary_start or footer_start,
+            summary_start=summary_start,
+            summary_size=footer_start - summary_start if summary_start else 0,
+            chunk_compressed_size=sum(c.compressed_size for c in chunk_indexes),
+            chunk_uncompressed_size=sum(c.uncompressed_size for c in chunk_indexes),
+        )
+
+    def get_summary_offsets(self) -> list[SummaryOffsetRecord]:
+        """Get the summary offset records, which locate each group of summary records."""
+        return read_summary_offsets(self._file)
+
+    # Records
+
+    def get_records(self) -> Generator[tuple[McapRecordType, Any], None, None]:
+        """Iterate over every record in the MCAP file in file order.
+
+        Each chunk is followed by the records it contains. Iteration stops
+        after the footer.
+
+        Returns:
+            Generator yielding (record_type, record) tuples.
+        """
+        position = MAGIC_BYTES_SIZE
+        while True:
+            # Other reads may move the file between records
+            self._file.seek_from_start(position)
+            if (record_type := McapRecordParser.peek_record(self._file)) == 0:
+                return  # Truncated file without a footer
+            record = McapRecordParser._parse_record(record_type, self._file)
+            position = self._file.tell()
+            if record is None:
+                continue  # Schema records with ID 0 are ignored
+            yield McapRecordType(record_type), record
+
+            if record_type == McapRecordType.FOOTER:
+                return
+            if record_type == McapRecordType.CHUNK:
+                chunk_reader = BytesReader(self._decompress_chunk(record))
+                for inner_type, inner_record in McapRecordParser.parse_record(chunk_reader):
+                    if inner_record is not None:
+                        yield McapRecordType(inner_type), inner_record
+
+    def _decompress_chunk(self, chunk: ChunkRecord) -> bytes:
+        return decompress_chunk(chunk, check_crc=self._check_crc)
+
+    # Validation
+
+    def validate(self) -> list[ValidationIssue]:
+        """Check the structural invariants of the MCAP file.
+
+        Checks that the file starts and ends with the magic bytes, that every
+        channel references an existing schema, that chunk indexes point at
+        chunk records, and that the statistics agree with the channel message
+        counts and time bounds.
+
+        Returns:
+            The issues found, empty if the file is valid.
+        """
+        issues: list[ValidationIssue] = []
+
+        for name in ['start', 'end']:
+            if name == 'start':
+                self._file.seek_from_start(0)
+            else:
+                self._file.seek_from_end(MAGIC_BYTES_SIZE)
+            try:
+                McapRecordParser.parse_magic_bytes(self._file)
+            except MalformedMCAP as e:
+                issues.append(ValidationIssue('error', f'{e} at the {name} of the file'))
+
+        schemas = self.get_schemas()
+        for channel in self.get_channels().values():
+            if channel.schema_id != 0 and channel.schema_id not in schemas:
+                issues.append(ValidationIssue(
+                    'error', f'Channel {channel.id} ({channel.topic}) references unknown schema {channel.schema_id}'
+                ))
+
+        chunk_indexes = self.get_chunk_indexes()
+        for chunk_index in chunk_indexes:
+            self._file.seek_from_start(chunk_index.chunk_start_offset)
+            if (record_type := McapRecordParser.peek_record(self._file)) != McapRecordType.CHUNK:
+                issues.append(ValidationIssue(
+                    'error', f'Chunk index offset {chunk_index.chunk_start_offset} points at opcode {record_type:#04x}'
+                ))
+            if chunk_index.message_start_time > chunk_index.message_end_time:
+                issues.append(ValidationIssue(
+                    'error', f'Chunk at offset {chunk_index.chunk_start_offset} starts after it ends'
+                ))
+
+        try:
+            statistics = self.get_statistics()
+        except McapNoStatisticsError:
+            issues.append(ValidationIssue('warning', 'No statistics record'))
+            return issues
+
+        channel_total = sum(statistics.channel_message_counts.values())
+        if statistics.message_count != channel_total:
+            issues.append(ValidationIssue(
+                'error', f'Statistics message count {statistics.message_count} '
+                f'does not match the channel message counts ({channel_total})'
+            ))
+        if statistics.message_count > 0:
+            if statistics.message_start_time > statistics.message_end_time:
+                issues.append(ValidationIssue('error', 'Statistics message start time is after the end time'))
+            for chunk_index in chunk_indexes:
+                if chunk_index.uncompressed_size == 0:
+                    continue  # Empty chunks have no message times
+                if (
+                    chunk_index.message_start_time < statistics.message_start_time
+                    or chunk_index.message_end_time > statistics.message_end_time
+                ):
+                    issues.append(ValidationIssue(
+                        'error', f'Chunk at offset {chunk_index.chunk_start_offset} is outside the statistics time range'
+                    ))
+        return issues
+
 
 class McapChunkedReader(BaseMcapRecordReader):
     """Class to efficiently get records from a chunked MCAP file.
@@ -224,6 +532,7 @@ class McapChunkedReader(BaseMcapRecordReader):
 
         self._version = McapRecordParser.parse_magic_bytes(self._file)
         logger.debug(f'MCAP version: {self._version}')
+        _assert_not_concatenated(self._file)
 
         # Mcap summary abstraction
         self._summary = McapChunkedSummary(
@@ -239,6 +548,10 @@ class McapChunkedReader(BaseMcapRecordReader):
         # LRU cache for decompressed chunks (key: chunk_start_offset)
         self._decompress_chunk_cached = lru_cache(maxsize=chunk_cache_size)(self._decompress_chunk_impl)
 
+        # Dictionary for zstd chunks, loaded when the first zstd chunk is read
+        self._zstd_dictionary: bytes | None = None
+        self._zstd_dictionary_loaded = False
+
     # Helpful Constructors
 
     @staticmethod
@@ -301,6 +614,36 @@ class McapChunkedReader(BaseMcapRecordReader):
             chunk_cache_size=chunk_cache_size,
         )
 
+    @staticmethod
+    def from_stream(
+        stream: BinaryIO,
+        *,
+        enable_crc_check: bool = False,
+        enable_summary_reconstruction: Literal['never', 'missing', 'always'] = 'missing',
+        chunk_cache_size: int = 1,
+    ) -> 'McapChunkedReader':
+        """Create a new MCAP reader from a seekable binary stream.
+
+        Args:
+            stream: Seekable binary stream positioned at the start of the MCAP data
+            enable_crc_check: Whether to validate CRC values
+            enable_summary_reconstruction: Controls summary reconstruction behavior:
+                - 'missing': Reconstruct if summary is missing (default)
+                - 'never': Raise error if summary is missing
+                - 'always': Always reconstruct even if summary exists
+            chunk_cache_size: The number of decompressed chunks to store in memory at a time.
+
+        Returns:
+            A McapChunkedReader instance
+        """
+        logging.debug('Creating McapChunkedReader')
+        return McapChunkedReader(
+            StreamReader(stream),
+            enable_crc_check=enable_crc_check,
+            enable_summary_reconstruction=enable_summary_reconstruction,
+            chunk_cache_size=chunk_cache_size,
+        )
+
     # Destructors
 
     def close(self) -> None:
@@ -327,6 +670,17 @@ class McapChunkedReader(BaseMcapRecordReader):
         self._file.seek_from_end(FOOTER_SIZE + MAGIC_BYTES_SIZE)
         return McapRecordParser.parse_footer(self._file)
 
+    def get_data_end(self) -> DataEndRecord | None:
+        """Get the data end record from the MCAP file."""
+        footer = self.get_footer()
+        if footer.summary_start:
+            self._file.seek_from_start(footer.summary_start - DATA_END_SIZE)
+        else:
+            self._file.seek_from_end(DATA_END_SIZE + FOOTER_SIZE + MAGIC_BYTES_SIZE)
+        if McapRecordParser.peek_record(self._file) != McapRecordType.DATA_END:
+            return None
+        return McapRecordParser.parse_data_end(self._file)
+
     def get_statistics(self) -> StatisticsRecord:
         """Get the statistics record from the MCAP file."""
         if (record := self._summary.get_statistics()) is None:
@@ -514,8 +868,19 @@ class McapChunkedReader(BaseMcapRecordReader):
         """
         # Seek to the chunk and read it
         self._file.seek_from_start(chunk_offset)
-        chunk = McapRecordParser.parse_chunk(self._file)
-        return decompress_chunk(chunk, check_crc=self._check_crc)
+        return self._decompress_chunk(McapRecordParser.parse_chunk(self._file))
+
+    def _decompress_chunk(self, chunk: ChunkRecord) -> bytes:
+        if chunk.compression == 'zstd' and not self._zstd_dictionary_loaded:
+            self._zstd_dictionary = self._load_zstd_dictionary()
+            self._zstd_dictionary_loaded = True
+        return decompress_chunk(chunk, check_crc=self._check_crc, zstd_dictionary=self._zstd_dictionary)
+
+    def _load_zstd_dictionary(self) -> bytes | None:
+        """Get the zstd dictionary attached by the writer, if there is one."""
+        if ZSTD_DICTIONARY_ATTACHMENT not in self._summary.get_attachment_indexes():
+            return None
+        return self.get_attachments(ZSTD_DICTIONARY_ATTACHMENT)[0].data
 
     # Message Management
 
@@ -561,6 +926,51 @@ class McapChunkedReader(BaseMcapRecordReader):
                 return McapRecordParser.parse_message(reader)
         return None
 
+    def get_nearest_message(self, channel_id: int, timestamp: int) -> MessageRecord | None:
+        """
+        Get the message from a given channel whose log time is closest to a timestamp.
+
+        The message indexes are binary searched so only the chunk containing
+        the nearest message is decompressed. Ties are resolved in favour of
+        the earlier message.
+
+        Args:
+            channel_id: The ID of the channel.
+            timestamp: The timestamp to search around.
+
+        Returns:
+            A MessageRecord object or None if the channel has no messages.
+        """
+        def lower_bound(chunk_index: ChunkIndexRecord) -> int:
+            if timestamp < chunk_index.message_start_time:
+                return chunk_index.message_start_time - timestamp
+            if timestamp > chunk_index.message_end_time:
+                return timestamp - chunk_index.message_end_time
+            return 0
+
+        best: tuple[int, int, int, ChunkIndexRecord] | None = None  # (distance, log_time, offset, chunk)
+        for chunk_index in sorted(self.get_chunk_indexes(channel_id), key=lower_bound):
+            if best is not None and lower_bound(chunk_index) > best[0]:
+                break  # Remaining chunks cannot contain a closer message
+            message_index = self.get_message_index(chunk_index, channel_id)
+            if message_index is None or not message_index.records:
+                continue
+
+            records = message_index.records
+            position = bisect.bisect_left(records, (timestamp,))
+            for log_time, offset in records[max(position - 1, 0):position + 1]:
+                candidate = (abs(log_time - timestamp), log_time, offset, chunk_index)
+                if best is None or candidate[:2] < best[:2]:
+                    best = candidate
+
+        if best is None:
+            return None
+
+        _, _, offset, chunk_index = best
+        reader = BytesReader(self._decompress_chunk_cached(chunk_index.chunk_start_offset))
+        _ = reader.seek_from_start(offset)
+        return McapRecordParser.parse_message(reader)
+
     def get_messages(
         self,
         channel_id: int | list[int] | None = None,
@@ -691,25 +1101,20 @@ class McapChunkedReader(BaseMcapRecordReader):
             if not message_indexes:
                 continue
 
-            # Collect (timestamp, offset) tuples for sorting
-            entries: list[tuple[int, int]] = []
+            # Collect (timestamp, channel_id, offset) tuples for sorting
+            entries: list[tuple[int, int, int]] = []
             for message_index in message_indexes:
                 for timestamp, offset in message_index.records:
                     if start_timestamp is not None and timestamp < start_timestamp:
                         continue
                     if end_timestamp is not None and timestamp > end_timestamp:
                         continue
-                    entries.append((timestamp, offset))
+                    entries.append((timestamp, message_index.channel_id, offset))
             if not entries:
                 continue
 
-            # Sort by timestamp (and offset for determinism), reverse if needed
-            entries.sort(key=lambda x: (x[0], x[1]), reverse=in_reverse)
-
             reader = BytesReader(self._decompress_chunk_cached(chunk_index.chunk_start_offset))
-            for _, offset in entries:
-                reader.seek_from_start(offset)
-                yield McapRecordParser.parse_message(reader)
+            yield from _parse_in_log_time_order(reader, entries, in_reverse=in_reverse)
 
     def _get_messages_with_overlaps(
         self,
@@ -734,10 +1139,9 @@ class McapChunkedReader(BaseMcapRecordReader):
         """
 
         def chunk_message_iterator(
-            chunk_index_id: int,
             chunk_index: ChunkIndexRecord
-        ) -> Iterator[tuple[int, int, MessageRecord]]:
-            """Create an iterator that yields (timestamp, chunk_id, message) tuples for a chunk."""
+        ) -> Iterator[tuple[int, MessageRecord]]:
+            """Create an iterator that yields (chunk_offset, message) tuples for a chunk."""
             if channel_id_set is None:
                 # All channels in this chunk
                 message_indexes = self.get_message_indexes(chunk_index).values()
@@ -751,38 +1155,37 @@ class McapChunkedReader(BaseMcapRecordReader):
             if not message_indexes:
                 return
 
-            # Collect and sort message references for this chunk
-            # The records should already by sorted by timestamp + offset
-            message_refs = []
+            # Collect message references for this chunk
+            message_refs: list[tuple[int, int, int]] = []
             for message_index in message_indexes:
                 for timestamp, offset in message_index.records:
                     if start_timestamp is not None and timestamp < start_timestamp:
                         continue
                     if end_timestamp is not None and timestamp > end_timestamp:
                         continue
-                    message_refs.append((timestamp, offset))
-            message_refs.sort()  # Sort to make sure timestamps are in correct order
+                    message_refs.append((timestamp, message_index.channel_id, offset))
 
             if not message_refs:
                 return
 
             # Load the chunk once and parse messages as needed (using cache)
             reader = BytesReader(self._decompress_chunk_cached(chunk_index.chunk_start_offset))
-            message_refs_it = reversed(message_refs) if in_reverse else iter(message_refs)
-            for timestamp, offset in message_refs_it:
-                reader.seek_from_start(offset)
-                message = McapRecordParser.parse_message(reader)
-                yield timestamp, chunk_index_id, message
+            for message in _parse_in_log_time_order(reader, message_refs, in_reverse=in_reverse):
+                yield chunk_index.chunk_start_offset, message
 
         chunk_iterators = [
             iterator
-            for i, chunk_index in enumerate(chunks)
-            if (iterator := chunk_message_iterator(i, chunk_index)) is not None
+            for chunk_index in chunks
+            if (iterator := chunk_message_iterator(chunk_index)) is not None
         ]
-        # Sort by the timestamp and break ties with the order of the chunk
-        # For reverse, negate both timestamp and chunk_index_id to get descending order from heapq.merge
-        heapq_key = (lambda x: (-x[0], -x[1])) if in_reverse else (lambda x: (x[0], x[1]))
-        for _, _, message in heapq.merge(*chunk_iterators, key=heapq_key):
+        # Sort by (log_time, channel_id, sequence) as within a chunk and break the
+        # remaining ties with the position of the chunk in the file.
+        # For reverse, negate the whole key to get descending order from heapq.merge
+        if in_reverse:
+            heapq_key = lambda x: (-x[1].log_time, -x[1].channel_id, -x[1].sequence, -x[0])
+        else:
+            heapq_key = lambda x: (x[1].log_time, x[1].channel_id, x[1].sequence, x[0])
+        for _, message in heapq.merge(*chunk_iterators, key=heapq_key):
             yield message
 
     def _get_messages_write_order(
@@ -885,8 +1288,8 @@ class McapChunkedReader(BaseMcapRecordReader):
         for attachment_index in attachment_indexes_flat:
             _ = self._file.seek_from_start(attachment_index.offset)
             attachment = McapRecordParser.parse_attachment(self._file)
-            if self._check_crc and attachment.crc:  # If crc is 0, do not check
-                assert_crc(attachment.data, attachment.crc)
+            if self._check_crc:
+                assert_attachment_crc(attachment)
             attachments.append(attachment)
         else:
             _ = self._file.seek_from_start(current_pos)
@@ -961,6 +1364,7 @@ class McapNonChunkedReader(BaseMcapRecordReader):
         # Parse file structure
         self._version = McapRecordParser.parse_magic_bytes(self._file)
         logger.debug(f'MCAP version: {self._version}')
+        _assert_not_concatenated(self._file)
 
         self._summary: McapNonChunkedSummary = McapNonChunkedSummary(
             self._file,
@@ -987,7 +1391,8 @@ class McapNonChunkedReader(BaseMcapRecordReader):
             message_count = 0
             message_index: dict[int, dict[int, list[int]]] = {}
 
-            while (record_type := McapRecordParser.peek_record(self._file)) != McapRecordType.DATA_END:
+            # Stop at the end of the data section, or cleanly at the end of the data
+            while (record_type := McapRecordParser.peek_record(self._file)) not in (0, McapRecordType.DATA_END):
                 current_pos = self._file.tell()
                 try:
                     if record_type == McapRecordType.MESSAGE:
@@ -999,6 +1404,8 @@ class McapNonChunkedReader(BaseMcapRecordReader):
                     else:
                         # Skip non-message records in data section
                         McapRecordParser.skip_record(self._file)
+                except McapUnexpectedEofError:
+                    raise  # A truncated record, not the end of the data
                 except Exception as e:
                     logger.warning(f'Error parsing record at position {current_pos}: {e}')
                     break
@@ -1061,6 +1468,33 @@ class McapNonChunkedReader(BaseMcapRecordReader):
             enable_summary_reconstruction=enable_summary_reconstruction,
         )
 
+    @staticmethod
+    def from_stream(
+        stream: BinaryIO,
+        *,
+        enable_crc_check: bool = False,
+        enable_summary_reconstruction: Literal['never', 'missing', 'always'] = 'missing',
+    ) -> 'McapNonChunkedReader':
+        """Create a new MCAP reader from a seekable binary stream.
+
+        Args:
+            stream: Seekable binary stream positioned at the start of the MCAP data
+            enable_crc_check: Whether to validate CRC values
+            enable_summary_reconstruction: Controls summary reconstruction behavior:
+                - 'missing': Reconstruct if summary is missing (default)
+                - 'never': Raise error if summary is missing
+                - 'always': Always reconstruct even if summary exists
+
+        Returns:
+            A McapNonChunkedReader instance
+        """
+        logging.debug('Creating McapNonChunkedReader')
+        return McapNonChunkedReader(
+            StreamReader(stream),
+            enable_crc_check=enable_crc_check,
+            enable_summary_reconstruction=enable_summary_reconstruction,
+        )
+
     # Destructors
 
     def close(self) -> None:
@@ -1087,6 +1521,17 @@ class McapNonChunkedReader(BaseMcapRecordReader):
         _ = self._file.seek_from_end(FOOTER_SIZE + MAGIC_BYTES_SIZE)
         return McapRecordParser.parse_footer(self._file)
 
+    def get_data_end(self) -> DataEndRecord | None:
+        """Get the data end record from the MCAP file."""
+        footer = self.get_footer()
+        if footer.summary_start:
+            _ = self._file.seek_from_start(footer.summary_start - DATA_END_SIZE)
+        else:
+            _ = self._file.seek_from_end(DATA_END_SIZE + FOOTER_SIZE + MAGIC_BYTES_SIZE)
+        if McapRecordParser.peek_record(self._file) != McapRecordType.DATA_END:
+            return None
+        return McapRecordParser.parse_data_end(self._file)
+
     def get_statistics(self) -> StatisticsRecord:  # TODO: Also return None here?
         """Get the statistics record from the MCAP file."""
         if self._statistics is None:
@@ -1264,6 +1709,30 @@ class McapNonChunkedReader(BaseMcapRecordReader):
         _ = self._file.seek_from_start(offsets[0])
         return McapRecordParser.parse_message(self._file)
 
+    def get_nearest_message(self, channel_id: int, timestamp: int) -> MessageRecord | None:
+        """
+        Get the message from a given channel whose log time is closest to a timestamp.
+
+        Ties are resolved in favour of the earlier message.
+
+        Args:
+            channel_id: The ID of the channel.
+            timestamp: The timestamp to search around.
+
+        Returns:
+            A MessageRecord object or None if the channel has no messages.
+        """
+        if not (messages := self._message_indexes.get(channel_id)):
+            return None
+
+        log_times = sorted(messages)
+        position = bisect.bisect_left(log_times, timestamp)
+        candidates = log_times[max(position - 1, 0):position + 1]
+        nearest = min(candidates, key=lambda log_time: (abs(log_time - timestamp), log_time))
+
+        _ = self._file.seek_from_start(messages[nearest][0])
+        return McapRecordParser.parse_message(self._file)
+
     def get_messages(
         self,
         channel_id: int | list[int] | None = None,
@@ -1306,7 +1775,7 @@ class McapNonChunkedReader(BaseMcapRecordReader):
         logger.debug(f'Channels requested: {channels_to_process}')
 
         # Collect all matching message offsets with timestamps
-        entries: list[tuple[int, int]] = []
+        entries: list[tuple[int, int, int]] = []
         for cid in channels_to_process:
             logger.debug(f'{len(self._message_indexes[cid])} messages for channel {cid}')
             for timestamp, offsets in self._message_indexes[cid].items():
@@ -1316,16 +1785,15 @@ class McapNonChunkedReader(BaseMcapRecordReader):
                 if end_timestamp is not None and timestamp > end_timestamp:
                     continue
                 for offset in offsets:
-                    entries.append((timestamp, offset))
+                    entries.append((timestamp, cid, offset))
+        logger.debug(f'Found {len(entries)} messages')
 
         if in_log_time_order:
-            entries.sort(key=lambda x: (x[0], x[1]), reverse=in_reverse)
-        else:
-            entries.sort(key=lambda x: x[1], reverse=in_reverse)
-
-        logger.debug(f'Found {len(entries)} messages')
+            yield from _parse_in_log_time_order(self._file, entries, in_reverse=in_reverse)
+            return
 
-        for _, offset in entries:
+        entries.sort(key=lambda x: x[2], reverse=in_reverse)
+        for _, _, offset in entries:
             _ = self._file.seek_from_start(offset)
             yield McapRecordParser.parse_message(self._file)
 
@@ -1371,8 +1839,8 @@ class McapNonChunkedReader(BaseMcapRecordReader):
         for attachment_index in attachment_indexes_flat:
             _ = self._file.seek_from_start(attachment_index.offset)
             attachment = McapRecordParser.parse_attachment(self._file)
-            if self._check_crc and attachment.crc:  # If crc is 0, do not check
-                assert_crc(attachment.data, attachment.crc)
+            if self._check_crc:
+                assert_attachment_crc(attachment)
             attachments.append(attachment)
         else:
             _ = self._file.seek_from_start(current_pos)
@@ -1413,6 +1881,24 @@ class McapNonChunkedReader(BaseMcapRecordReader):
         return metadata_records
 
 
+def _raise_missing_summary(
+    error: McapNoSummarySectionError | McapNoSummaryIndexError,
+    enable_summary_reconstruction: Literal['never', 'missing', 'always'],
+) -> NoReturn:
+    """Raise the error for a missing summary that was not reconstructed."""
+    # Only raise if reconstruction is explicitly disabled
+    if enable_summary_reconstruction == 'never':
+        logger.error('Summary section missing and reconstruction is disabled')
+        raise NotImplementedError(
+            'Sequential readers are not implemented yet. '
+            'Use enable_summary_reconstruction="missing" to reconstruct summaries.'
+        ) from error
+    # This should never happen since 'missing' mode should reconstruct
+    # But if it does, provide helpful error message
+    logger.error(f'Unexpected error with reconstruction mode "{enable_summary_reconstruction}": {error}')
+    raise error
+
+
 class McapRecordReaderFactory:
     """Factory to create a McapFileSequentialReader or McapFileRandomAccessReader."""
 
@@ -1459,17 +1945,7 @@ class McapRecordReaderFactory:
                 enable_summary_reconstruction=enable_summary_reconstruction,
             )
         except (McapNoSummarySectionError, McapNoSummaryIndexError) as e:
-            # Only raise if reconstruction is explicitly disabled
-            if enable_summary_reconstruction == 'never':
-                logger.error('Summary section missing and reconstruction is disabled')
-                raise NotImplementedError(
-                    'Sequential readers are not implemented yet. '
-                    'Use enable_summary_reconstruction="missing" to reconstruct summaries.'
-                ) from e
-            # This should never happen since 'missing' mode should reconstruct
-            # But if it does, provide helpful error message
-            logger.error(f'Unexpected error with reconstruction mode "{enable_summary_reconstruction}": {e}')
-            raise
+            _raise_missing_summary(e, enable_summary_reconstruction)
 
     @staticmethod
     def from_bytes(
@@ -1513,14 +1989,52 @@ class McapRecordReaderFactory:
                 enable_summary_reconstruction=enable_summary_reconstruction,
             )
         except (McapNoSummarySectionError, McapNoSummaryIndexError) as e:
-            # Only raise if reconstruction is explicitly disabled
-            if enable_summary_reconstruction == 'never':
-                logger.error('Summary section missing and reconstruction is disabled')
-                raise NotImplementedError(
-                    'Sequential readers are not implemented yet. '
-                    'Use enable_summary_reconstruction="missing" to reconstruct summaries.'
-                ) from e
-            # This should never happen since 'missing' mode should reconstruct
-            # But if it does, provide helpful error message
-            logger.error(f'Unexpected error with reconstruction mode "{enable_summary_reconstruction}": {e}')
-            raise
+            _raise_missing_summary(e, enable_summary_reconstruction)
+
+    @staticmethod
+    def from_stream(
+        stream: BinaryIO,
+        *,
+        enable_crc_check: bool = False,
+        enable_summary_reconstruction: Literal['never', 'missing', 'always'] = 'missing',
+        chunk_cache_size: int = 1,
+    ) -> BaseMcapRecordReader:
+        """Create a new MCAP reader from a seekable binary stream.
+
+        The stream is read in place rather than loaded into memory.
+
+        Args:
+            stream: Seekable binary stream positioned at the start of the MCAP data
+            enable_crc_check: Whether to validate CRC values
+            enable_summary_reconstruction: Controls summary reconstruction behavior:
+                - 'missing': Reconstruct if summary is missing (default)
+                - 'never': Raise error if summary is missing
+                - 'always': Always reconstruct even if summary exists
+            chunk_cache_size: The number of decompressed chunks to store in memory at a time.
+
+        Returns:
+            Appropriate reader instance (chunked or non-chunked)
+
+        Raises:
+            NotImplementedError: If summary is missing and reconstruction is disabled
+        """
+        start = stream.tell()
+        try:
+            # Try to create a chunked reader first
+            return McapChunkedReader.from_stream(
+                stream,
+                enable_crc_check=enable_crc_check,
+                enable_summary_reconstruction=enable_summary_reconstruction,
+                chunk_cache_size=chunk_cache_size,
+            )
+        except McapNoChunkIndexError:
+            # If no chunks exist, use the non-chunked reader
+            logger.warning('No chunk indexes detected, using non-chunked reader')
+            stream.seek(start)
+            return McapNonChunkedReader.from_stream(
+                stream,
+                enable_crc_check=enable_crc_check,
+                enable_summary_reconstruction=enable_summary_reconstruction,
+            )
+        except (McapNoSummarySectionError, McapNoSummaryIndexError) as e:
+            _raise_missing_summary(e, enable_summary_reconstruction)
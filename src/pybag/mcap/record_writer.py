@@ -36,8 +36,13 @@ from pybag.mcap.summary import (
     McapSummaryFactory
 )
 
+# Range of levels accepted by zstd (negative levels trade ratio for speed)
+ZSTD_DEFAULT_LEVEL = 3
+ZSTD_MIN_LEVEL = -(1 << 17)
+ZSTD_MAX_LEVEL = zstd.MAX_COMPRESSION_LEVEL
 
-def _prepare_append_writer(writer: BaseWriter) -> CrcWriter:
+
+def _prepare_append_writer(writer: BaseWriter, compute_data_crc: bool = True) -> CrcWriter:
     """Seek to the start of the existing DATA_END and seed CRC from prior data."""
     # Read footer to locate the existing data end record
     _ = writer.seek_from_end(FOOTER_SIZE + MAGIC_BYTES_SIZE)
@@ -52,7 +57,9 @@ def _prepare_append_writer(writer: BaseWriter) -> CrcWriter:
     data_end = McapRecordParser.parse_data_end(writer)
     # Rewind to the start of the DataEnd record so new writes overwrite it
     writer.seek_from_current(-DATA_END_SIZE)
-    return CrcWriter(writer, initial_crc=data_end.data_section_crc)
+    # A zero CRC means the existing data was never checksummed, so keep it unset
+    enabled = compute_data_crc and data_end.data_section_crc != 0
+    return CrcWriter(writer, initial_crc=data_end.data_section_crc, enabled=enabled)
 
 
 class BaseMcapRecordWriter(ABC):
@@ -146,6 +153,8 @@ class McapNonChunkedWriter(BaseMcapRecordWriter):
         mode: Literal['w', 'a'] = 'w',
         summary: McapNonChunkedSummary,
         profile: str = "ros2",
+        library: str | None = None,
+        compute_data_crc: bool = True,
     ) -> None:
         """Initialize a non-chunked MCAP writer.
 
@@ -153,17 +162,22 @@ class McapNonChunkedWriter(BaseMcapRecordWriter):
             writer: The underlying writer to write binary data to.
             summary: Existing summary
             profile: The MCAP profile to use (default: "ros2").
+            library: The library written to the header (default: "pybag <version>").
+            compute_data_crc: Whether to compute the data section CRC (0 is written otherwise).
             has_file_start: File already contains magic bytes + header
         """
 
-        self._writer = CrcWriter(writer) if mode == 'w' else _prepare_append_writer(writer)
+        self._writer = (
+            CrcWriter(writer, enabled=compute_data_crc) if mode == 'w'
+            else _prepare_append_writer(writer, compute_data_crc)
+        )
         self._summary = summary
         self._profile = profile
 
         # Write file header
         if mode == 'w':
             McapRecordWriter.write_magic_bytes(self._writer)
-            header = HeaderRecord(profile=profile, library=f"pybag {__version__}")
+            header = HeaderRecord(profile=profile, library=library or f"pybag {__version__}")
             McapRecordWriter.write_header(self._writer, header)
 
     def __enter__(self) -> 'McapNonChunkedWriter':
@@ -248,7 +262,11 @@ class McapChunkedWriter(BaseMcapRecordWriter):
         mode: Literal['w', 'a'] = 'w',
         chunk_size: int,
         chunk_compression: Literal["none", "lz4", "zstd"] | None = "none",
+        compression_level: int | None = None,
         profile: str = "ros2",
+        library: str | None = None,
+        compute_data_crc: bool = True,
+        zstd_dictionary: bytes | None = None,
     ) -> None:
         """Initialize a chunked MCAP writer.
 
@@ -257,14 +275,23 @@ class McapChunkedWriter(BaseMcapRecordWriter):
             summary: Existing summary
             chunk_size: The size threshold for flushing chunks (in bytes).
             chunk_compression: Compression algorithm ("lz4" or "zstd").
+            compression_level: Compression level (default: 3 for zstd, library default for lz4).
             profile: The MCAP profile to use (default: "ros2").
+            library: The library written to the header (default: "pybag <version>").
+            compute_data_crc: Whether to compute the data section CRC (0 is written otherwise).
+            zstd_dictionary: Dictionary to compress every zstd chunk with.
             has_file_start: File already contains magic bytes + header
         """
-        self._writer = CrcWriter(writer) if mode == 'w' else _prepare_append_writer(writer)
+        self._writer = (
+            CrcWriter(writer, enabled=compute_data_crc) if mode == 'w'
+            else _prepare_append_writer(writer, compute_data_crc)
+        )
         self._summary = summary
         self._profile = profile
         self._chunk_size = chunk_size
         self._chunk_compression = "" if chunk_compression in ("none", None)  else chunk_compression
+        self._compression_level = compression_level
+        self._zstd_dictionary = zstd_dictionary
         self._compress_chunk = self._create_chunk_compressor()
 
         # Current chunk buffering
@@ -276,7 +303,7 @@ class McapChunkedWriter(BaseMcapRecordWriter):
         # Write file header
         if mode == 'w':
             McapRecordWriter.write_magic_bytes(self._writer)
-            header = HeaderRecord(profile=profile, library=f"pybag {__version__}")
+            header = HeaderRecord(profile=profile, library=library or f"pybag {__version__}")
             McapRecordWriter.write_header(self._writer, header)
 
     def __enter__(self) -> 'McapChunkedWriter':
@@ -288,9 +315,20 @@ class McapChunkedWriter(BaseMcapRecordWriter):
     def _create_chunk_compressor(self) -> Callable[[bytes], bytes]:
         """Create a compression function based on the configured algorithm."""
         if self._chunk_compression == "lz4":
-            return lz4.frame.compress
+            if self._compression_level is None:
+                return lz4.frame.compress
+            level = self._compression_level
+            return lambda x: lz4.frame.compress(x, compression_level=level)
         elif self._chunk_compression == "zstd":
-            return zstd.ZstdCompressor().compress
+            level = ZSTD_DEFAULT_LEVEL if self._compression_level is None else self._compression_level
+            if not ZSTD_MIN_LEVEL <= level <= ZSTD_MAX_LEVEL:
+                raise ValueError(
+                    f"zstd compression level must be between {ZSTD_MIN_LEVEL} and {ZSTD_MAX_LEVEL}: {level}"
+                )
+            if self._zstd_dictionary is not None:
+                dict_data = zstd.ZstdCompressionDict(self._zstd_dictionary)
+                return zstd.ZstdCompressor(level=level, dict_data=dict_data).compress
+            return zstd.ZstdCompressor(level=level).compress
         elif self._chunk_compression == "":
             return lambda x: x
         else:
@@ -447,7 +485,11 @@ class McapRecordWriterFactory:
         mode: Literal['w', 'a'] = 'w',
         chunk_size: int | None = None,
         chunk_compression: Literal["none", "lz4", "zstd"] | None = "none",
+        compression_level: int | None = None,
         profile: str = "ros2",
+        library: str | None = None,
+        compute_data_crc: bool = True,
+        zstd_dictionary: bytes | None = None,
     ) -> BaseMcapRecordWriter:
         """Create an appropriate MCAP record writer based on configuration.
 
@@ -456,7 +498,11 @@ class McapRecordWriterFactory:
             chunk_size: If provided, creates a chunked writer with this size threshold.
                        If None, creates a non-chunked writer.
             chunk_compression: Compression algorithm for chunks ("lz4" or "zstd").
+            compression_level: Compression level for chunks (ignored without chunking).
             profile: The MCAP profile to use (default: "ros2").
+            library: The library written to the header (default: "pybag <version>").
+            compute_data_crc: Whether to compute the data section CRC.
+            zstd_dictionary: Dictionary to compress zstd chunks with (ignored without chunking).
 
         Returns:
             A BaseMcapRecordWriter instance (either chunked or non-chunked).
@@ -468,7 +514,9 @@ class McapRecordWriterFactory:
                 writer,
                 mode=mode,
                 profile=profile,
+                library=library,
                 summary=summary,
+                compute_data_crc=compute_data_crc,
             )
         elif isinstance(summary, McapChunkedSummary):
             # For chunked writer, use provided chunk_size or default
@@ -480,7 +528,11 @@ class McapRecordWriterFactory:
                 summary=summary,
                 chunk_size=chunk_size,
                 chunk_compression=chunk_compression,
+                compression_level=compression_level,
                 profile=profile,
+                library=library,
+                compute_data_crc=compute_data_crc,
+                zstd_dictionary=zstd_dictionary,
             )
         else:
             raise ValueError(f"Unknown summary type: {type(summary)}")
@@ -208,9 +208,6 @@ class McapChunkedSummary(McapSummary):
             # TODO: What do we do if the summary is incomplete?
             logging.debug('Loading summary from summary section')
             self._load_summary()
-            if self._cached_statistics is None and self._load_summary_eagerly:
-                logging.warning('Statistics record not found in summary, generating')
-                self._cached_statistics = self.get_statistics()
 
         # After loading summary, if we have records, then we assume they are complete
         # and we disable searching through the file for more records of the same type
@@ -220,6 +217,10 @@ class McapChunkedSummary(McapSummary):
         self._has_searched_attachments = len(self._cached_attachment_indexes) > 0
         self._has_searched_metadata = len(self._cached_metadata_indexes) > 0
 
+        if self._cached_statistics is None and self._load_summary_eagerly:
+            logging.warning('Statistics record not found in summary, generating')
+            self._cached_statistics = self.get_statistics()
+
         # If not chunk index records are found, then we cannot use this class
         # TODO: For MCAP files that do not have chunks, this could be slow
         if not self.get_chunk_indexes() and self._file:
@@ -781,6 +782,12 @@ class McapChunkedSummary(McapSummary):
             logging.warning('Statistics: file not initialized!')
             return None
 
+        # Chunk and message indexes are much cheaper to go through than the data section
+        if self._has_summary and self._cached_chunk_indexes:
+            logging.warning('No statistics record found in summary. Recreating from chunk indexes!')
+            self._cached_statistics = self._statistics_from_chunk_indexes()
+            return self._cached_statistics
+
         # Track message statistics
         chunk_count = 0
         message_count = 0
@@ -872,6 +879,37 @@ class McapChunkedSummary(McapSummary):
         )
         return self._cached_statistics
 
+    def _statistics_from_chunk_indexes(self) -> StatisticsRecord:
+        """Build a statistics record from the chunk indexes in the summary section.
+
+        Per-channel message counts come from the message index records following
+        each chunk, or from the chunk itself if these are missing.
+        """
+        assert self._file is not None
+
+        channel_message_counts: dict[ChannelId, int] = defaultdict(int)
+        for chunk_index in self._cached_chunk_indexes:
+            if chunk_index.message_index_offsets:
+                for channel_id, message_index_offset in chunk_index.message_index_offsets.items():
+                    _ = self._file.seek_from_start(message_index_offset)
+                    message_index = McapRecordParser.parse_message_index(self._file)
+                    channel_message_counts[channel_id] += len(message_index.records)
+            else:
+                for channel_id, message_index in self.get_message_indexes(chunk_index).items():
+                    channel_message_counts[channel_id] += len(message_index.records)
+
+        return StatisticsRecord(
+            message_count=sum(channel_message_counts.values()),
+            schema_count=len(self.get_schemas()),
+            channel_count=len(self.get_channels()),
+            attachment_count=sum(len(v) for v in self.get_attachment_indexes().values()),
+            metadata_count=sum(len(v) for v in self.get_metadata_indexes().values()),
+            chunk_count=len(self._cached_chunk_indexes),
+            message_start_time=min(ci.message_start_time for ci in self._cached_chunk_indexes),
+            message_end_time=max(ci.message_end_time for ci in self._cached_chunk_indexes),
+            channel_message_counts=dict(channel_message_counts),
+        )
+
     def get_attachment_indexes(self) -> dict[str, list[AttachmentIndexRecord]]:
         """Get all attachment indexes from the MCAP file.
 
@@ -1351,14 +1389,14 @@ class McapNonChunkedSummary(McapSummary):
         # because once we have, the cache will no longer be None.
 
         # Track message statistics
-        schema_count = 0
-        channel_count = 0
         message_count = 0
         attachment_count = 0
         metadata_count = 0
         message_start_time: int | None = None
         message_end_time: int | None = None
         channel_message_counts: dict[ChannelId, int] = defaultdict(int)
+        schema_ids: set[SchemaId] = set()
+        channel_ids: set[ChannelId] = set()
 
         # Seek to start of data section (after magic bytes)
         _ = self._file.seek_from_start(MAGIC_BYTES_SIZE)
@@ -1366,12 +1404,13 @@ class McapNonChunkedSummary(McapSummary):
         # Iterate through records until we reach the footer
         logging.warning('No statistics record found in summary. Recreating from data section!')
         while (record_type := McapRecordParser.peek_record(self._file)) != McapRecordType.FOOTER:
+            # Schemas and channels are repeated in the summary section so count unique ids
             if record_type == McapRecordType.SCHEMA:
-                schema_count += 1
-                McapRecordParser.skip_record(self._file)
+                if schema := McapRecordParser.parse_schema(self._file):
+                    schema_ids.add(schema.id)
             elif record_type == McapRecordType.CHANNEL:
-                channel_count += 1
-                McapRecordParser.skip_record(self._file)
+                channel = McapRecordParser.parse_channel(self._file)
+                channel_ids.add(channel.id)
             elif record_type == McapRecordType.MESSAGE:
                 # Parse message to extract statistics
                 message = McapRecordParser.parse_message(self._file)
@@ -1397,14 +1436,14 @@ class McapNonChunkedSummary(McapSummary):
         # Create statistics record from collected data
         self._cached_statistics = StatisticsRecord(
             message_count=message_count,
-            schema_count=schema_count,
-            channel_count=channel_count,
+            schema_count=len(schema_ids),
+            channel_count=len(channel_ids),
             attachment_count=attachment_count,
             metadata_count=metadata_count,
             chunk_count=0,  # Non-chunked files have no chunks
             message_start_time=message_start_time or 0,
             message_end_time=message_end_time or 0,
-            channel_message_counts=channel_message_counts,
+            channel_message_counts=dict(channel_message_counts),
         )
         return self._cached_statistics
 
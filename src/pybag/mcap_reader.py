@@ -1,37 +1,174 @@
 import fnmatch
+import gzip
 import heapq
+import json
 import logging
-from collections.abc import Generator
-from dataclasses import dataclass
+import lzma
+import re
+from collections.abc import Collection, Generator, Iterable
+from dataclasses import asdict, dataclass, fields, is_dataclass
 from pathlib import Path
 from types import TracebackType
-from typing import Any, Callable
-
-from pybag.deserialize import MessageDeserializerFactory
-from pybag.mcap.error import McapUnknownEncodingError, McapUnknownTopicError
+from typing import Any, BinaryIO, Callable, Literal
+
+from pybag.deserialize import MessageDeserializer, MessageDeserializerFactory
+from pybag.io.raw_reader import FileReader
+from pybag.io.raw_writer import FileWriter
+from pybag.mcap.error import (
+    McapDecodeError,
+    McapMessageTooLargeError,
+    McapNoStatisticsError,
+    McapUnknownEncodingError,
+    McapUnknownSchemaError,
+    McapUnknownTopicError
+)
 from pybag.mcap.record_reader import (
     BaseMcapRecordReader,
-    McapRecordReaderFactory
+    McapRecordReaderFactory,
+    read_statistics
 )
+from pybag.mcap.record_writer import McapRecordWriterFactory
 from pybag.mcap.records import (
     AttachmentRecord,
     ChannelRecord,
+    FooterRecord,
+    HeaderRecord,
+    MessageRecord,
     MetadataRecord,
-    SchemaRecord
+    SchemaRecord,
+    StatisticsRecord,
+    SummaryOffsetRecord
+)
+from pybag.mcap.summary import McapSummaryFactory
+from pybag.schema import Schema, normalize_message_name
+from pybag.schema.field_path import (
+    FieldPathError,
+    check_field_path,
+    get_field,
+    parse_field_path,
+    to_dict
+)
+from pybag.schema.structured import (
+    StructuredTypeError,
+    message_record,
+    schema_dtype
 )
 
 logger = logging.getLogger(__name__)
 
+# Message encodings that are decoded using the channel's schema
+SCHEMA_MESSAGE_ENCODINGS = {'ros2': 'cdr', 'ros1': 'ros1'}
+
+# Messages that combine_time_fields gives a __nanoseconds__ attribute
+TIME_MESSAGE_NAMES = {'builtin_interfaces/Time', 'builtin_interfaces/Duration'}
+
+# Magic bytes of the formats whole MCAP files may be compressed with
+FILE_COMPRESSION_MAGIC = {'gzip': b'\x1f\x8b', 'xz': b'\xfd7zXZ\x00'}
+
+
+def _detect_file_compression(file_path: Path | str) -> str:
+    """Detect whether a whole file is gzip or xz compressed from its magic bytes."""
+    with open(file_path, 'rb') as f:
+        start = f.read(max(len(magic) for magic in FILE_COMPRESSION_MAGIC.values()))
+    for compression, magic in FILE_COMPRESSION_MAGIC.items():
+        if start.startswith(magic):
+            return compression
+    return 'none'
+
+
+def _expand_topics(
+    available_topics: list[str],
+    topic: str | list[str] | None,
+    topic_regex: str | None = None,
+) -> list[str]:
+    """Expand topic patterns to list of concrete topic names.
+
+    Handles:
+    - Single topic string (may contain glob pattern like "/sensor/*")
+    - List of topic strings (each may contain glob patterns)
+    - Regular expression matched against the full topic name
+
+    Args:
+        available_topics: The topics to match against
+        topic: Topic pattern (string or list of strings)
+        topic_regex: Regular expression for topic names
+
+    Returns:
+        Sorted, deduplicated list of the available topics that match
+
+    Raises:
+        McapUnknownTopicError: If a glob pattern or the regular expression matches no topic.
+    """
+    if topic is None and topic_regex is None:
+        raise ValueError('Either topic or topic_regex must be given')
+
+    matched_topics = set()
+    if topic is not None:
+        topic_patterns = [topic] if isinstance(topic, str) else topic
+        for pattern in topic_patterns:
+            if not any(c in pattern for c in '*?['):
+                if pattern in available_topics:  # Plain topic name
+                    matched_topics.add(pattern)
+                continue
+            matches = fnmatch.filter(available_topics, pattern)
+            if not matches:
+                raise McapUnknownTopicError(f'No topic matches the pattern {pattern!r}')
+            matched_topics.update(matches)
+
+    if topic_regex is not None:
+        compiled = re.compile(topic_regex)
+        matches = [t for t in available_topics if compiled.fullmatch(t)]
+        if not matches:
+            raise McapUnknownTopicError(f'No topic matches the pattern {topic_regex!r}')
+        matched_topics.update(matches)
+    return sorted(matched_topics)
+
+
+def _combine_time_fields(value: Any) -> None:
+    """Set __nanoseconds__ on every Time and Duration message nested in a decoded message."""
+    if isinstance(value, list):
+        if value and is_dataclass(value[0]):
+            for item in value:
+                _combine_time_fields(item)
+        return
+    if not is_dataclass(value) or isinstance(value, type):
+        return
+    if normalize_message_name(getattr(value, '__msg_name__', '')) in TIME_MESSAGE_NAMES:
+        if hasattr(value, '__dict__'):
+            value.__nanoseconds__ = value.sec * 1_000_000_000 + value.nanosec
+        return
+    for field in fields(value):
+        _combine_time_fields(getattr(value, field.name))
+
 
 @dataclass(slots=True)
 class DecodedMessage():
     topic: str
-    msg_type: str
+    msg_type: str  # Empty for channels without a schema
     channel_id: int
     sequence: int
     log_time: int
     publish_time: int
     data: Any  # TODO: Figure out how to type this
+    decode_error: str | None = None  # Set when data holds the undecoded bytes
+    schema_encoding: str = ''  # e.g. "ros2msg" or "protobuf", empty for channels without a schema
+    message_encoding: str = ''  # The channel's encoding, e.g. "cdr" or "json"
+
+    @property
+    def schema_name(self) -> str:
+        """The name of the channel's schema (the same as msg_type)."""
+        return self.msg_type
+
+
+@dataclass(slots=True)
+class McapStatistics():
+    message_count: int
+    schema_count: int
+    channel_count: int
+    chunk_count: int
+    message_start_time: int
+    message_end_time: int
+    channel_message_counts: dict[str, int]  # Keyed by topic name
 
 
 class McapFileReader:
@@ -43,13 +180,37 @@ class McapFileReader:
         header = self._reader.get_header()
         self._profile = header.profile
         self._message_deserializer = MessageDeserializerFactory.from_profile(self._profile)
+        self._message_types: dict[str, Callable[..., Any]] = {}
 
     @staticmethod
     def from_file(
         file_path: Path | str,
         *,
         enable_crc_check: bool = False,
+        file_compression: Literal['auto', 'none', 'gzip', 'xz'] = 'auto',
     ) -> 'McapFileReader':
+        """Open an MCAP file.
+
+        Whole-file gzip or xz compression (e.g. recording.mcap.gz) is separate
+        from chunk compression. Such files are decompressed into memory.
+
+        Args:
+            file_path: Path to the MCAP file.
+            enable_crc_check: Whether to validate the CRC values.
+            file_compression: How the whole file is compressed. 'auto' detects
+                gzip and xz from the magic bytes.
+        """
+        if file_compression not in ('auto', 'none', 'gzip', 'xz'):
+            raise ValueError(f"file_compression must be 'auto', 'none', 'gzip' or 'xz', got: {file_compression}")
+        if file_compression == 'auto':
+            file_compression = _detect_file_compression(file_path)
+        if file_compression != 'none':
+            logger.debug(f'Decompressing {file_compression} file {file_path} into memory')
+            with open(file_path, 'rb') as f:
+                compressed = f.read()
+            data = gzip.decompress(compressed) if file_compression == 'gzip' else lzma.decompress(compressed)
+            return McapFileReader.from_bytes(data, enable_crc_check=enable_crc_check)
+
         reader = McapRecordReaderFactory.from_file(
             file_path,
             enable_crc_check=enable_crc_check,
@@ -68,14 +229,78 @@ class McapFileReader:
         )
         return McapFileReader(reader)
 
+    @staticmethod
+    def from_stream(
+        stream: BinaryIO,
+        *,
+        enable_crc_check: bool = False,
+    ) -> 'McapFileReader':
+        """Read MCAP data from a seekable binary stream, e.g. ``sys.stdin.buffer``.
+
+        The reader takes ownership of the stream and closes it when closed.
+
+        Args:
+            stream: Seekable binary stream positioned at the start of the MCAP data.
+            enable_crc_check: Whether to validate the CRC values.
+        """
+        reader = McapRecordReaderFactory.from_stream(
+            stream,
+            enable_crc_check=enable_crc_check,
+        )
+        return McapFileReader(reader)
+
     @property
     def profile(self) -> str:
         return self._profile
 
+    @property
+    def library(self) -> str:
+        """The library that wrote the MCAP file, e.g. "pybag 0.5.0"."""
+        return self._reader.get_header().library
+
+    @property
+    def header(self) -> HeaderRecord:
+        """The header record (profile and library) of the MCAP file."""
+        return self._reader.get_header()
+
+    @property
+    def footer(self) -> FooterRecord:
+        """The footer record (summary offsets and CRC) of the MCAP file."""
+        return self._reader.get_footer()
+
+    @property
+    def data_section_crc(self) -> int | None:
+        """The data section CRC from the data end record (0 if it was not computed).
+
+        Returns None if the file has no data end record.
+        """
+        if (data_end := self._reader.get_data_end()) is None:
+            return None
+        return data_end.data_section_crc
+
     def get_topics(self) -> list[str]:
         """Get all topics in the MCAP file."""
         return [c.topic for c in self._reader.get_channels().values()] # TODO: Use a set?
 
+    def get_topics_of_type(self, schema_name: str) -> list[str]:
+        """Get all topics whose messages have a given type.
+
+        Args:
+            schema_name: The message type name. Names with and without the
+                interface namespace (e.g. "sensor_msgs/msg/Image" and
+                "sensor_msgs/Image") are treated as equal.
+
+        Returns:
+            List of topic names with that message type.
+        """
+        wanted = normalize_message_name(schema_name)
+        topics = []
+        for channel_id, channel in self._reader.get_channels().items():
+            schema = self._reader.get_channel_schema(channel_id)
+            if schema is not None and normalize_message_name(schema.name) == wanted:
+                topics.append(channel.topic)
+        return topics
+
     def get_channels(self) -> list[ChannelRecord]:
         """Get all channels in the MCAP file.
 
@@ -91,13 +316,59 @@ class McapFileReader:
             topic: The topic name to get the schema for.
 
         Returns:
-            SchemaRecord for the topic, or None if not found.
+            SchemaRecord for the topic, or None if not found or the channel has no schema.
         """
         channel_id = self._reader.get_channel_id(topic)
         if channel_id is None:
             return None
         return self._reader.get_channel_schema(channel_id)
 
+    def _parse_topic_schema(self, topic: str) -> Schema:
+        channel_id = self._reader.get_channel_id(topic)
+        if channel_id is None:
+            raise McapUnknownTopicError(f'Topic {topic} not found in MCAP file')
+        channel_record = self._reader.get_channel(channel_id)
+        message_schema = self._reader.get_channel_schema(channel_id)
+        if channel_record is None or message_schema is None:
+            raise McapUnknownTopicError(f'No channel or schema for topic {topic}')
+        if (message_deserializer := self._get_deserializer(channel_record, message_schema)) is None:
+            raise McapUnknownEncodingError(
+                f'Cannot parse the schema of {channel_record.message_encoding} messages on {topic}'
+            )
+        schema, _ = message_deserializer.parse_schema(message_schema)
+        return schema
+
+    def get_defaults(self, topic: str) -> dict[str, Any]:
+        """Get the default values declared in the schema of a topic.
+
+        Args:
+            topic: The topic name.
+
+        Returns:
+            Dictionary mapping field names to their default values. Fields
+            without a default are omitted.
+        """
+        return self._parse_topic_schema(topic).defaults()
+
+    def get_constants(self, topic: str) -> dict[str, Any]:
+        """Get the constants declared in the schema of a topic.
+
+        Args:
+            topic: The topic name.
+
+        Returns:
+            Dictionary mapping constant names to their values.
+        """
+        return self._parse_topic_schema(topic).constants()
+
+    def get_schemas(self) -> dict[str, SchemaRecord]:
+        """Get all schemas in the MCAP file.
+
+        Returns:
+            Dictionary mapping schema names to SchemaRecord objects.
+        """
+        return {s.name: s for s in self._reader.get_schemas().values()}
+
     def get_message_count(self, topic: str) -> int:
         """Get the number of messages in a given topic."""
         channel_id = self._reader.get_channel_id(topic)
@@ -105,6 +376,148 @@ class McapFileReader:
             raise McapUnknownTopicError(f'Topic {topic} not found in MCAP file')
         return self._reader.get_statistics().channel_message_counts[channel_id]
 
+    def chunks_for_topic(self, topic: str) -> list[tuple[int, int, int, int]]:
+        """Get the chunks that contain messages on a topic.
+
+        This allows planning which parts of the file need to be read.
+
+        Args:
+            topic: The topic name.
+
+        Returns:
+            List of (message_start_time, message_end_time, chunk_start_offset,
+            compressed_size) tuples, one per chunk. Empty for unchunked files.
+        """
+        channel_ids = [
+            channel_id
+            for channel_id, channel in self._reader.get_channels().items()
+            if channel.topic == topic
+        ]
+        if not channel_ids:
+            raise McapUnknownTopicError(f'Topic {topic} not found in MCAP file')
+        return [
+            (
+                chunk_index.message_start_time,
+                chunk_index.message_end_time,
+                chunk_index.chunk_start_offset,
+                chunk_index.compressed_size,
+            )
+            for chunk_index in self._reader.get_chunk_indexes(channel_ids)
+        ]
+
+    def get_statistics(self) -> McapStatistics | None:
+        """Get the file statistics with message counts keyed by topic.
+
+        Returns:
+            The statistics, or None if the file has no statistics record.
+        """
+        try:
+            statistics = self._reader.get_statistics()
+        except McapNoStatisticsError:
+            return None
+
+        channels = self._reader.get_channels()
+        channel_message_counts: dict[str, int] = {}
+        for channel_id, count in statistics.channel_message_counts.items():
+            if (channel := channels.get(channel_id)) is None:
+                continue
+            channel_message_counts[channel.topic] = channel_message_counts.get(channel.topic, 0) + count
+
+        return McapStatistics(
+            message_count=statistics.message_count,
+            schema_count=statistics.schema_count,
+            channel_count=statistics.channel_count,
+            chunk_count=statistics.chunk_count,
+            message_start_time=statistics.message_start_time,
+            message_end_time=statistics.message_end_time,
+            channel_message_counts=channel_message_counts,
+        )
+
+    @staticmethod
+    def read_statistics(file_path: str | Path) -> StatisticsRecord | None:
+        """Read only the statistics record of an MCAP file.
+
+        Much faster than opening a reader for large files, as the statistics
+        are found through the summary offsets and the chunk indexes are never
+        parsed. The channel message counts are keyed by channel ID.
+
+        Args:
+            file_path: The path to the MCAP file.
+
+        Returns:
+            The statistics record, or None if the file's summary has none.
+        """
+        with FileReader(file_path) as file:
+            return read_statistics(file)
+
+    def get_summary_offsets(self) -> list[SummaryOffsetRecord]:
+        """Get the summary offset records of the MCAP file.
+
+        Returns:
+            The offset and length of each group of summary records, or an
+            empty list if the file has no summary offsets.
+        """
+        return self._reader.get_summary_offsets()
+
+    def message_count_by_channel(self) -> dict[str, int]:
+        """Get the number of messages on each topic.
+
+        The counts are read from the statistics record. If the file has none,
+        the messages are counted without being decoded.
+
+        Returns:
+            Dictionary mapping topic names to message counts.
+        """
+        if (statistics := self.get_statistics()) is not None:
+            return statistics.channel_message_counts
+
+        channels = self._reader.get_channels()
+        counts = {channel.topic: 0 for channel in channels.values()}
+        for msg in self._reader.get_messages(in_log_time_order=False):
+            if (channel := channels.get(msg.channel_id)) is not None:
+                counts[channel.topic] += 1
+        return counts
+
+    def get_layout(self) -> dict[str, int]:
+        """Get the sizes of the data, summary and chunk sections of the MCAP file.
+
+        Useful for diagnosing how effective the chunk compression is.
+
+        Returns:
+            Dictionary with the "file_size", "data_section_start",
+            "data_section_end" (the summary start, or the footer start if there
+            is no summary), "summary_start" (0 if there is no summary),
+            "summary_size", and the total "chunk_compressed_size" and
+            "chunk_uncompressed_size" from the chunk indexes.
+        """
+        return asdict(self._reader.get_layout())
+
+    def iter_records(self) -> Generator[dict[str, Any], None, None]:
+        """Iterate over every record in the MCAP file for inspection.
+
+        Records are returned in file order, including schemas, channels,
+        attachments, metadata and the summary section. Chunks are decompressed
+        and each chunk is followed by the records it contains.
+
+        Returns:
+            Generator yielding the fields of each record as a dictionary, with
+            "type" set to the record type (e.g. "schema", "message", "chunk").
+        """
+        for record_type, record in self._reader.get_records():
+            yield {'type': record_type.name.lower(), **asdict(record)}
+
+    def validate(self) -> list[dict[str, str]]:
+        """Check the structure of the MCAP file.
+
+        Checks the magic bytes, channel schema references, chunk index offsets,
+        and that the statistics are consistent with the channels and chunks.
+
+        Returns:
+            List of issues, each a dictionary with a "severity" ("error" or
+            "warning") and a "message". Empty if the file is valid.
+        """
+        return [asdict(issue) for issue in self._reader.validate()]
+
     @property
     def start_time(self) -> int:
         """Get the start time of the MCAP file in nanoseconds since epoch."""
@@ -115,38 +528,186 @@ class McapFileReader:
         """Get the end time of the MCAP file in nanoseconds since epoch."""
         return self._reader.get_statistics().message_end_time
 
+    def get_time_range(self) -> tuple[int, int, int] | None:
+        """Get the log time range of the messages in the MCAP file.
+
+        The range is read from the statistics record. If the file has none,
+        it is taken from the chunk indexes or, for unchunked files, by
+        scanning the messages.
+
+        Returns:
+            (start_time, end_time, duration) in nanoseconds, or None if the
+            file has no messages.
+        """
+        try:
+            statistics = self._reader.get_statistics()
+        except McapNoStatisticsError:
+            # Empty chunks have no messages to take the times of
+            chunk_indexes = [c for c in self._reader.get_chunk_indexes() if c.uncompressed_size > 0]
+            if chunk_indexes:
+                start_time = min(c.message_start_time for c in chunk_indexes)
+                end_time = max(c.message_end_time for c in chunk_indexes)
+            else:
+                log_times = [m.log_time for m in self._reader.get_messages(in_log_time_order=False)]
+                if not log_times:
+                    return None
+                start_time, end_time = min(log_times), max(log_times)
+        else:
+            if statistics.message_count == 0:
+                return None
+            start_time, end_time = statistics.message_start_time, statistics.message_end_time
+        return start_time, end_time, end_time - start_time
+
+    def _first_log_time(self) -> int:
+        """Get the start time, falling back to the earliest message without statistics."""
+        try:
+            return self.start_time
+        except McapNoStatisticsError:
+            first = next(self._reader.get_messages(), None)
+            return 0 if first is None else first.log_time
+
     # Message Access
 
-    def _expand_topics(self, topic: str | list[str]) -> list[str]:
-        """Expand topic patterns to list of concrete topic names.
+    def register_message_type(self, schema_name: str, constructor: Callable[..., Any]) -> None:
+        """Decode messages of a given type into a user provided type.
 
-        Handles:
-        - Single topic string (may contain glob pattern like "/sensor/*")
-        - List of topic strings (each may contain glob patterns)
+        Args:
+            schema_name: The message type name (e.g. "geometry_msgs/msg/Point").
+            constructor: Callable invoked with the decoded fields as keyword
+                arguments (e.g. a dataclass with matching field names).
+        """
+        self._message_types[schema_name] = constructor
+        if self._message_deserializer is not None:
+            self._message_deserializer.register_message_type(schema_name, constructor)
+
+    def _get_deserializer(
+        self,
+        channel_record: ChannelRecord,
+        message_schema: SchemaRecord | None,
+    ) -> MessageDeserializer | None:
+        """Get the deserializer for a channel.
+
+        Returns None if the channel has no schema or its messages are not CDR or ROS1 encoded.
+        """
+        encoding = channel_record.message_encoding
+        if message_schema is None or encoding not in SCHEMA_MESSAGE_ENCODINGS.values():
+            return None
+        message_deserializer = self._message_deserializer
+        if message_deserializer is None or encoding != SCHEMA_MESSAGE_ENCODINGS.get(self._profile):
+            message_deserializer = MessageDeserializerFactory.from_channel(
+                channel_record, message_schema
+            )
+        if message_deserializer is None:
+            raise McapUnknownEncodingError(
+                f'Unknown encoding type: {encoding} ({message_schema.encoding} schema)'
+            )
+        if message_deserializer is not self._message_deserializer:
+            for schema_name, constructor in self._message_types.items():
+                message_deserializer.register_message_type(schema_name, constructor)
+        return message_deserializer
+
+    @staticmethod
+    def _decode_data(
+        msg: MessageRecord,
+        channel_record: ChannelRecord,
+        message_schema: SchemaRecord | None,
+        message_deserializer: MessageDeserializer | None,
+    ) -> Any:
+        """Decode a message, parsing JSON and returning other encodings as raw bytes."""
+        if message_deserializer is not None and message_schema is not None:
+            return message_deserializer.deserialize_message(msg, message_schema)
+        if channel_record.message_encoding == 'json':
+            return json.loads(bytes(msg.data))
+        return bytes(msg.data)
+
+    def get_message_at(self, topic: str, log_time: int) -> DecodedMessage | None:
+        """Get the message on a topic whose log time is closest to a timestamp.
+
+        Only the chunk holding the nearest message is decompressed. If two
+        messages are equally close, the earlier one is returned.
 
         Args:
-            topic: Topic pattern (string or list of strings)
+            topic: The topic name.
+            log_time: The timestamp to search around (nanoseconds).
 
         Returns:
-            Deduplicated list of concrete topic names that exist in the file
+            The nearest DecodedMessage or None if the topic has no messages.
         """
-        available_topics = self.get_topics()
-        topic_patterns = [topic] if isinstance(topic, str) else topic
-        matched_topics = set()
-        for pattern in topic_patterns:
-            matches = fnmatch.filter(available_topics, pattern)
-            matched_topics.update(matches)
-        return list(matched_topics)
+        channel_id = self._reader.get_channel_id(topic)
+        if channel_id is None:
+            raise McapUnknownTopicError(f'Topic {topic} not found in MCAP file')
+        channel_record = self._reader.get_channel(channel_id)
+        message_schema = self._reader.get_channel_schema(channel_id)
+        if channel_record is None or (message_schema is None and channel_record.schema_id != 0):
+            return None
+
+        if (msg := self._reader.get_nearest_message(channel_id, log_time)) is None:
+            return None
+        message_deserializer = self._get_deserializer(channel_record, message_schema)
+        return DecodedMessage(
+            topic=channel_record.topic,
+            msg_type='' if message_schema is None else message_schema.name,
+            schema_encoding='' if message_schema is None else message_schema.encoding,
+            message_encoding=channel_record.message_encoding,
+            channel_id=msg.channel_id,
+            sequence=msg.sequence,
+            log_time=msg.log_time,
+            publish_time=msg.publish_time,
+            data=self._decode_data(msg, channel_record, message_schema, message_deserializer),
+        )
+
+    def _expand_topics(
+        self,
+        topic: str | list[str] | None,
+        topic_regex: str | None = None,
+    ) -> list[str]:
+        """Expand topic patterns to the sorted topic names in the file that they match."""
+        return _expand_topics(self.get_topics(), topic, topic_regex)
+
+    def _messages_by_publish_time(
+        self,
+        channel_ids: list[int],
+        start_time: int | None,
+        end_time: int | None,
+        *,
+        in_time_order: bool,
+        in_reverse: bool,
+    ) -> Iterable[MessageRecord]:
+        """Get the raw messages of channels filtered and ordered by publish time."""
+        matching = [
+            msg for msg in self._reader.get_messages(channel_ids, in_log_time_order=False)
+            if (start_time is None or msg.publish_time >= start_time)
+            and (end_time is None or msg.publish_time <= end_time)
+        ]
+        if in_time_order:
+            matching.sort(key=lambda msg: (msg.publish_time, msg.channel_id, msg.sequence))
+        if in_reverse:
+            matching.reverse()
+        return matching
 
     def messages(
         self,
-        topic: str | list[str],
+        topic: str | list[str] | None = None,
         start_time: int | None = None,
         end_time: int | None = None,
         filter: Callable[[DecodedMessage], bool] | None = None,
         *,
+        topic_regex: str | None = None,
         in_log_time_order: bool = True,
         in_reverse: bool = False,
+        max_message_bytes: int | None = None,
+        on_oversized: Literal['skip', 'error'] = 'skip',
+        sequences: Collection[int] | None = None,
+        relative_time: bool = False,
+        max_decode_errors: int | None = None,
+        field_paths: list[str] | None = None,
+        field_map: dict[str, str] | None = None,
+        combine_time_fields: bool = False,
+        time_field: Literal['log', 'publish'] = 'log',
+        progress: Callable[[int, int | None], None] | None = None,
+        progress_interval: int = 1000,
+        strict: bool = False,
+        limit: int | None = None,
     ) -> Generator[DecodedMessage, None, None]:
         """
         Iterate over messages in the MCAP file.
@@ -154,25 +715,84 @@ class McapFileReader:
         Args:
             topic: Topic(s) to filter by. Can be:
                 - Single topic string (e.g., "/camera")
-                - Glob pattern (e.g., "/sensor/*")
+                - Glob pattern (e.g., "/sensor/*"). Raises McapUnknownTopicError if no topic matches.
                 - List of topics/patterns (e.g., ["/topic1", "/sensor/*"])
                 - Empty list [] returns no messages
             start_time: Start time to filter by. If None, start from the beginning.
             end_time: End time to filter by. If None, read to the end.
             filter: Callable to filter messages. If None, all messages are returned.
+            topic_regex: Regular expression matched against full topic names. Raises
+                McapUnknownTopicError if no topic matches.
             in_log_time_order: Return messages in log time order if True, otherwise in write order.
+                Messages with equal log times are ordered by channel id, then sequence number.
+                Write order is chunk by chunk, even if the chunks overlap in time.
             in_reverse: Return messages in reverse order (last first) if True.
+            max_message_bytes: Maximum serialized payload size. Larger messages are not decoded.
+            on_oversized: Skip oversized messages ('skip') or raise McapMessageTooLargeError ('error').
+            sequences: Only return messages whose sequence number is in this collection.
+            relative_time: Return log and publish times as nanoseconds since the start of the file.
+            max_decode_errors: Number of messages allowed to fail decoding. These are returned
+                with their raw bytes as data and decode_error set. Once exceeded,
+                McapDecodeError is raised. If None, decoding errors are raised immediately.
+            field_paths: Dotted paths of the fields to return, e.g. ["header.stamp", "poses[0].position.x"].
+                If given, data is a dict mapping each path to its value. An index past the end
+                of a sequence gives None. The filter still sees the full message.
+            field_map: Dotted field paths mapped to new names, e.g. {"pose.position.x": "px"}.
+                If given, data is the message as nested dicts with each mapped field's key
+                renamed. Unmapped fields keep their names. The filter still sees the full
+                message. Cannot be combined with field_paths.
+            combine_time_fields: Give every builtin_interfaces/Time and Duration field a
+                __nanoseconds__ attribute equal to sec * 1_000_000_000 + nanosec.
+            time_field: The timestamp that start_time, end_time and in_log_time_order use,
+                'log' or 'publish'. Chunk indexes only bound log times, so filtering on
+                publish time reads every chunk of the requested channels.
+            progress: Called as progress(processed, total) after every progress_interval
+                messages read, and once more when iteration finishes. total is the number
+                of messages on the requested channels from the statistics, or None if the
+                file has no statistics. Exceptions raised by the callback stop iteration.
+            progress_interval: Number of messages read between progress calls.
+            strict: Raise instead of returning raw bytes. A message that fails to decode
+                raises McapDecodeError naming the failing field, a channel with an unknown
+                schema raises McapUnknownSchemaError, and a channel whose messages cannot
+                be decoded raises McapUnknownEncodingError. Cannot be combined with
+                max_decode_errors.
+            limit: Maximum number of messages to return (after filtering). Messages are
+                read lazily, so chunks after the last returned message are not read. In
+                log time order the first messages are still the earliest ones, as chunks
+                overlapping in time are merged before the limit is applied.
 
         Returns:
             Generator yielding DecodedMessage objects from matching topics.
         """
+        if on_oversized not in ('skip', 'error'):
+            raise ValueError(f"on_oversized must be 'skip' or 'error', got: {on_oversized}")
+        if time_field not in ('log', 'publish'):
+            raise ValueError(f"time_field must be 'log' or 'publish', got: {time_field}")
+        if progress_interval < 1:
+            raise ValueError(f'progress_interval must be positive, got: {progress_interval}')
+        if strict and max_decode_errors is not None:
+            raise ValueError('strict cannot be combined with max_decode_errors')
+        if field_paths is not None and field_map is not None:
+            raise ValueError('field_paths cannot be combined with field_map')
+        if limit is not None and limit < 0:
+            raise ValueError(f'limit must not be negative, got: {limit}')
+        if limit == 0:
+            return
+        sequence_set = None if sequences is None else set(sequences)
+        parsed_paths = None if field_paths is None else {p: parse_field_path(p) for p in field_paths}
+        parsed_map = None if field_map is None else {
+            tuple(parse_field_path(p)): name for p, name in field_map.items()
+        }
+        if parsed_map is not None and any(isinstance(parts[-1], int) for parts in parsed_map):
+            raise FieldPathError('field_map paths must end in a field name')
+
         # If empty list we return no messages
-        if (concrete_topics := self._expand_topics(topic)) == []:
+        if (concrete_topics := self._expand_topics(topic, topic_regex)) == []:
             return
         logging.debug(f"Expanded topics: {concrete_topics}")
 
         # Get the channels corresponding to the topics given
-        channel_infos = {}  # dict[channel_id, tuple[channel_record, schema]]
+        channel_infos = {}  # dict[channel_id, tuple[channel_record, schema, deserializer]]
         for topic_name in concrete_topics:
             channel_id = self._reader.get_channel_id(topic_name)
             if channel_id is None:
@@ -184,45 +804,265 @@ class McapFileReader:
                 logging.warning(f"No channel record for {topic_name} ({channel_id})")
                 continue
 
+            # A schema ID of 0 means the channel has no schema
             message_schema = self._reader.get_channel_schema(channel_id)
-            if message_schema is None:
+            if message_schema is None and channel_record.schema_id != 0:
+                if strict:
+                    raise McapUnknownSchemaError(
+                        f'Unknown schema {channel_record.schema_id} for {topic_name} ({channel_id})'
+                    )
                 logging.warning(f"Unknown schema for {topic_name} ({channel_id})")
                 continue
 
-            channel_infos[channel_id] = (channel_record, message_schema)
+            message_deserializer = self._get_deserializer(channel_record, message_schema)
+            if strict and message_deserializer is None and channel_record.message_encoding != 'json':
+                raise McapUnknownEncodingError(
+                    f'Cannot decode {channel_record.message_encoding} messages on {topic_name} '
+                    f'({"no" if message_schema is None else message_schema.encoding} schema)'
+                )
+            channel_infos[channel_id] = (channel_record, message_schema, message_deserializer)
 
         if not channel_infos:
             logging.warning(f'Nothing to retrieve!')
             return
 
-        if (message_deserializer := self._message_deserializer) is None:
-            # TODO: Do not assume all channels use the same encoding
-            channel_record, message_schema = next(iter(channel_infos.values()))
-            message_deserializer = MessageDeserializerFactory.from_channel(
-                channel_record, message_schema
+        # Fail early if a requested field does not exist
+        if parsed_paths is not None or parsed_map is not None:
+            requested_paths = list(parsed_paths.values()) if parsed_paths is not None else [list(p) for p in parsed_map]
+            for channel_record, message_schema, message_deserializer in channel_infos.values():
+                if message_deserializer is None:
+                    raise FieldPathError(
+                        f'Cannot select fields of {channel_record.message_encoding} messages on {channel_record.topic}'
+                    )
+                parsed_schema, sub_schemas = message_deserializer.parse_schema(message_schema)
+                for parts in requested_paths:
+                    check_field_path(parts, parsed_schema, sub_schemas)
+
+        time_offset = self._first_log_time() if relative_time else 0
+        decode_errors = 0
+        returned = 0
+
+        processed = 0
+        progress_total = None
+        if progress is not None:
+            try:
+                channel_counts = self._reader.get_statistics().channel_message_counts
+                progress_total = sum(channel_counts.get(channel_id, 0) for channel_id in channel_infos)
+            except McapNoStatisticsError:
+                pass
+
+        if time_field == 'publish':
+            raw_messages = self._messages_by_publish_time(
+                list(channel_infos.keys()),
+                start_time,
+                end_time,
+                in_time_order=in_log_time_order,
+                in_reverse=in_reverse,
             )
-        if message_deserializer is None:
-            raise McapUnknownEncodingError(f'Unknown encoding type: {self._profile}')
-
-        for msg in self._reader.get_messages(
-            list(channel_infos.keys()),
-            start_time,
-            end_time,
-            in_log_time_order=in_log_time_order,
-            in_reverse=in_reverse,
-        ):
-            channel_record, schema = channel_infos[msg.channel_id]
+        else:
+            raw_messages = self._reader.get_messages(
+                list(channel_infos.keys()),
+                start_time,
+                end_time,
+                in_log_time_order=in_log_time_order,
+                in_reverse=in_reverse,
+            )
+
+        for msg in raw_messages:
+            if progress is not None:
+                processed += 1
+                if processed % progress_interval == 0:
+                    progress(processed, progress_total)
+            if sequence_set is not None and msg.sequence not in sequence_set:
+                continue
+            channel_record, schema, message_deserializer = channel_infos[msg.channel_id]
+            if max_message_bytes is not None and len(msg.data) > max_message_bytes:
+                if on_oversized == 'error':
+                    raise McapMessageTooLargeError(
+                        f'Message on {channel_record.topic} at {msg.log_time} is '
+                        f'{len(msg.data)} bytes (limit {max_message_bytes})'
+                    )
+                logger.debug(f'Skipping {len(msg.data)} byte message on {channel_record.topic}')
+                continue
+
+            decode_error = None
+            if strict:
+                try:
+                    data = self._decode_data(msg, channel_record, schema, message_deserializer)
+                except Exception as e:
+                    raise McapDecodeError(
+                        f'Failed to decode message on {channel_record.topic} at {msg.log_time}: {e}'
+                    ) from e
+            elif max_decode_errors is None:
+                data = self._decode_data(msg, channel_record, schema, message_deserializer)
+            else:
+                try:
+                    data = self._decode_data(msg, channel_record, schema, message_deserializer)
+                except Exception as e:
+                    decode_errors += 1
+                    if decode_errors > max_decode_errors:
+                        raise McapDecodeError(
+                            f'Failed to decode message on {channel_record.topic} at {msg.log_time} '
+                            f'({decode_errors} decode errors, limit {max_decode_errors}): {e}'
+                        ) from e
+                    logger.warning(f'Failed to decode message on {channel_record.topic} at {msg.log_time}: {e}')
+                    data, decode_error = bytes(msg.data), str(e)
+            if combine_time_fields and decode_error is None and message_deserializer is not None:
+                _combine_time_fields(data)
+
             decoded = DecodedMessage(
                 topic=channel_record.topic,
-                msg_type=schema.name,
+                msg_type='' if schema is None else schema.name,
+                schema_encoding='' if schema is None else schema.encoding,
+                message_encoding=channel_record.message_encoding,
                 channel_id=msg.channel_id,
                 sequence=msg.sequence,
-                log_time=msg.log_time,
-                publish_time=msg.publish_time,
-                data=message_deserializer.deserialize_message(msg, schema),
+                log_time=msg.log_time - time_offset,
+                publish_time=msg.publish_time - time_offset,
+                data=data,
+                decode_error=decode_error,
             )
             if filter is None or filter(decoded):
+                if parsed_paths is not None and decode_error is None:
+                    decoded.data = {path: get_field(data, parts) for path, parts in parsed_paths.items()}
+                elif parsed_map is not None and decode_error is None:
+                    decoded.data = to_dict(data, parsed_map)
                 yield decoded
+                returned += 1
+                if limit is not None and returned >= limit:
+                    break
+
+        if progress is not None and processed % progress_interval:
+            progress(processed, progress_total)
+
+    def scan(
+        self,
+        topic: str | list[str] | None = None,
+    ) -> Generator[tuple[int, int, bytes], None, None]:
+        """Iterate over the raw messages in write order without decoding them.
+
+        Meant for indexing passes that only need timestamps or payload sizes.
+
+        Args:
+            topic: Topic(s) or glob pattern(s) to scan. If None, scans every channel.
+
+        Returns:
+            Generator yielding (channel_id, log_time, data) tuples. The data is a copy
+            of the serialized message.
+        """
+        channel_ids = None
+        if topic is not None:
+            channel_ids = [
+                channel_id for name in self._expand_topics(topic)
+                if (channel_id := self._reader.get_channel_id(name)) is not None
+            ]
+            if not channel_ids:
+                return
+        for msg in self._reader.get_messages(channel_ids, in_log_time_order=False):
+            yield msg.channel_id, msg.log_time, bytes(msg.data)
+
+    def export(
+        self,
+        file_path: str | Path,
+        topic: str | list[str],
+        start_time: int | None = None,
+        end_time: int | None = None,
+        *,
+        chunk_size: int | None = None,
+        chunk_compression: Literal["none", "lz4", "zstd"] | None = None,
+    ) -> int:
+        """Copy the messages of some topics into a new MCAP file.
+
+        Schemas, channels and messages are copied without being decoded, so
+        schema and channel IDs and message sequence numbers are preserved.
+        Messages are written in log time order.
+
+        Args:
+            file_path: The path of the MCAP file to write.
+            topic: Topic(s) or glob pattern(s) to export.
+            start_time: Start time to filter by. If None, start from the beginning.
+            end_time: End time to filter by. If None, read to the end.
+            chunk_size: The size of the chunks to write in bytes. If None, writes without chunking.
+            chunk_compression: The compression to use for the chunks.
+
+        Returns:
+            The number of messages written.
+        """
+        topics = set(self._expand_topics(topic))
+        channels = {
+            channel_id: channel
+            for channel_id, channel in self._reader.get_channels().items()
+            if channel.topic in topics
+        }
+
+        count = 0
+        with McapRecordWriterFactory.create_writer(
+            FileWriter(file_path),
+            McapSummaryFactory.create_summary(chunk_size=chunk_size),
+            chunk_size=chunk_size,
+            chunk_compression=chunk_compression,
+            profile=self._profile,
+        ) as writer:
+            written_schema_ids = set()
+            for channel in channels.values():
+                if channel.schema_id != 0 and channel.schema_id not in written_schema_ids:
+                    if (schema := self._reader.get_schema(channel.schema_id)) is not None:
+                        writer.write_schema(schema)
+                        written_schema_ids.add(channel.schema_id)
+                writer.write_channel(channel)
+
+            if channels:
+                for msg in self._reader.get_messages(list(channels), start_time, end_time):
+                    writer.write_message(msg)
+                    count += 1
+        return count
+
+    def read_structured(
+        self,
+        topic: str,
+        start_time: int | None = None,
+        end_time: int | None = None,
+    ) -> Any:
+        """Read all messages on a topic into a numpy structured array.
+
+        The dtype mirrors the message schema: nested messages become nested
+        fields and fixed-length arrays become sub-arrays. Requires numpy.
+
+        Args:
+            topic: The topic name.
+            start_time: Start time to filter by. If None, start from the beginning.
+            end_time: End time to filter by. If None, read to the end.
+
+        Returns:
+            A numpy structured array with one element per message.
+
+        Raises:
+            StructuredTypeError: If the message type has strings, sequences or bounded arrays,
+                or the channel is not CDR or ROS1 encoded.
+        """
+        import numpy as np
+
+        channel_id = self._reader.get_channel_id(topic)
+        if channel_id is None:
+            raise McapUnknownTopicError(f'Topic {topic} not found in MCAP file')
+        channel_record = self._reader.get_channel(channel_id)
+        message_schema = self._reader.get_channel_schema(channel_id)
+        if channel_record is None or message_schema is None:
+            raise McapUnknownTopicError(f'No channel or schema for topic {topic}')
+
+        message_deserializer = self._get_deserializer(channel_record, message_schema)
+        if message_deserializer is None:
+            raise StructuredTypeError(
+                f'{channel_record.message_encoding} messages on {topic} have no fixed-size layout'
+            )
+        schema, sub_schemas = message_deserializer.parse_schema(message_schema)
+        dtype = np.dtype(schema_dtype(schema, sub_schemas))
+
+        records = [
+            message_record(message_deserializer.deserialize_message(msg, message_schema), schema, sub_schemas)
+            for msg in self._reader.get_messages([channel_id], start_time, end_time)
+        ]
+        return np.array(records, dtype=dtype)
 
     def get_attachments(self, name: str | None = None) -> list[AttachmentRecord]:
         """Get attachments from the MCAP file.
@@ -264,8 +1104,17 @@ class McapFileReader:
         self.close()
 
 
+def _natural_sort_key(path: Path) -> list[int | str]:
+    """Sort key placing e.g. bag_2.mcap before bag_10.mcap."""
+    return [int(part) if part.isdigit() else part for part in re.split(r'(\d+)', path.name)]
+
+
 class McapMultipleFileReader:
-    """Reader that seamlessly reads from multiple MCAP files."""
+    """Reader that seamlessly reads from multiple MCAP files.
+
+    Channel IDs differ between files, so messages are given a channel ID shared
+    by all files with the same topic, numbered in order of first appearance.
+    """
 
     def __init__(self, readers: list[McapFileReader]):
         self._readers = readers
@@ -276,21 +1125,51 @@ class McapMultipleFileReader:
             for profile in self._profiles
         }
 
+        self._channel_ids: dict[str, int] = {}
+        for reader in self._readers:
+            for channel in sorted(reader.get_channels(), key=lambda c: c.id):
+                self._channel_ids.setdefault(channel.topic, len(self._channel_ids) + 1)
+
     @staticmethod
     def from_files(file_paths: list[Path | str], *, enable_crc_check: bool = False) -> 'McapMultipleFileReader':
         readers = [McapFileReader.from_file(p, enable_crc_check=enable_crc_check) for p in file_paths]
         return McapMultipleFileReader(readers)
 
+    @staticmethod
+    def from_directory(directory: Path | str, *, enable_crc_check: bool = False) -> 'McapMultipleFileReader':
+        """Open every MCAP file in a directory, e.g. a split rosbag2 recording.
+
+        Files are opened in natural order of their names (bag_2.mcap before bag_10.mcap).
+        """
+        file_paths = sorted(Path(directory).glob('*.mcap'), key=_natural_sort_key)
+        if not file_paths:
+            raise FileNotFoundError(f'No MCAP files found in {directory}')
+        return McapMultipleFileReader.from_files(file_paths, enable_crc_check=enable_crc_check)
+
     @property
     def profiles(self) -> set[str]:
         return self._profiles
 
+    def get_channel_id(self, topic: str) -> int | None:
+        """Get the channel ID shared by all files for a topic."""
+        return self._channel_ids.get(topic)
+
+    def register_message_type(self, schema_name: str, constructor: Callable[..., Any]) -> None:
+        for reader in self._readers:
+            reader.register_message_type(schema_name, constructor)
+
     def get_topics(self) -> list[str]:
         topics: set[str] = set()
         for reader in self._readers:
             topics.update(reader.get_topics())
         return list(topics)
 
+    def get_topics_of_type(self, schema_name: str) -> list[str]:
+        topics: set[str] = set()
+        for reader in self._readers:
+            topics.update(reader.get_topics_of_type(schema_name))
+        return list(topics)
+
     def get_message_count(self, topic: str) -> int:
         count = 0
         for reader in self._readers:
@@ -325,9 +1204,12 @@ class McapMultipleFileReader:
             raise ValueError('in_log_time_order must be True')
 
         # Initialize the heap with the first message of each file
+        # Patterns are matched against the topics of every file, as a topic may be in only some files
+        topics = _expand_topics(self.get_topics(), topic)
         heap: list[tuple[int, int, DecodedMessage, Generator[DecodedMessage, None, None]]] = []
         for reader in self._readers:
-            it = iter(reader.messages(topic, start_time, end_time, in_log_time_order=in_log_time_order, in_reverse=in_reverse))
+            file_topics = [t for t in topics if t in reader.get_topics()]
+            it = iter(reader.messages(file_topics, start_time, end_time, in_log_time_order=in_log_time_order, in_reverse=in_reverse))
             try:
                 msg = next(it)
                 # For reverse iteration, negate log_time so heap gives us largest times first
@@ -340,6 +1222,7 @@ class McapMultipleFileReader:
         # Ties are split by the index the files were provided to in the constructor
         while heap:
             _, idx, msg, it = heapq.heappop(heap)
+            msg.channel_id = self._channel_ids[msg.topic]
             if filter is None or filter(msg):
                 yield msg
             try:
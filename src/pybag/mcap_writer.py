@@ -8,8 +8,13 @@ from pybag.encoding import MessageEncoder
 from pybag.encoding.cdr import CdrEncoder
 from pybag.encoding.rosmsg import RosMsgEncoder
 from pybag.io.raw_reader import FileReader
-from pybag.io.raw_writer import BaseWriter, FileWriter
-from pybag.mcap.crc import compute_crc
+from pybag.io.raw_writer import BaseWriter, BytesWriter, FileWriter
+from pybag.mcap.chunk import (
+    ZSTD_DICTIONARY_ATTACHMENT,
+    ZSTD_DICTIONARY_MEDIA_TYPE
+)
+from pybag.mcap.crc import compute_attachment_crc
+from pybag.mcap.error import McapNonMonotonicError
 from pybag.mcap.record_writer import McapRecordWriterFactory
 from pybag.mcap.records import (
     AttachmentRecord,
@@ -49,8 +54,14 @@ class McapFileWriter:
         *,
         mode: Literal['w', 'a'] = 'w',
         profile: Literal['ros1', 'ros2'] = "ros2",
+        library: str | None = None,
         chunk_size: int | None = None,
         chunk_compression: Literal["none", "lz4", "zstd"] | None = "none",
+        compression_level: int | None = None,
+        little_endian: bool = True,
+        compute_data_crc: bool = True,
+        enforce_monotonic: Literal['channel', 'global'] | None = None,
+        zstd_dictionary: bytes | None = None,
     ) -> None:
         """Initialize a high-level MCAP file writer.
 
@@ -59,11 +70,31 @@ class McapFileWriter:
             mode: The mode to open the file in: 'a' for append, 'w' for write.
                   In append mode, the file must already exist and be a valid MCAP file.
             profile: The MCAP profile to use (default: "ros2").
+            library: The library written to the header, e.g. to record the tool that made
+                the file. Defaults to "pybag <version>". Ignored in append mode.
             chunk_size: If provided, creates chunks of approximately this size in bytes. If None, writes without chunking.
             chunk_compression: Compression algorithm for chunks ("lz4" or "zstd" or None for no compression).
+            compression_level: Compression level for chunks (default: 3 for zstd, library default for lz4).
+            little_endian: Byte order of CDR encoded messages (ros2 profile only).
+            compute_data_crc: Compute the data section CRC. Disable to trade integrity checks for write speed.
+            enforce_monotonic: Raise McapNonMonotonicError if a message's log time is earlier than
+                the previous message on the same channel ('channel') or on any channel ('global').
+                If None, messages can be written in any order.
+            zstd_dictionary: Dictionary to compress every zstd chunk with, e.g. one made by
+                zstandard.train_dictionary from sample messages. It is stored as a pybag-specific
+                attachment, so only pybag can read the file: other MCAP readers (the mcap CLI,
+                Foxglove, the mcap Python library) cannot decompress the chunks without it.
+                Only supported when creating a file.
         """
+        if zstd_dictionary is not None:
+            if chunk_compression != "zstd" or not chunk_size:
+                raise ValueError('zstd_dictionary requires zstd chunk compression')
+            if mode != 'w':
+                raise ValueError('zstd_dictionary is only supported when creating a file')
+
         # Get message serializer for this profile
         self._profile = profile
+        self._little_endian = little_endian
         message_serializer = MessageSerializerFactory.from_profile(self._profile)
         if message_serializer is None:
             raise ValueError(f"Unknown encoding type: {self._profile}")
@@ -75,13 +106,18 @@ class McapFileWriter:
             chunk_size = 1024 * 1024  # 1MB
 
         # Create the low-level record writer via factory
+        self._writer = writer
         self._record_writer = McapRecordWriterFactory.create_writer(
             writer,
             self._summary,
             mode=mode,
             chunk_size=chunk_size,
             chunk_compression=chunk_compression,
+            compression_level=compression_level,
             profile=self._profile,
+            library=library,
+            compute_data_crc=compute_data_crc,
+            zstd_dictionary=zstd_dictionary,
         )
 
         # Pre-compiled serializers for topics with explicit schemas
@@ -91,6 +127,8 @@ class McapFileWriter:
         # Schema decoder/encoder and compiler based on profile
         # TODO: This should be integrated with message_serializer
         if self._profile == "ros1":
+            if not little_endian:
+                raise ValueError('ros1 messages are always little endian')
             self._schema_decoder = Ros1McapSchemaDecoder()
             self._schema_encoder = Ros1MsgSchemaEncoder()
             self._schema_compiler = compile_ros1_serializer
@@ -99,12 +137,23 @@ class McapFileWriter:
             self._schema_decoder = Ros2MsgSchemaDecoder()
             self._schema_encoder = Ros2MsgSchemaEncoder()
             self._schema_compiler = compile_serializer
-            self._create_encoder = lambda: CdrEncoder(little_endian=True)
+            self._create_encoder = lambda: CdrEncoder(little_endian=little_endian)
         else:
             raise ValueError(f'Unsupported profile: {self._profile}')
 
-        # TODO: Use Summary instead
-        self._written_schemas: dict[int, SchemaRecord] = {}
+        # Schemas written by this writer, keyed by their (name, encoding, data)
+        self._written_schemas: dict[tuple[str, str, bytes], SchemaRecord] = {}
+
+        # Last log time written, keyed by channel ID (or None when enforced globally)
+        self._enforce_monotonic = enforce_monotonic
+        self._last_log_times: dict[int | None, int] = {}
+
+        if zstd_dictionary is not None:
+            self.write_attachment(
+                ZSTD_DICTIONARY_ATTACHMENT,
+                zstd_dictionary,
+                media_type=ZSTD_DICTIONARY_MEDIA_TYPE,
+            )
 
     def __enter__(self) -> "McapFileWriter":
         """Context manager entry."""
@@ -121,8 +170,14 @@ class McapFileWriter:
         *,
         mode: Literal['w', 'a'] = 'w',
         profile: Literal['ros1', 'ros2'] = "ros2",
+        library: str | None = None,
         chunk_size: int | None = None,
         chunk_compression: Literal["none", "lz4", "zstd"] | None = "lz4",
+        compression_level: int | None = None,
+        little_endian: bool = True,
+        compute_data_crc: bool = True,
+        enforce_monotonic: Literal['channel', 'global'] | None = None,
+        zstd_dictionary: bytes | None = None,
     ) -> "McapFileWriter":
         """Create a writer backed by a file on disk.
 
@@ -131,9 +186,17 @@ class McapFileWriter:
             mode: The mode to open the file in: 'a' for append, 'w' for write.
                   In append mode, the file must already exist and be a valid MCAP file.
             profile: The profile to use for the MCAP file.
+            library: The library written to the header (default: "pybag <version>").
             chunk_size: The size of the chunk to write to in bytes.
                        If None, writes without chunking.
             chunk_compression: The compression to use for the chunk.
+            compression_level: The compression level to use for the chunk.
+            little_endian: Byte order of CDR encoded messages (ros2 profile only).
+            compute_data_crc: Compute the data section CRC.
+            enforce_monotonic: Reject messages written out of log time order,
+                per channel ('channel') or across all channels ('global').
+            zstd_dictionary: Dictionary to compress every zstd chunk with. The file can then
+                only be read by pybag.
 
         Returns:
             A writer backed by a file on disk.
@@ -142,30 +205,130 @@ class McapFileWriter:
             FileWriter(file_path, mode='wb' if mode == 'w' else 'r+b'),
             mode=mode,
             profile=profile,
+            library=library,
             chunk_size=chunk_size,
             chunk_compression=chunk_compression,
+            compression_level=compression_level,
+            little_endian=little_endian,
+            compute_data_crc=compute_data_crc,
+            enforce_monotonic=enforce_monotonic,
+            zstd_dictionary=zstd_dictionary,
             summary=McapSummaryFactory.create_summary(
                 file=FileReader(file_path) if mode == 'a' else None,
                 chunk_size=chunk_size,
             ),
         )
 
+    @classmethod
+    def in_memory(
+        cls,
+        *,
+        profile: Literal['ros1', 'ros2'] = "ros2",
+        library: str | None = None,
+        chunk_size: int | None = None,
+        chunk_compression: Literal["none", "lz4", "zstd"] | None = "lz4",
+        compression_level: int | None = None,
+        little_endian: bool = True,
+        compute_data_crc: bool = True,
+        enforce_monotonic: Literal['channel', 'global'] | None = None,
+        zstd_dictionary: bytes | None = None,
+    ) -> "McapFileWriter":
+        """Create a writer backed by an in-memory buffer.
+
+        The file contents are returned by to_bytes() once the writer is closed.
+
+        Args:
+            profile: The profile to use for the MCAP file.
+            library: The library written to the header (default: "pybag <version>").
+            chunk_size: The size of the chunk to write to in bytes.
+                       If None, writes without chunking.
+            chunk_compression: The compression to use for the chunk.
+            compression_level: The compression level to use for the chunk.
+            little_endian: Byte order of CDR encoded messages (ros2 profile only).
+            compute_data_crc: Compute the data section CRC.
+            enforce_monotonic: Reject messages written out of log time order,
+                per channel ('channel') or across all channels ('global').
+            zstd_dictionary: Dictionary to compress every zstd chunk with. The file can then
+                only be read by pybag.
+
+        Returns:
+            A writer backed by an in-memory buffer.
+        """
+        return cls(
+            BytesWriter(),
+            profile=profile,
+            library=library,
+            chunk_size=chunk_size,
+            chunk_compression=chunk_compression,
+            compression_level=compression_level,
+            little_endian=little_endian,
+            compute_data_crc=compute_data_crc,
+            enforce_monotonic=enforce_monotonic,
+            zstd_dictionary=zstd_dictionary,
+            summary=McapSummaryFactory.create_summary(chunk_size=chunk_size),
+        )
+
+    def to_bytes(self) -> bytes:
+        """Get the contents of a writer created with in_memory().
+
+        The file is only complete (with its summary and footer) after close().
+
+        Raises:
+            ValueError: If the writer is not backed by an in-memory buffer.
+        """
+        if not isinstance(self._writer, BytesWriter):
+            raise ValueError('Only writers created with in_memory() can return their bytes')
+        return self._writer.as_bytes()
+
+    def add_schema(self, name: str, encoding: str, data: bytes) -> int:
+        """Add a schema to the MCAP output.
+
+        Schemas are assigned increasing IDs. Adding a schema with the same
+        name, encoding and data as one already added returns its ID.
+
+        Args:
+            name: The schema name, e.g. "std_msgs/msg/String".
+            encoding: The schema encoding, e.g. "ros2msg" or "protobuf".
+            data: The schema data.
+
+        Returns:
+            The schema ID.
+        """
+        key = (name, encoding, bytes(data))
+        if (schema_record := self._written_schemas.get(key)) is None:
+            schema_record = SchemaRecord(
+                id=self._summary.next_schema_id(),
+                name=name,
+                encoding=encoding,
+                data=key[2],
+            )
+            self._record_writer.write_schema(schema_record)
+            self._written_schemas[key] = schema_record
+        return schema_record.id
+
     def add_channel(
         self,
         topic: str,
         *,
-        schema: SchemaText | type[Message] | Message,
+        schema: SchemaText | type[Message] | Message | int,
+        message_encoding: str | None = None,
+        metadata: dict[str, str] | None = None,
     ) -> int:
         """Add a channel to the MCAP output.
 
         If the topic already exists, returns the existing channel ID.
-        Otherwise, creates a new schema (if needed) and channel.
+        Otherwise, creates a new schema (if needed) and channel. Channels are
+        assigned increasing IDs.
 
         Args:
             topic: The topic name.
             schema: A SchemaText object containing the message type name and
-                   schema definition text, or a message class/instance to
-                   generate the schema from.
+                   schema definition text, a message class/instance to
+                   generate the schema from, or the ID of a schema returned by
+                   add_schema (0 for a channel without a schema).
+            message_encoding: The channel's message encoding. Defaults to the
+                profile's encoding ("cdr" or "ros1").
+            metadata: The channel metadata.
 
         Returns:
             The channel ID.
@@ -174,48 +337,50 @@ class McapFileWriter:
         if (channel_id := self._summary.get_channel_id(topic)) is not None:
             return channel_id
 
-        # Convert message class or instance to SchemaText
-        if isinstance(schema, type) and hasattr(schema, '__msg_name__'):
-            schema = SchemaText(
-               name=schema.__msg_name__,
-               text=self._schema_encoder.encode(schema).decode('utf-8'),
-            )
-        elif isinstance(schema, Message):
-            schema_type = type(schema)
-            schema = SchemaText(
-               name=schema_type.__msg_name__,
-               text=self._schema_encoder.encode(schema_type).decode('utf-8'),
-            )
-
-        schema_hash = hash(schema.text + schema.name)
-        if schema_hash not in self._written_schemas:
-            schema_data = schema.text.encode('utf-8')
-            schema_name = schema.name
-            schema_record = SchemaRecord(
-                id=self._summary.next_schema_id(),
-                name=schema_name,
-                encoding=self._message_serializer.schema_encoding,
-                data=schema_data,
-            )
-            self._record_writer.write_schema(schema_record)
-            self._written_schemas[schema_hash] = schema_record
+        if isinstance(schema, int):
+            schema_id = schema
+            if schema_id != 0 and schema_id not in self._summary.get_schemas():
+                raise ValueError(f'Unknown schema ID {schema_id} for {topic}')
         else:
-            schema_record = self._written_schemas[schema_hash]
+            # Convert message class or instance to SchemaText
+            if isinstance(schema, type) and hasattr(schema, '__msg_name__'):
+                schema = SchemaText(
+                   name=schema.__msg_name__,
+                   text=self._schema_encoder.encode(schema).decode('utf-8'),
+                )
+            elif isinstance(schema, Message):
+                schema_type = type(schema)
+                schema = SchemaText(
+                   name=schema_type.__msg_name__,
+                   text=self._schema_encoder.encode(schema_type).decode('utf-8'),
+                )
+            schema_id = self.add_schema(
+                schema.name,
+                self._message_serializer.schema_encoding,
+                schema.text.encode('utf-8'),
+            )
 
         # Parse the schema text and compile a serializer for this topic
         # This allows us to serialize messages without relying on type annotations
-        parsed_schema, sub_schemas = self._schema_decoder.parse_schema(schema_record)
-        serializer = self._schema_compiler(parsed_schema, sub_schemas)
-        self._topic_serializers[topic] = serializer
+        schema_record = self._summary.get_schemas().get(schema_id)
+        message_encoding = message_encoding or self._message_serializer.message_encoding
+        if (
+            schema_record is not None
+            and schema_record.encoding == self._message_serializer.schema_encoding
+            and message_encoding == self._message_serializer.message_encoding
+        ):
+            parsed_schema, sub_schemas = self._schema_decoder.parse_schema(schema_record)
+            serializer = self._schema_compiler(parsed_schema, sub_schemas)
+            self._topic_serializers[topic] = serializer
 
         # Register the channel
         channel_id = self._summary.next_channel_id()
         channel_record = ChannelRecord(
             id=channel_id,
-            schema_id=schema_record.id,
+            schema_id=schema_id,
             topic=topic,
-            message_encoding=self._message_serializer.message_encoding,
-            metadata={},
+            message_encoding=message_encoding,
+            metadata=metadata or {},
         )
         self._record_writer.write_channel(channel_record)
 
@@ -225,7 +390,7 @@ class McapFileWriter:
         self,
         topic: str,
         timestamp: int,
-        message: Message,
+        message: Message | bytes,
         publish_time: int | None = None
     ) -> None:
         """Write a message to a topic at a given timestamp.
@@ -233,17 +398,35 @@ class McapFileWriter:
         Automatically creates the channel (and schema) if it doesn't exist.
         If the channel was pre-registered with add_channel(), uses that schema.
 
+        Already encoded messages (e.g. copied from another file) can be passed
+        as bytes and are written unchanged. Their channel must be added first.
+
         Args:
             topic: The topic name.
             timestamp: The log timestamp of the message (nanoseconds).
-            message: The message to write.
+            message: The message to write, or its encoded data.
             publish_time: The publish timestamp (nanoseconds). If None, defaults to timestamp.
+
+        Raises:
+            ValueError: If encoded data is written to a topic without a channel.
         """
+        encoded = isinstance(message, (bytes, bytearray, memoryview))
+
         # Check if channel already exists (may have been pre-registered)
         channel_id = self._summary.get_channel_id(topic)
         if channel_id is None:
+            if encoded:
+                raise ValueError(f'Cannot write encoded data to {topic} before calling add_channel')
             channel_id = self.add_channel(topic, schema=message)
 
+        if self._enforce_monotonic is not None:
+            key = channel_id if self._enforce_monotonic == 'channel' else None
+            if (last_log_time := self._last_log_times.get(key)) is not None and timestamp < last_log_time:
+                raise McapNonMonotonicError(
+                    f'Message on {topic} at {timestamp} is earlier than the previous message at {last_log_time}'
+                )
+            self._last_log_times[key] = timestamp
+
         # Get and increment sequence number
         sequence = self._summary.next_sequence_id(channel_id)
 
@@ -251,8 +434,10 @@ class McapFileWriter:
         actual_publish_time = publish_time if publish_time is not None else timestamp
 
         # Serialize the message
-        if topic not in self._topic_serializers:
-            data = self._message_serializer.serialize_message(message)
+        if encoded:
+            data = bytes(message)
+        elif topic not in self._topic_serializers:
+            data = self._message_serializer.serialize_message(message, little_endian=self._little_endian)
         else:
             serializer = self._topic_serializers[topic]
             encoder = self._create_encoder()
@@ -303,7 +488,7 @@ class McapFileWriter:
             media_type: MIME type of the attachment.
             log_time: Log timestamp (nanoseconds). If None, defaults to 0.
             create_time: Creation timestamp (nanoseconds). If None, defaults to log_time.
-            compute_crc: Whether to compute the crc value on the data or not
+            do_compute_crc: Whether to compute the attachment CRC (0 is written otherwise).
         """
         actual_log_time = log_time if log_time is not None else 0
         actual_create_time = create_time if create_time is not None else actual_log_time
@@ -314,8 +499,10 @@ class McapFileWriter:
             name=name,
             media_type=media_type,
             data=data,
-            crc=compute_crc(data) if do_compute_crc else 0,
+            crc=0,
         )
+        if do_compute_crc:
+            record.crc = compute_attachment_crc(record)
         self._record_writer.write_attachment(record)
 
     def write_metadata(
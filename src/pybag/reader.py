@@ -2,6 +2,7 @@
 
 from collections.abc import Generator
 from dataclasses import dataclass
+from datetime import datetime, timezone
 from pathlib import Path
 from types import TracebackType
 from typing import Any, Callable, Literal
@@ -33,6 +34,15 @@ class DecodedMessage:
     data: Any
 
 
+def nanoseconds_to_datetime(ns: int) -> datetime:
+    """Convert a timestamp in nanoseconds since epoch to a UTC datetime.
+
+    The datetime has microsecond resolution, so the nanoseconds are truncated.
+    """
+    seconds, nanoseconds = divmod(ns, 1_000_000_000)
+    return datetime.fromtimestamp(seconds, tz=timezone.utc).replace(microsecond=nanoseconds // 1000)
+
+
 def _detect_format(path: Path) -> Literal['mcap', 'bag']:
     """Detect file format from extension.
 
@@ -47,6 +57,8 @@ def _detect_format(path: Path) -> Literal['mcap', 'bag']:
     """
     # TODO: Read magic bytes to determine?
     suffix = path.suffix.lower()
+    if suffix in ('.gz', '.xz') and path.stem.lower().endswith('.mcap'):
+        return 'mcap'  # Whole-file compressed MCAP
     if suffix == '.mcap':
         return 'mcap'
     elif suffix == '.bag':
@@ -137,6 +149,15 @@ class Reader:
         """Get the end time of the file in nanoseconds since epoch."""
         return self._reader.end_time
 
+    def get_time_range(self) -> tuple[int, int, int] | None:
+        """Get the time range of the messages in the file.
+
+        Returns:
+            (start_time, end_time, duration) in nanoseconds, or None if the
+            file has no messages.
+        """
+        return self._reader.get_time_range()
+
     def get_topics(self) -> list[str]:
         """Get all topics in the file.
 
@@ -1,5 +1,6 @@
 from __future__ import annotations
 
+import re
 from abc import ABC, abstractmethod
 from dataclasses import dataclass
 from typing import TYPE_CHECKING, Any
@@ -14,7 +15,7 @@ from pybag.mcap.records import SchemaRecord
 PRIMITIVE_TYPE_MAP = {
     'bool': bool,
     'byte': int,
-    'char': str,
+    'char': int,
     'float32': float,
     'float64': float,
     'int8': int,
@@ -32,6 +33,43 @@ STRING_TYPE_MAP = {
 }
 
 
+# Line separating embedded message definitions (80 '=' by convention)
+_DEFINITION_SEPARATOR = re.compile(r'^[ \t]*={3,}[ \t]*$', re.MULTILINE)
+
+
+def is_definition_separator(line: str) -> bool:
+    """Check if a line separates embedded message definitions.
+
+    Any line of three or more '=' characters is accepted since not every
+    writer uses the conventional 80.
+    """
+    return _DEFINITION_SEPARATOR.fullmatch(line) is not None
+
+
+def decode_definition_text(data: bytes) -> str:
+    """Decode the text of a schema written on any platform.
+
+    A leading UTF-8 byte order mark is dropped and Windows (CRLF) and old
+    Mac (CR) line endings are converted to LF.
+    """
+    text = data.decode('utf-8-sig')
+    return text.replace('\r\n', '\n').replace('\r', '\n')
+
+
+def split_message_definition(text: str) -> list[str]:
+    """Split a message definition into the main and embedded definitions."""
+    return _DEFINITION_SEPARATOR.split(text)
+
+
+def normalize_message_name(name: str) -> str:
+    """Normalize a message type name to the ``pkg/Type`` form.
+
+    ROS2 schema names may include the interface namespace (``pkg/msg/Type``)
+    while sub-schemas and ROS1 names do not (``pkg/Type``).
+    """
+    return name.replace('/msg/', '/')
+
+
 @dataclass
 class SchemaFieldType(ABC):
     ...
@@ -91,6 +129,22 @@ class Schema:
     name: str
     fields: dict[str, SchemaEntry]
 
+    def defaults(self) -> dict[str, Any]:
+        """Get the default values of the fields that declare one."""
+        return {
+            name: entry.default
+            for name, entry in self.fields.items()
+            if isinstance(entry, SchemaField) and entry.default is not None
+        }
+
+    def constants(self) -> dict[str, Any]:
+        """Get the values of the constants."""
+        return {
+            name: entry.value
+            for name, entry in self.fields.items()
+            if isinstance(entry, SchemaConstant)
+        }
+
 
 class SchemaDecoder(ABC):
     @abstractmethod
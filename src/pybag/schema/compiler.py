@@ -10,6 +10,7 @@ from typing import Annotated, Any, Callable
 
 import pybag.types as t
 from pybag.encoding import MessageDecoder
+from pybag.encoding.cdr import CdrDecodeError
 from pybag.schema import (
     Array,
     Complex,
@@ -19,7 +20,8 @@ from pybag.schema import (
     SchemaField,
     SchemaFieldType,
     Sequence,
-    String
+    String,
+    normalize_message_name
 )
 
 # Map primitive ROS2 types to struct format characters
@@ -77,6 +79,22 @@ def _is_simple_struct(schema: Schema) -> tuple[list[tuple[str, str, int]], int]
     return (field_info, max_align)
 
 
+def _uniform_struct(schema: Schema) -> tuple[list[str], str, int] | None:
+    """Check if a schema contains only primitive fields of a single size.
+
+    Consecutive elements of such a struct have no padding between them, so an
+    array of them can be unpacked in one pass.
+
+    Returns (field_names, struct_format, field_size) if uniform, None otherwise.
+    """
+    if (simple_info := _is_simple_struct(schema)) is None:
+        return None
+    field_info, max_align = simple_info
+    if any(size != max_align for _, _, size in field_info):
+        return None
+    return [f[0] for f in field_info], ''.join(f[1] for f in field_info), max_align
+
+
 def _sanitize(name: str) -> str:
     return re.sub(r"[^0-9a-zA-Z_]", "_", name)
 
@@ -100,11 +118,17 @@ def _to_uint8(value: Any) -> int:
     raise TypeError(error_msg)
 
 
-def compile_schema(schema: Schema, sub_schemas: dict[str, Schema]) -> Callable[[MessageDecoder], type]:
+def compile_schema(
+    schema: Schema,
+    sub_schemas: dict[str, Schema],
+    message_types: dict[str, Callable[..., Any]] | None = None,
+) -> Callable[[MessageDecoder], type]:
     """Compile ``schema`` into a decoder function.
 
     The returned function accepts a :class:`MessageDecoder` instance and returns a
-    dynamically constructed dataclass instance with the decoded fields.
+    dynamically constructed dataclass instance with the decoded fields. Message
+    names found in ``message_types`` are instead built by calling the registered
+    constructor with the decoded fields as keyword arguments.
     """
 
     function_defs: list[str] = []
@@ -209,6 +233,25 @@ def compile_schema(schema: Schema, sub_schemas: dict[str, Schema]) -> Callable[[
         dataclass_types[class_name] = dataclass_type
         return dataclass_type
 
+    def emit_uniform_structs(field_name: str, sub_schema: Schema, length_expr: str) -> list[str]:
+        """Unpack an array of uniform structs in one pass instead of per element."""
+        field_names, fmt, size = _uniform_struct(sub_schema)  # type: ignore[misc]
+        create_dataclass_type(sub_schema)
+        sub_class_name = _sanitize(sub_schema.name)
+        element_vars = ', '.join(f'_e_{f}' for f in field_names)
+        kwargs = ', '.join(f'{f}=_e_{f}' for f in field_names)
+        return [
+            f"{_TAB}if {length_expr}:",
+            f"{_TAB}    _data.align({size})",
+            f"{_TAB}_end = _data.position + {size * len(field_names)} * {length_expr}",
+            f"{_TAB}_cls = _dataclass_types[{sub_class_name!r}]",
+            f"{_TAB}_fields[{field_name!r}] = [",
+            f"{_TAB}    _cls({kwargs})",
+            f"{_TAB}    for ({element_vars},) in struct.iter_unpack(fmt_prefix + {fmt!r}, _view[_data.position:_end])",
+            f"{_TAB}]",
+            f"{_TAB}_data.position = _end",
+        ]
+
     def build(current: Schema) -> str:
         func_name = f"decode_{_sanitize(current.name)}"
         if func_name in compiled:
@@ -221,6 +264,7 @@ def compile_schema(schema: Schema, sub_schemas: dict[str, Schema]) -> Callable[[
         lines: list[str] = [
             f"def {func_name}(decoder):",
             f"{_TAB}fmt_prefix = '<' if decoder._is_little_endian else '>'",
+            f"{_TAB}_uint32 = _UINT32_LE if decoder._is_little_endian else _UINT32_BE",
             f"{_TAB}_data = decoder._data",
             f"{_TAB}_view = _data.view",  # Cache memoryview for unpack_from
             f"{_TAB}_fields = {{}}",
@@ -337,6 +381,12 @@ def compile_schema(schema: Schema, sub_schemas: dict[str, Schema]) -> Callable[[
 
             flush()
 
+            # Bounded arrays are sequences (with a length prefix) of at most length elements
+            bound = None
+            if isinstance(field_type, Array) and field_type.is_bounded:
+                bound = field_type.length
+                field_type = Sequence(field_type.type)
+
             if isinstance(field_type, Primitive):
                 lines.append(f"{_TAB}_fields[{field_name!r}] = decoder.{field_type.type}()")
 
@@ -347,15 +397,21 @@ def compile_schema(schema: Schema, sub_schemas: dict[str, Schema]) -> Callable[[
                     var_pos = f'_pos_{field_name}'
                     var_len = f'_len_{field_name}'
                     lines.append(f"{_TAB}{var_pos} = _data.position")
-                    lines.append(f"{_TAB}if {var_pos} & 3:")
-                    lines.append(f"{_TAB}    {var_pos} += 4 - ({var_pos} & 3)")
-                    lines.append(f"{_TAB}{var_len} = _UINT32.unpack_from(_view, {var_pos})[0]")
+                    lines.append(f"{_TAB}if _rem := ({var_pos} - _data.alignment_origin) & 3:")
+                    lines.append(f"{_TAB}    {var_pos} += 4 - _rem")
+                    lines.append(f"{_TAB}{var_len} = _uint32.unpack_from(_view, {var_pos})[0]")
                     lines.append(f"{_TAB}{var_pos} += 4")
+                    lines.append(f"{_TAB}if {var_pos} + {var_len} > len(_view):")
+                    lines.append(
+                        f"{_TAB}    raise CdrDecodeError(f'String length {{{var_len}}} exceeds the "
+                        f"{{len(_view) - {var_pos}}} bytes remaining')"
+                    )
                     lines.append(f"{_TAB}if {var_len} <= 1:")
                     lines.append(f"{_TAB}    _data.position = {var_pos} + {var_len}")
                     lines.append(f"{_TAB}    _fields[{field_name!r}] = ''")
                     lines.append(f"{_TAB}else:")
-                    lines.append(f"{_TAB}    _fields[{field_name!r}] = _view[{var_pos}:{var_pos} + {var_len} - 1].tobytes().decode()")
+                    # Decode straight from the buffer without copying the string bytes out first
+                    lines.append(f"{_TAB}    _fields[{field_name!r}] = str(_view[{var_pos}:{var_pos} + {var_len} - 1], 'utf-8')")
                     lines.append(f"{_TAB}    _data.position = {var_pos} + {var_len}")
                 else:
                     # wstring or other string types - fall back to method call
@@ -379,6 +435,13 @@ def compile_schema(schema: Schema, sub_schemas: dict[str, Schema]) -> Callable[[
                             f"{_TAB}_fields[{field_name!r}] = list(struct.unpack_from(fmt_prefix + '{fmt}', _view, _data.position))"
                         )
                         lines.append(f"{_TAB}_data.position += {total_size}")
+                elif isinstance(elem, Primitive) and elem.type == 'byte':
+                    lines.append(f"{_TAB}_fields[{field_name!r}] = _data.read({field_type.length})")
+                elif isinstance(elem, Primitive) and elem.type == 'char':
+                    # One character per byte so the encoder can map each back with ord()
+                    lines.append(f"{_TAB}_fields[{field_name!r}] = _data.read({field_type.length}).decode('latin-1')")
+                elif isinstance(elem, Complex) and _uniform_struct(sub_schemas[elem.type]) is not None:
+                    lines.extend(emit_uniform_structs(field_name, sub_schemas[elem.type], str(field_type.length)))
                 elif isinstance(elem, Complex):
                     sub_schema = sub_schemas[elem.type]
                     sub_func = build(sub_schema)
@@ -401,27 +464,37 @@ def compile_schema(schema: Schema, sub_schemas: dict[str, Schema]) -> Callable[[
                 if isinstance(elem, Primitive) and elem.type in _STRUCT_FORMAT:
                     # Special optimization for uint8 - keep as bytes instead of unpacking
                     if elem.type == 'uint8':
-                        lines.append(f"{_TAB}_len = decoder.uint32()")
+                        lines.append(f"{_TAB}_len = decoder.sequence_length(1)")
                         lines.append(f"{_TAB}_fields[{field_name!r}] = _data.read(_len)")
                     else:
                         size = _STRUCT_SIZE[elem.type]
                         char = _STRUCT_FORMAT[elem.type]
-                        lines.append(f"{_TAB}_len = decoder.uint32()")
+                        lines.append(f"{_TAB}_len = decoder.sequence_length({size})")
                         lines.append(f"{_TAB}_data.align({size})")
                         # Use unpack_from to avoid intermediate bytes allocation
                         lines.append(
-                            f"{_TAB}_fields[{field_name!r}] = list(struct.unpack_from(fmt_prefix + '{char}' * _len, _view, _data.position))"
+                            f"{_TAB}_fields[{field_name!r}] = list(struct.unpack_from(f'{{fmt_prefix}}{{_len}}{char}', _view, _data.position))"
                         )
                         lines.append(f"{_TAB}_data.position += {size} * _len")
+                elif isinstance(elem, Primitive) and elem.type == 'byte':
+                    lines.append(f"{_TAB}_len = decoder.sequence_length(1)")
+                    lines.append(f"{_TAB}_fields[{field_name!r}] = _data.read(_len)")
+                elif isinstance(elem, Primitive) and elem.type == 'char':
+                    lines.append(f"{_TAB}_len = decoder.sequence_length(1)")
+                    lines.append(f"{_TAB}_fields[{field_name!r}] = _data.read(_len).decode('latin-1')")
+                elif isinstance(elem, Complex) and (uniform := _uniform_struct(sub_schemas[elem.type])) is not None:
+                    _, _, size = uniform
+                    lines.append(f"{_TAB}length = decoder.sequence_length({size})")
+                    lines.extend(emit_uniform_structs(field_name, sub_schemas[elem.type], "length"))
                 elif isinstance(elem, Complex):
                     sub_schema = sub_schemas[elem.type]
                     sub_func = build(sub_schema)
-                    lines.append(f"{_TAB}length = decoder.uint32()")
+                    lines.append(f"{_TAB}length = decoder.sequence_length(1)")
                     lines.append(
                         f"{_TAB}_fields[{field_name!r}] = [{sub_func}(decoder) for _ in range(length)]"
                     )
                 elif isinstance(elem, String):
-                    lines.append(f"{_TAB}length = decoder.uint32()")
+                    lines.append(f"{_TAB}length = decoder.sequence_length(4)")
                     elem_name = elem.type
                     lines.append(
                         f"{_TAB}_fields[{field_name!r}] = [decoder.{elem_name}() for _ in range(length)]"
@@ -489,6 +562,15 @@ def compile_schema(schema: Schema, sub_schemas: dict[str, Schema]) -> Callable[[
             else:
                 lines.append(f"{_TAB}_fields[{field_name!r}] = None")
 
+            if bound is not None:
+                # Count outside the f-string since it cannot reuse its quotes before Python 3.12
+                lines.append(f"{_TAB}_count = len(_fields[{field_name!r}])")
+                lines.append(f"{_TAB}if _count > {bound}:")
+                lines.append(
+                    f"{_TAB}    raise CdrDecodeError(f'Bounded array {field_name} has "
+                    f"{{_count}} elements, more than {bound}')"
+                )
+
         flush()
 
         # Return dataclass instance - always instantiate, even if _fields is empty
@@ -501,9 +583,21 @@ def compile_schema(schema: Schema, sub_schemas: dict[str, Schema]) -> Callable[[
     build(schema)
     code = "import struct\n" + "\n\n".join(function_defs)
 
-    # Pre-compiled struct for inlined string decoding (little-endian uint32)
-    _UINT32 = struct.Struct('<I')
-    namespace: dict[str, object] = {"struct": struct, "_dataclass_types": dataclass_types, "_UINT32": _UINT32}
+    # Use registered constructors in place of the generated dataclasses
+    if message_types:
+        for class_name, dataclass_type in list(dataclass_types.items()):
+            msg_name = normalize_message_name(dataclass_type.__msg_name__)  # type: ignore[attr-defined]
+            if msg_name in message_types:
+                dataclass_types[class_name] = message_types[msg_name]  # type: ignore[assignment]
+
+    # Pre-compiled structs for inlined string decoding (uint32 length prefix)
+    namespace: dict[str, object] = {
+        "struct": struct,
+        "_dataclass_types": dataclass_types,
+        "_UINT32_LE": struct.Struct('<I'),
+        "_UINT32_BE": struct.Struct('>I'),
+        "CdrDecodeError": CdrDecodeError,
+    }
     exec(code, namespace)
     return namespace[f"decode_{_sanitize(schema.name)}"]  # type: ignore[index]
 
@@ -604,19 +698,29 @@ def compile_serializer(schema: Schema, sub_schemas: dict[str, Schema]) -> Callab
                     f"{pad}_payload.write({encoded_var} + b'\\x00')",
                 ]
 
+            if isinstance(field_type, Array) and field_type.is_bounded:
+                # Bounded arrays are written as sequences of at most length elements
+                values_var = new_var("values")
+                return [
+                    f"{pad}{values_var} = {value_expr}",
+                    f"{pad}if len({values_var}) > {field_type.length}:",
+                    f"{pad}    raise ValueError(f'Bounded array size exceeded: at most {field_type.length} "
+                    f"elements, got {{len({values_var})}}')",
+                    *emit(Sequence(field_type.type), values_var, indent),
+                ]
+
             if isinstance(field_type, Array):
                 elem = field_type.type
                 values_var = new_var("values")
                 result: list[str] = [f"{pad}{values_var} = {value_expr}"]
-                # Add validation for fixed-size arrays (non-bounded)
-                if not field_type.is_bounded:
-                    expected_length = field_type.length
-                    result.append(
-                        f"{pad}if len({values_var}) != {expected_length}:"
-                    )
-                    result.append(
-                        f"{pad}    raise ValueError(f'Fixed array size mismatch: expected {expected_length} elements, got {{len({values_var})}}')"
-                    )
+                # Add validation for fixed-size arrays
+                expected_length = field_type.length
+                result.append(
+                    f"{pad}if len({values_var}) != {expected_length}:"
+                )
+                result.append(
+                    f"{pad}    raise ValueError(f'Fixed array size mismatch: expected {expected_length} elements, got {{len({values_var})}}')"
+                )
                 if isinstance(elem, Primitive) and elem.type in _WRITE_FORMAT:
                     # Special optimization for uint8 - write bytes directly if input is bytes/bytearray
                     if elem.type == 'uint8':
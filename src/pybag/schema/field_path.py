@@ -0,0 +1,121 @@
+"""Select individual fields of decoded messages using dotted paths.
+
+A path names nested fields separated by dots, with array elements selected
+by an index in brackets, e.g. ``header.stamp.sec`` or ``poses[0].position.x``.
+"""
+import re
+from dataclasses import fields, is_dataclass
+from typing import Any
+
+from pybag.schema import (
+    Array,
+    Complex,
+    Schema,
+    SchemaConstant,
+    SchemaField,
+    SchemaFieldType,
+    Sequence
+)
+
+_SEGMENT = re.compile(r'([A-Za-z_][A-Za-z0-9_]*)((?:\[\d+\])*)')
+_INDEX = re.compile(r'\[(\d+)\]')
+
+FieldPath = list[str | int]
+
+
+class FieldPathError(Exception):
+    """Exception raised when a field path is invalid for a schema."""
+    def __init__(self, message: str):
+        super().__init__(message)
+
+
+def parse_field_path(path: str) -> FieldPath:
+    """Split a dotted field path into field names and array indices.
+
+    Args:
+        path: The field path, e.g. ``poses[0].position.x``.
+
+    Returns:
+        The field names (str) and array indices (int) in order.
+
+    Raises:
+        FieldPathError: If the path is not well formed.
+    """
+    parts: FieldPath = []
+    for segment in path.split('.'):
+        if (match := _SEGMENT.fullmatch(segment)) is None:
+            raise FieldPathError(f'Invalid field path {path!r}')
+        parts.append(match.group(1))
+        parts.extend(int(index) for index in _INDEX.findall(match.group(2)))
+    return parts
+
+
+def check_field_path(parts: FieldPath, schema: Schema, sub_schemas: dict[str, Schema]) -> None:
+    """Check that a parsed field path exists in a schema.
+
+    Indices into fixed-size arrays are checked against the array length.
+    Indices into sequences and bounded arrays can only be checked per message.
+
+    Raises:
+        FieldPathError: If a field does not exist or an index is applied to a non-array.
+    """
+    current: Schema = schema
+    field_type: SchemaFieldType | None = None
+    for part in parts:
+        if isinstance(part, int):
+            if not isinstance(field_type, (Array, Sequence)):
+                raise FieldPathError(f'Cannot index into non-array field {field_type}')
+            if isinstance(field_type, Array) and not field_type.is_bounded and part >= field_type.length:
+                raise FieldPathError(f'Index {part} out of range for array of length {field_type.length}')
+            field_type = field_type.type
+            continue
+
+        if field_type is not None:
+            if not isinstance(field_type, Complex):
+                raise FieldPathError(f'Field {part!r} requested from non-message field {field_type}')
+            current = sub_schemas[field_type.type]
+        entry = current.fields.get(part)
+        if not isinstance(entry, (SchemaField, SchemaConstant)):
+            raise FieldPathError(f'{current.name} has no field {part!r}')
+        field_type = entry.type
+
+
+def get_field(message: Any, parts: FieldPath) -> Any:
+    """Get the value at a parsed field path of a decoded message.
+
+    Returns:
+        The value, or None if an index is past the end of a sequence.
+    """
+    value = message
+    for part in parts:
+        if isinstance(part, int):
+            if part >= len(value):
+                return None
+            value = value[part]
+        else:
+            value = getattr(value, part)
+    return value
+
+
+def to_dict(message: Any, field_map: dict[tuple[str | int, ...], str], path: tuple[str | int, ...] = ()) -> Any:
+    """Convert a decoded message to nested dicts, renaming the mapped fields.
+
+    Args:
+        message: The decoded message, or a value nested in it.
+        field_map: Parsed field paths mapped to the name their key is given.
+        path: The path of message within the top level message.
+
+    Returns:
+        Nested dicts and lists, with values that are not messages returned as is.
+    """
+    if isinstance(message, list):
+        if not message or not is_dataclass(message[0]):
+            return message
+        return [to_dict(item, field_map, (*path, i)) for i, item in enumerate(message)]
+    if not is_dataclass(message) or isinstance(message, type):
+        return message
+    result = {}
+    for field in fields(message):
+        field_path = (*path, field.name)
+        result[field_map.get(field_path, field.name)] = to_dict(getattr(message, field.name), field_map, field_path)
+    return result
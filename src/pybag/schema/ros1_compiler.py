@@ -25,7 +25,8 @@ from pybag.schema import (
     SchemaField,
     SchemaFieldType,
     Sequence,
-    String
+    String,
+    normalize_message_name
 )
 from pybag.types import ros1
 
@@ -68,13 +69,20 @@ def _to_uint8(value: Any) -> int:
     raise TypeError(f"Cannot convert value of type {type(value)!r} to uint8")
 
 
-def compile_ros1_schema(schema: Schema, sub_schemas: dict[str, Schema]) -> Callable[[MessageDecoder], type]:
+def compile_ros1_schema(
+    schema: Schema,
+    sub_schemas: dict[str, Schema],
+    message_types: dict[str, Callable[..., Any]] | None = None,
+) -> Callable[[MessageDecoder], type]:
     """Compile ``schema`` into a decoder function for ROS 1 messages.
 
     Similar to compile_schema but handles ROS 1 specifics:
     - time/duration as primitives (uint32 sec + uint32 nsec)
     - No alignment requirements
     - Strings without null terminator
+
+    Message names found in ``message_types`` are built by calling the
+    registered constructor with the decoded fields as keyword arguments.
     """
     function_defs: list[str] = []
     compiled: dict[str, str] = {}
@@ -321,6 +329,13 @@ def compile_ros1_schema(schema: Schema, sub_schemas: dict[str, Schema]) -> Calla
     build(schema)
     code = "import struct\n" + "\n\n".join(function_defs)
 
+    # Use registered constructors in place of the generated dataclasses
+    if message_types:
+        for class_name, dataclass_type in list(dataclass_types.items()):
+            msg_name = normalize_message_name(dataclass_type.__msg_name__)  # type: ignore[attr-defined]
+            if msg_name in message_types:
+                dataclass_types[class_name] = message_types[msg_name]  # type: ignore[assignment]
+
     namespace: dict[str, object] = {
         "struct": struct,
         "_dataclass_types": dataclass_types,
@@ -37,7 +37,9 @@ from pybag.schema import (
     SchemaField,
     SchemaFieldType,
     Sequence,
-    String
+    String,
+    decode_definition_text,
+    split_message_definition
 )
 from pybag.types import Message
 
@@ -170,7 +172,7 @@ class _Ros1SchemaParserMixin:
         sub_schemas: dict[str, Schema]
     ) -> None:
         """Add any missing built-in schemas that are referenced but not defined."""
-        schema_text = schema_data.decode('utf-8')
+        schema_text = decode_definition_text(schema_data)
         for builtin_name, builtin_schema in self._builtin_schemas.items():
             if builtin_name not in sub_schemas and builtin_name in schema_text:
                 sub_schemas[builtin_name] = builtin_schema
@@ -199,7 +201,7 @@ class _Ros1SchemaParserMixin:
         msg = '\n'.join(lines)
 
         # Split along '=' delimiter (80 '=' characters separates message defs)
-        msg_parts = [m.strip() for m in msg.split('=' * 80)]
+        msg_parts = [m.strip() for m in split_message_definition(msg)]
 
         msg_schema: dict[str, SchemaEntry] = {}
         # The first message does not have the 'MSG: ' prefix line
@@ -291,7 +293,7 @@ class Ros1McapSchemaDecoder(_Ros1SchemaParserMixin, SchemaDecoder):
 
         result = self._parse_message_definition(
             schema.name,
-            schema.data.decode('utf-8'),
+            decode_definition_text(schema.data),
             schema.data,
         )
 
@@ -508,7 +510,7 @@ def compute_md5sum(message_definition: str, msg_type: str) -> str:
     sub_msg_defs = _parse_sub_message_definitions(message_definition)
 
     # Get the main message definition (first part before any separator)
-    main_def = message_definition.split('=' * 80)[0].strip()
+    main_def = split_message_definition(message_definition)[0].strip()
 
     # Compute MD5 text for the main message
     md5_text = _compute_md5_text(main_def, msg_type, sub_msg_defs)
@@ -529,8 +531,8 @@ def _parse_sub_message_definitions(message_definition: str) -> dict[str, str]:
     """
     sub_msgs: dict[str, str] = {}
 
-    # Split on the 80 '=' separator
-    parts = message_definition.split('=' * 80)
+    # Split on the '=' separator
+    parts = split_message_definition(message_definition)
 
     for part in parts[1:]:  # Skip the first part (main message)
         part = part.strip()
@@ -0,0 +1,319 @@
+import ast
+import logging
+import re
+from typing import Any
+
+from pybag.mcap.records import SchemaRecord
+from pybag.schema import (
+    PRIMITIVE_TYPE_MAP,
+    Array,
+    Complex,
+    Primitive,
+    Schema,
+    SchemaConstant,
+    SchemaDecoder,
+    SchemaEntry,
+    SchemaField,
+    SchemaFieldType,
+    Sequence,
+    String,
+    decode_definition_text
+)
+
+logger = logging.getLogger(__name__)
+
+# Map IDL primitive spellings to the ROS2 primitive names used by the schema
+IDL_PRIMITIVE_TYPE_MAP = {
+    'boolean': 'bool',
+    'octet': 'byte',
+    'wchar': 'uint16',  # XTypes wide characters are 16-bit
+    'short': 'int16',
+    'unsigned short': 'uint16',
+    'long': 'int32',
+    'unsigned long': 'uint32',
+    'long long': 'int64',
+    'unsigned long long': 'uint64',
+    'float': 'float32',
+    'double': 'float64',
+}
+
+# rosidl puts the constants of a message in a module named after the struct
+CONSTANTS_MODULE_SUFFIX = '_Constants'
+
+_TOKEN_RE = re.compile(
+    r'\s*(?:'
+    r'(?P<string>"(?:[^"\\]|\\.)*")'
+    r'|(?P<ident>[A-Za-z_][A-Za-z0-9_]*(?:::[A-Za-z_][A-Za-z0-9_]*)*)'
+    r'|(?P<number>[-+]?(?:\d+\.\d*|\.\d+|\d+)(?:[eE][-+]?\d+)?)'
+    r'|(?P<punct>[{}();<>,\[\]=@])'
+    r')'
+)
+
+_COMMENT_RE = re.compile(r'(?P<string>"(?:[^"\\]|\\.)*")|//[^\n]*|/\*.*?\*/', re.DOTALL)
+
+
+class Ros2IdlError(Exception):
+    """Exception raised for errors in the ROS2 IDL parsing."""
+    def __init__(self, message: str):
+        super().__init__(message)
+
+
+class Ros2IdlSchemaDecoder(SchemaDecoder):
+    """Decode ``ros2idl`` schemas (the OMG IDL subset emitted by ROS2)."""
+
+    def __init__(self):
+        self._cache: dict[int, tuple[Schema, dict[str, Schema]]] = {}
+
+    def _remove_comments(self, text: str) -> str:
+        lines = []
+        for line in text.split('\n'):
+            stripped = line.strip()
+            # Drop delimiters between concatenated definitions and preprocessor lines
+            if stripped.startswith('IDL:') or stripped.startswith('#'):
+                continue
+            if stripped and set(stripped) == {'='}:
+                continue
+            lines.append(line)
+        # Keep string literals (e.g. URLs in @verbatim comments) intact
+        return _COMMENT_RE.sub(lambda m: m.group('string') or ' ', '\n'.join(lines))
+
+    def _tokenize(self, text: str) -> list[str]:
+        tokens = []
+        position = 0
+        text = text.rstrip()
+        while position < len(text):
+            if not (match := _TOKEN_RE.match(text, position)):
+                raise Ros2IdlError(f'Unexpected character in IDL: {text[position:position + 20]!r}')
+            tokens.append(match.group(match.lastgroup))
+            position = match.end()
+        return tokens
+
+    def _expect(self, tokens: list[str], index: int, value: str) -> int:
+        if index >= len(tokens) or tokens[index] != value:
+            found = tokens[index] if index < len(tokens) else 'end of schema'
+            raise Ros2IdlError(f'Expected "{value}" but found "{found}"')
+        return index + 1
+
+    def _resolve_name(self, name: str, scope: list[str]) -> str:
+        if '::' in name:
+            return name.replace('::', '/')
+        return '/'.join([*scope, name])
+
+    def _parse_bound(self, tokens: list[str], index: int) -> tuple[int, int]:
+        """Parse the ``N>`` ending a bounded string or sequence."""
+        if not tokens[index].isdigit():
+            raise Ros2IdlError(f'Expected a bound but found "{tokens[index]}"')
+        return int(tokens[index]), self._expect(tokens, index + 1, '>')
+
+    def _parse_type(
+        self,
+        tokens: list[str],
+        index: int,
+        scope: list[str],
+        typedefs: dict[str, SchemaFieldType],
+    ) -> tuple[SchemaFieldType, int]:
+        token = tokens[index]
+        if token == 'sequence':
+            index = self._expect(tokens, index + 1, '<')
+            element_type, index = self._parse_type(tokens, index, scope, typedefs)
+            if tokens[index] == ',':  # sequence<T, N>
+                bound, index = self._parse_bound(tokens, index + 1)
+                return Array(element_type, bound, is_bounded=True), index
+            index = self._expect(tokens, index, '>')
+            return Sequence(element_type), index
+
+        if token in ('string', 'wstring'):
+            if index + 1 < len(tokens) and tokens[index + 1] == '<':  # string<N>
+                bound, index = self._parse_bound(tokens, index + 2)
+                return String(token, max_length=bound), index
+            return String(token), index + 1
+
+        # Some spellings are several words, e.g. unsigned long long
+        for length in (3, 2, 1):
+            spelling = ' '.join(tokens[index:index + length])
+            if spelling in IDL_PRIMITIVE_TYPE_MAP:
+                return Primitive(IDL_PRIMITIVE_TYPE_MAP[spelling]), index + length
+        if token in PRIMITIVE_TYPE_MAP:
+            return Primitive(token), index + 1
+
+        name = self._resolve_name(token, scope)
+        if name in typedefs:
+            return typedefs[name], index + 1
+        return Complex(name), index + 1
+
+    def _parse_declarator(
+        self,
+        tokens: list[str],
+        index: int,
+        field_type: SchemaFieldType,
+    ) -> tuple[str, SchemaFieldType, int]:
+        """Parse a declared name with an optional fixed array size, e.g. ``covariance[36]``."""
+        name = tokens[index]
+        index += 1
+        if tokens[index] == '[':
+            if not tokens[index + 1].isdigit():
+                raise Ros2IdlError(f'Expected an array size but found "{tokens[index + 1]}"')
+            field_type = Array(field_type, int(tokens[index + 1]))
+            index = self._expect(tokens, index + 2, ']')
+            if tokens[index] == '[':
+                raise Ros2IdlError(f'Multi-dimensional arrays are not supported ({name})')
+        return name, field_type, index
+
+    def _parse_annotations(self, tokens: list[str], index: int) -> tuple[dict[str, list[str]], int]:
+        """Parse ``@name`` or ``@name (...)`` annotations, returning their raw arguments."""
+        annotations: dict[str, list[str]] = {}
+        while index < len(tokens) and tokens[index] == '@':
+            name = tokens[index + 1]
+            index += 2
+            arguments: list[str] = []
+            if index < len(tokens) and tokens[index] == '(':
+                depth = 0
+                while True:
+                    if index >= len(tokens):
+                        raise Ros2IdlError(f'Unterminated annotation @{name}')
+                    token = tokens[index]
+                    index += 1
+                    depth += (token == '(') - (token == ')')
+                    if depth == 0:
+                        break
+                    arguments.append(token)
+                arguments = arguments[1:]  # Drop the opening parenthesis
+            annotations[name] = arguments
+        return annotations, index
+
+    def _parse_value(self, field_type: SchemaFieldType, token: str) -> Any:
+        """Convert an IDL literal to the Python value of the field type."""
+        if isinstance(field_type, String):
+            if not (token.startswith('"') and token.endswith('"')):
+                raise Ros2IdlError(f'Expected a string literal but found {token}')
+            return ast.literal_eval(token)
+        if isinstance(field_type, Primitive):
+            if field_type.type == 'bool':
+                if token not in ('TRUE', 'FALSE'):
+                    raise Ros2IdlError(f'Expected TRUE or FALSE but found {token}')
+                return token == 'TRUE'
+            if field_type.type in ('float32', 'float64'):
+                return float(token)
+            try:
+                return int(token, 0)
+            except ValueError:
+                raise Ros2IdlError(f'Expected an integer but found {token}') from None
+        raise Ros2IdlError(f'Values of {field_type} are not supported')
+
+    def _parse_struct(
+        self,
+        tokens: list[str],
+        index: int,
+        scope: list[str],
+        structs: dict[str, Schema],
+        typedefs: dict[str, SchemaFieldType],
+    ) -> int:
+        name = tokens[index]
+        index = self._expect(tokens, index + 1, '{')
+
+        fields: dict[str, SchemaEntry] = {}
+        while tokens[index] != '}':
+            annotations, index = self._parse_annotations(tokens, index)
+            field_type, index = self._parse_type(tokens, index, scope, typedefs)
+            field_name, field_type, index = self._parse_declarator(tokens, index, field_type)
+            index = self._expect(tokens, index, ';')
+
+            default = None
+            if (arguments := annotations.get('default')) is not None:
+                # @default (value=...)
+                if len(arguments) != 3 or arguments[:2] != ['value', '=']:
+                    raise Ros2IdlError(f'Unsupported default for {field_name}: {arguments}')
+                default = self._parse_value(field_type, arguments[2])
+            fields[field_name] = SchemaField(field_type, default)
+
+        struct_name = '/'.join([*scope, name])
+        structs[struct_name] = Schema(struct_name, fields)
+        return self._expect(tokens, index + 1, ';')
+
+    def _parse_constants(
+        self,
+        tokens: list[str],
+        index: int,
+        scope: list[str],
+        typedefs: dict[str, SchemaFieldType],
+    ) -> tuple[dict[str, SchemaEntry], int]:
+        constants: dict[str, SchemaEntry] = {}
+        while tokens[index] != '}':
+            _, index = self._parse_annotations(tokens, index)
+            index = self._expect(tokens, index, 'const')
+            constant_type, index = self._parse_type(tokens, index, scope, typedefs)
+            constant_name = tokens[index]
+            index = self._expect(tokens, index + 1, '=')
+            value = self._parse_value(constant_type, tokens[index])
+            index = self._expect(tokens, index + 1, ';')
+            constants[constant_name] = SchemaConstant(constant_type, value)
+        return constants, index
+
+    def _parse_definitions(
+        self,
+        tokens: list[str],
+        index: int,
+        scope: list[str],
+        structs: dict[str, Schema],
+        typedefs: dict[str, SchemaFieldType],
+        constants: dict[str, dict[str, SchemaEntry]],
+    ) -> int:
+        while index < len(tokens) and tokens[index] != '}':
+            _, index = self._parse_annotations(tokens, index)
+            token = tokens[index]
+            if token == 'module':
+                module_name = tokens[index + 1]
+                index = self._expect(tokens, index + 2, '{')
+                if module_name.endswith(CONSTANTS_MODULE_SUFFIX):
+                    struct_name = '/'.join([*scope, module_name.removesuffix(CONSTANTS_MODULE_SUFFIX)])
+                    constants[struct_name], index = self._parse_constants(tokens, index, scope, typedefs)
+                else:
+                    index = self._parse_definitions(
+                        tokens, index, [*scope, module_name], structs, typedefs, constants
+                    )
+                index = self._expect(tokens, index, '}')
+                index = self._expect(tokens, index, ';')
+            elif token == 'struct':
+                index = self._parse_struct(tokens, index + 1, scope, structs, typedefs)
+            elif token == 'typedef':
+                # e.g. typedef double double__36[36];
+                base_type, index = self._parse_type(tokens, index + 1, scope, typedefs)
+                alias, alias_type, index = self._parse_declarator(tokens, index, base_type)
+                typedefs['/'.join([*scope, alias])] = alias_type
+                index = self._expect(tokens, index, ';')
+            else:
+                raise Ros2IdlError(f'Unsupported IDL definition: {token}')
+        return index
+
+    def parse_schema(self, schema: SchemaRecord) -> tuple[Schema, dict[str, Schema]]:
+        if schema.id in self._cache:
+            return self._cache[schema.id]
+
+        assert schema.encoding == "ros2idl"
+
+        text = self._remove_comments(decode_definition_text(schema.data))
+        tokens = self._tokenize(text)
+
+        structs: dict[str, Schema] = {}
+        constants: dict[str, dict[str, SchemaEntry]] = {}
+        if self._parse_definitions(tokens, 0, [], structs, {}, constants) != len(tokens):
+            raise Ros2IdlError('Unbalanced "}" in IDL schema')
+
+        # Constants come before the fields, as in the ros2msg schema
+        for struct_name, struct_constants in constants.items():
+            if struct_name in structs:
+                structs[struct_name].fields = {**struct_constants, **structs[struct_name].fields}
+
+        # Schema names may omit the interface namespace (e.g. pkg/Type)
+        main_name = schema.name
+        if main_name not in structs:
+            package_name, _, type_name = main_name.rpartition('/')
+            main_name = f'{package_name}/msg/{type_name}'
+        if main_name not in structs:
+            raise Ros2IdlError(f'Schema {schema.name} not defined in IDL')
+
+        main_schema = Schema(schema.name, structs.pop(main_name).fields)
+        result = main_schema, structs
+
+        self._cache[schema.id] = result
+        return result
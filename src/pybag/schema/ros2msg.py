@@ -28,7 +28,10 @@ from pybag.schema import (
     SchemaField,
     SchemaFieldType,
     Sequence,
-    String
+    String,
+    decode_definition_text,
+    normalize_message_name,
+    split_message_definition
 )
 from pybag.types import Message
 
@@ -41,6 +44,31 @@ class Ros2MsgError(Exception):
         super().__init__(message)
 
 
+# Components of service and action definitions, in the order of their '---' separated blocks
+_INTERFACE_COMPONENTS = {
+    2: ('Request', 'Response'),
+    3: ('Goal', 'Result', 'Feedback'),
+}
+
+
+def _select_interface_block(schema_name: str, definition: str) -> str:
+    """Select the block of a service or action definition named by the schema.
+
+    Service (``.srv``) and action (``.action``) definitions are split into
+    blocks by ``---`` lines. Schema names such as ``pkg/srv/AddTwoInts_Response``
+    select the matching block, and the first block is used otherwise.
+    """
+    blocks = re.split(r'^[ \t]*---[ \t]*$', definition, flags=re.MULTILINE)
+    if len(blocks) == 1:
+        return definition
+    if (components := _INTERFACE_COMPONENTS.get(len(blocks))) is None:
+        raise Ros2MsgError(f'Definition of {schema_name} has {len(blocks)} blocks separated by ---')
+    suffix = schema_name.rsplit('/', 1)[-1].rsplit('_', 1)[-1]
+    if suffix in components:
+        return blocks[components.index(suffix)]
+    return blocks[0]
+
+
 class Ros2MsgSchemaDecoder(SchemaDecoder):
     def __init__(self):
         self._cache: dict[int, tuple[Schema, dict[str, Schema]]] = {}
@@ -104,17 +132,22 @@ class Ros2MsgSchemaDecoder(SchemaDecoder):
         raise Ros2MsgError('Default values not supported for this field type')
 
     def _parse_field_type(self, field_raw_type: str, package_name: str) -> SchemaFieldType:
-        # Handle array and sequence types
-        if array_match := re.match(r'(.*)\[(.*)\]$', field_raw_type):
+        # Handle array and sequence types: T[] (sequence), T[N] (fixed) and T[<=N] (bounded).
+        # The bound of a string element (string<=N[...]) is part of the element type.
+        if array_match := re.match(r'([^\[\]]+)\[([^\[\]]*)\]$', field_raw_type):
             element_raw, length_spec = array_match.groups()
-            element_field = self._parse_field_type(element_raw, package_name)
+            if not (length_match := re.fullmatch(r'(<=)?(\d*)', length_spec)):
+                raise Ros2MsgError(f'Invalid array length: {field_raw_type}')
+            is_bounded, length = length_match.group(1) is not None, length_match.group(2)
+            if is_bounded and not length:
+                raise Ros2MsgError(f'Bounded sequence has no maximum length: {field_raw_type}')
 
-            if length_spec == '':
+            element_field = self._parse_field_type(element_raw, package_name)
+            if not length:
                 return Sequence(element_field)
-
-            is_bounded = length_spec.startswith('<=')
-            length = int(length_spec[2:]) if is_bounded else int(length_spec)
-            return Array(element_field, length, is_bounded)
+            return Array(element_field, int(length), is_bounded)
+        if '[' in field_raw_type or ']' in field_raw_type:
+            raise Ros2MsgError(f'Invalid array type: {field_raw_type}')
 
         # Handle string types
         if field_raw_type.startswith('string'):
@@ -131,13 +164,16 @@ class Ros2MsgSchemaDecoder(SchemaDecoder):
         # Handle primitive types
         if field_raw_type in PRIMITIVE_TYPE_MAP:
             return Primitive(field_raw_type)
+        if '<=' in field_raw_type:
+            raise Ros2MsgError(f'Only strings can be bounded: {field_raw_type}')
 
         # Handle complex types
         if field_raw_type == 'Header':
             field_raw_type = 'std_msgs/Header'
         elif '/' not in field_raw_type:
             field_raw_type = f'{package_name}/{field_raw_type}'
-        return Complex(field_raw_type)
+        # Sub-schemas are keyed by pkg/Type, even if referenced as pkg/msg/Type
+        return Complex(normalize_message_name(field_raw_type))
 
     def _parse_field(self, field: str, package_name: str) -> tuple[str, SchemaEntry]:
         # Extract the field type, name and optional default value
@@ -198,7 +234,7 @@ class Ros2MsgSchemaDecoder(SchemaDecoder):
         sub_schemas: dict[str, Schema]
     ) -> None:
         """Add any missing built-in schemas that are referenced but not defined."""
-        schema_text = main_schema.data.decode('utf-8')
+        schema_text = normalize_message_name(decode_definition_text(main_schema.data))
         for builtin_name, builtin_schema in self._builtin_schemas.items():
             if builtin_name not in sub_schemas and builtin_name in schema_text:
                 sub_schemas[builtin_name] = builtin_schema
@@ -210,7 +246,7 @@ class Ros2MsgSchemaDecoder(SchemaDecoder):
         assert schema.encoding == "ros2msg"
 
         package_name = schema.name.split('/')[0]
-        msg = schema.data.decode('utf-8')
+        msg = decode_definition_text(schema.data)
 
         # Remove comments and empty lines
         lines = [self._remove_inline_comment(line) for line in msg.split('\n')]
@@ -218,18 +254,20 @@ class Ros2MsgSchemaDecoder(SchemaDecoder):
         msg = '\n'.join(lines)
 
         # Split along '=' delimiter
-        msg = [m.strip() for m in msg.split('=' * 80)]
+        msg = [m.strip() for m in split_message_definition(msg)]
 
         msg_schema = {}
         # The first message does not have the 'MSG: ' prefix line
-        main_fields = [m.strip() for m in msg[0].split('\n') if m.strip()]
+        main_definition = _select_interface_block(schema.name, msg[0])
+        main_fields = [m.strip() for m in main_definition.split('\n') if m.strip()]
         for raw_field in main_fields:
             field_name, field = self._parse_field(raw_field, package_name)
             msg_schema[field_name] = field
 
         sub_msg_schemas = {}
         for sub_msg in msg[1:]:
-            sub_msg_name = sub_msg.split('\n')[0].strip()[5:]  # Remove 'MSG: ' prefix
+            # Remove 'MSG: ' prefix
+            sub_msg_name = normalize_message_name(sub_msg.split('\n')[0].strip()[5:])
             sub_msg_fields = [m.strip() for m in sub_msg.split('\n')[1:] if m]
             # TODO: Do some caching here
             sub_msg_schema = {}
@@ -0,0 +1,117 @@
+"""Convert fixed-layout messages to numpy structured arrays.
+
+numpy is an optional dependency and is only imported when needed.
+"""
+from typing import Any
+
+from pybag.schema import (
+    Array,
+    Complex,
+    Primitive,
+    Schema,
+    SchemaField,
+    SchemaFieldType
+)
+
+# numpy type codes of the fixed-size primitives
+_PRIMITIVE_DTYPE = {
+    'bool': '?',
+    'byte': 'u1',
+    'char': 'u1',
+    'int8': 'i1',
+    'uint8': 'u1',
+    'int16': 'i2',
+    'uint16': 'u2',
+    'int32': 'i4',
+    'uint32': 'u4',
+    'int64': 'i8',
+    'uint64': 'u8',
+    'float32': 'f4',
+    'float64': 'f8',
+}
+
+# ROS1 time and duration primitives are stored as (secs, nsecs) structs
+_TIME_DTYPE = {
+    'time': [('secs', 'u4'), ('nsecs', 'u4')],
+    'duration': [('secs', 'i4'), ('nsecs', 'i4')],
+}
+
+
+class StructuredTypeError(Exception):
+    """Exception raised when a schema has no fixed-size layout."""
+    def __init__(self, message: str):
+        super().__init__(message)
+
+
+def _field_dtype(field_type: SchemaFieldType, sub_schemas: dict[str, Schema]) -> Any:
+    if isinstance(field_type, Primitive):
+        if field_type.type in _PRIMITIVE_DTYPE:
+            return _PRIMITIVE_DTYPE[field_type.type]
+        if field_type.type in _TIME_DTYPE:
+            return _TIME_DTYPE[field_type.type]
+    elif isinstance(field_type, Complex):
+        return schema_dtype(sub_schemas[field_type.type], sub_schemas)
+    elif isinstance(field_type, Array) and not field_type.is_bounded:
+        return (_field_dtype(field_type.type, sub_schemas), (field_type.length,))
+    raise StructuredTypeError(f'{field_type} does not have a fixed size')
+
+
+def schema_dtype(schema: Schema, sub_schemas: dict[str, Schema]) -> list[tuple]:
+    """Build a numpy structured dtype description mirroring a schema.
+
+    Nested messages become nested structured types and fixed-length arrays
+    become sub-arrays. Constants are skipped.
+
+    Args:
+        schema: The message schema.
+        sub_schemas: The schemas of nested messages.
+
+    Returns:
+        A list of (name, type) pairs accepted by ``numpy.dtype``.
+
+    Raises:
+        StructuredTypeError: If the schema contains strings, sequences or bounded arrays.
+    """
+    dtype = []
+    for name, entry in schema.fields.items():
+        if not isinstance(entry, SchemaField):
+            continue
+        field_dtype = _field_dtype(entry.type, sub_schemas)
+        if isinstance(field_dtype, tuple):
+            dtype.append((name, *field_dtype))
+        else:
+            dtype.append((name, field_dtype))
+    return dtype
+
+
+def _to_record(value: Any, field_type: SchemaFieldType, sub_schemas: dict[str, Schema]) -> Any:
+    if isinstance(field_type, Complex):
+        return message_record(value, sub_schemas[field_type.type], sub_schemas)
+    if isinstance(field_type, Array):
+        return [_to_record(v, field_type.type, sub_schemas) for v in value]
+    if isinstance(field_type, Primitive):
+        if field_type.type in _TIME_DTYPE:
+            return (value.secs, value.nsecs)
+        if isinstance(value, bytes):  # byte
+            return value[0]
+        if isinstance(value, str):  # ROS2 char
+            return ord(value)
+    return value
+
+
+def message_record(message: Any, schema: Schema, sub_schemas: dict[str, Schema]) -> tuple:
+    """Convert a decoded message into a row of a structured array.
+
+    Args:
+        message: The decoded message.
+        schema: The message schema.
+        sub_schemas: The schemas of nested messages.
+
+    Returns:
+        A (possibly nested) tuple matching ``schema_dtype(schema, sub_schemas)``.
+    """
+    return tuple(
+        _to_record(getattr(message, name), entry.type, sub_schemas)
+        for name, entry in schema.fields.items()
+        if isinstance(entry, SchemaField)
+    )
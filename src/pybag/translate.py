@@ -12,6 +12,7 @@ from typing import Any, TypeVar
 
 from pybag.ros2.humble.builtin_interfaces import Duration as Ros2Duration
 from pybag.ros2.humble.builtin_interfaces import Time as Ros2Time
+from pybag.schema import is_definition_separator
 from pybag.types import Duration as Ros1Duration
 from pybag.types import SchemaText
 from pybag.types import Time as Ros1Time
@@ -201,7 +202,7 @@ def translate_schema_ros1_to_ros2(msg_name: str, schema_text: str) -> SchemaText
     This function transforms ROS1 message definition text to ROS2 format:
     - time -> builtin_interfaces/Time (with sec/nanosec fields)
     - duration -> builtin_interfaces/Duration (with sec/nanosec fields)
-    - char remains as-is (handled by the serializer as uint8 vs string)
+    - char remains as-is (both profiles encode it as a uint8)
     - Message name: package/Message -> package/msg/Message
 
     Args:
@@ -236,7 +237,7 @@ def translate_schema_ros1_to_ros2(msg_name: str, schema_text: str) -> SchemaText
             continue
 
         # Check for separator
-        if stripped == '=' * 80:
+        if is_definition_separator(stripped):
             result_lines.append(line)
             continue
 
@@ -303,10 +304,9 @@ def _is_separator_line(line: str) -> bool:
 
     The standard separator is 80 '=' characters. However, some tools
     (e.g., certain MCAP writers) incorrectly use 40 '=' characters.
-    We accept both for compatibility with real-world files.
+    Any run of three or more is accepted for compatibility with real-world files.
     """
-    stripped = line.strip()
-    return stripped == '=' * 80
+    return is_definition_separator(line.strip())
 
 
 def translate_schema_ros2_to_ros1(msg_name: str, schema_text: str) -> SchemaText:
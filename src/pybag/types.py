@@ -97,8 +97,8 @@ ros1 = SimpleNamespace(
 # ROS 2 namespace for ROS 2 specific types
 # Usage: t.ros2.char
 ros2 = SimpleNamespace(
-    # ROS 2 char is a single character (string)
-    char = Annotated[str, ("char",)]
+    # ROS 2 char is an unsigned 8-bit integer (as in rclpy)
+    char = Annotated[int, ("char",)]
 )
 
 
@@ -38,7 +38,7 @@ class CharMessageRos1:
 
 @dataclass(kw_only=True)
 class CharMessageRos2:
-    """Message with ROS2 char field (string)."""
+    """Message with ROS2 char field (uint8)."""
     __msg_name__ = 'test_msgs/CharMessage'
     value: t.ros2.char
 
@@ -426,11 +426,11 @@ def test_convert_bag_to_mcap_with_char(tmp_path: Path) -> None:
     # Convert to mcap
     convert(bag_path, mcap_path)
 
-    # Verify the char value is preserved (as string in ROS2)
+    # Verify the char value is preserved
     with McapFileReader.from_file(mcap_path) as reader:
         messages = list(reader.messages("/char"))
         assert len(messages) == 1
-        assert messages[0].data.value == 'A'
+        assert messages[0].data.value == 65  # ASCII 'A'
 
 
 def test_convert_mcap_to_bag_with_char(tmp_path: Path) -> None:
@@ -438,7 +438,7 @@ def test_convert_mcap_to_bag_with_char(tmp_path: Path) -> None:
     mcap_path = tmp_path / "input.mcap"
     bag_path = tmp_path / "output.bag"
 
-    # Create mcap with ROS2 char message (string)
+    # Create mcap with ROS2 char message
     with McapFileWriter.open(mcap_path, chunk_size=1024) as writer:
         msg = CharMessageRos2(value='A')
         writer.write_message("/char", int(1e9), msg)
@@ -1,6 +1,7 @@
 from pathlib import Path
 
 import pytest
+import zstandard as zstd
 
 from pybag.bag.record_parser import BagRecordParser
 from pybag.bag.records import BagRecordType
@@ -81,6 +82,30 @@ def test_cli_recover_with_compression(tmp_path: Path) -> None:
         assert [m.data.data for m in messages] == list(range(10))
 
 
+def test_cli_recover_zstd_dictionary(tmp_path: Path) -> None:
+    """Test recovery of a file whose zstd chunks were compressed with a dictionary."""
+    input_path = tmp_path / "input.mcap"
+    output_path = tmp_path / "recovered.mcap"
+
+    samples = [bytes([i % 7]) * 64 + bytes(range(i % 50)) for i in range(200)]
+    dictionary = zstd.train_dictionary(1024, samples).as_bytes()
+    with McapFileWriter.open(
+        input_path,
+        chunk_size=256,
+        chunk_compression="zstd",
+        zstd_dictionary=dictionary,
+    ) as writer:
+        for i in range(20):
+            writer.write_message("/test", int((i + 1) * 1e9), Int32(data=i))
+
+    cli_main(["recover", str(input_path), "--output", str(output_path)])
+
+    with McapFileReader.from_file(output_path) as reader:
+        assert [m.data.data for m in reader.messages("/test")] == list(range(20))
+        # The recovered chunks do not use the dictionary, so it is not copied
+        assert reader.get_attachments() == []
+
+
 def test_cli_recover_overwrite(tmp_path: Path) -> None:
     """Test that recovery respects overwrite flag."""
     input_path = tmp_path / "input.mcap"
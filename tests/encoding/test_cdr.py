@@ -1,6 +1,19 @@
+import struct
+
 import pytest
 
-from pybag.encoding.cdr import CdrDecoder, CdrEncoder
+from pybag.encoding.cdr import CdrDecodeError, CdrDecoder, CdrEncoder
+from pybag.schema import (
+    Array,
+    Complex,
+    Primitive,
+    Schema,
+    SchemaField,
+    SchemaFieldType,
+    Sequence,
+    String
+)
+from pybag.schema.compiler import compile_schema, compile_serializer
 
 
 @pytest.mark.parametrize('little_endian', [True, False])
@@ -36,6 +49,23 @@ def test_encode_decode_all_primitive_types(little_endian: bool) -> None:
             assert decoder.parse(type_name) == v
 
 
+@pytest.mark.parametrize('little_endian', [True, False])
+def test_wstring_is_utf16(little_endian: bool) -> None:
+    value = 'Grüße 世界 🚀'  # The rocket needs a surrogate pair
+    encoder = CdrEncoder(little_endian=little_endian)
+    encoder.wstring(value)
+    encoder.uint8(7)
+    data = encoder.save()
+
+    utf16 = value.encode('utf-16-le' if little_endian else 'utf-16-be')
+    length = (len(utf16) // 2).to_bytes(4, 'little' if little_endian else 'big')
+    assert data[4:] == length + utf16 + b'\x07'
+
+    decoder = CdrDecoder(data)
+    assert decoder.wstring() == value
+    assert decoder.uint8() == 7
+
+
 @pytest.mark.parametrize('little_endian', [True, False])
 def test_encode_decode_array(little_endian: bool) -> None:
     # Encode the data
@@ -56,3 +86,387 @@ def test_encode_decode_sequence(little_endian: bool) -> None:
     # Decode the data
     decoder = CdrDecoder(encoder.save())
     assert decoder.sequence('int32') == [1, 2, 3]
+
+
+@pytest.mark.parametrize('little_endian', [True, False])
+def test_decode_again_after_seek_and_reset(little_endian: bool) -> None:
+    encoder = CdrEncoder(little_endian=little_endian)
+    encoder.uint8(7)
+    encoder.string('hello')
+    encoder.float64(1.5)
+
+    decoder = CdrDecoder(encoder.save())
+    assert decoder.uint8() == 7
+    position = decoder.position()
+    first = (decoder.string(), decoder.float64())
+
+    assert decoder.seek(position).position() == position
+    assert (decoder.string(), decoder.float64()) == first
+
+    decoder.reset()
+    assert decoder.position() == 4
+    assert (decoder.uint8(), decoder.string(), decoder.float64()) == (7, *first)
+
+    for position in (3, len(encoder.save()) + 1):
+        with pytest.raises(ValueError, match='outside the message payload'):
+            decoder.seek(position)
+
+
+@pytest.mark.parametrize('little_endian', [True, False])
+def test_default_alignment_origin_is_classic_cdr(little_endian: bool) -> None:
+    encoder = CdrEncoder(little_endian=little_endian)
+    encoder.uint8(1)
+    encoder.float64(2.5)
+    encoder.uint16(3)
+    encoder.string('four')
+    encoder.array('int32', [5, 6])
+    data = encoder.save()
+
+    for decoder in (CdrDecoder(data), CdrDecoder(data, alignment_origin=4)):
+        assert decoder.uint8() == 1
+        assert decoder.float64() == 2.5
+        assert decoder.position() == 20  # Aligned to 8 bytes after the header
+        assert decoder.uint16() == 3
+        assert decoder.string() == 'four'
+        assert decoder.array('int32', 2) == [5, 6]
+        assert decoder.position() == len(data)
+
+
+@pytest.mark.parametrize('little_endian', [True, False])
+def test_headerless_decoder(little_endian: bool) -> None:
+    encoder = CdrEncoder(little_endian=little_endian)
+    encoder.uint8(1)
+    encoder.float64(2.5)
+    payload = encoder.save()[4:]
+
+    decoder = CdrDecoder.headerless(payload, little_endian=little_endian)
+    assert decoder.position() == 0
+    assert decoder.uint8() == 1
+    assert decoder.float64() == 2.5
+    assert decoder.position() == len(payload)
+
+    decoder.reset(payload)
+    assert (decoder.uint8(), decoder.float64()) == (1, 2.5)
+
+
+def test_alignment_origin_after_delimiter_header() -> None:
+    # A 4-byte DHEADER followed by a struct aligned from the end of the DHEADER
+    data = (
+        b'\x00\x09\x00\x00'  # XCDR2 little endian header
+        + b'\x00\x00\x00\x00'  # DHEADER
+        + b'\x01' + b'\x00' * 7 + struct.pack('<d', 2.5)
+        + b'\x03\x00\x00\x00' + struct.pack('<I', 3) + b'ab\x00'
+    )
+    decoder = CdrDecoder(data, alignment_origin=8)
+    decoder.seek(8)
+    assert decoder.uint8() == 1
+    assert decoder.float64() == 2.5
+    assert decoder.position() == 24
+    assert decoder.uint16() == 3
+    assert decoder.string() == 'ab'
+    assert decoder.position() == len(data)
+
+    with pytest.raises(ValueError, match='inside the CDR header'):
+        CdrDecoder(data, alignment_origin=2)
+
+
+def test_compiled_string_alignment_origin() -> None:
+    # A struct aligned from 2 bytes after the header, so strings are not 4-byte aligned in the data
+    data = (
+        b'\x00\x01\x00\x00'  # CDR little endian header
+        + b'\xff\xff'  # Prefix before the alignment origin
+        + b'\x01' + b'\x00' * 7 + struct.pack('<d', 2.5)
+        + struct.pack('<H', 3) + b'\x00' * 2 + struct.pack('<I', 3) + b'ab\x00'
+    )
+    schema = Schema('pkg/Offset', {
+        'tag': SchemaField(Primitive('uint8')),
+        'value': SchemaField(Primitive('float64')),
+        'count': SchemaField(Primitive('uint16')),
+        'name': SchemaField(String('string')),
+    })
+
+    decoder = CdrDecoder(data, alignment_origin=6)
+    decoder.seek(6)
+    assert (decoder.uint8(), decoder.float64(), decoder.uint16(), decoder.string()) == (1, 2.5, 3, 'ab')
+
+    decoder = CdrDecoder(data, alignment_origin=6)
+    decoder.seek(6)
+    message = compile_schema(schema, {})(decoder)
+    assert (message.tag, message.value, message.count, message.name) == (1, 2.5, 3, 'ab')
+    assert decoder.position() == len(data)
+
+
+def test_compiled_empty_schema() -> None:
+    decoder = CdrDecoder(CdrEncoder().save())
+    message = compile_schema(Schema('std_msgs/msg/Empty', {}), {})(decoder)
+    assert vars(message) == {}
+    assert decoder.position() == 4
+
+
+@pytest.mark.parametrize('little_endian', [True, False])
+def test_decode_sequence_length_exceeds_data(little_endian: bool) -> None:
+    encoder = CdrEncoder(little_endian=little_endian)
+    encoder.uint32(0xFFFFFFFF)
+    encoder.int32(1)
+
+    decoder = CdrDecoder(encoder.save())
+    with pytest.raises(CdrDecodeError, match='Sequence length 4294967295'):
+        decoder.sequence('int32')
+
+
+@pytest.mark.parametrize('element', [
+    Primitive('float64'),
+    Primitive('uint8'),
+    String('string'),
+    Complex('pkg/Point'),
+])
+def test_compiled_decode_sequence_length_exceeds_data(element: SchemaFieldType) -> None:
+    schema = Schema('pkg/Values', {'values': SchemaField(Sequence(element))})
+    sub_schemas = {'pkg/Point': Schema('pkg/Point', {'x': SchemaField(Primitive('float64'))})}
+    decode = compile_schema(schema, sub_schemas)
+
+    encoder = CdrEncoder()
+    encoder.uint32(0xFFFFFFFF)
+    encoder.float64(1.0)
+
+    with pytest.raises(CdrDecodeError, match='Sequence length 4294967295'):
+        decode(CdrDecoder(encoder.save()))
+
+
+@pytest.mark.parametrize('length', [0xFFFFFFFF, 0xFFFFFFFF - 3, 9])
+@pytest.mark.parametrize('little_endian', [True, False])
+def test_decode_string_length_exceeds_data(little_endian: bool, length: int) -> None:
+    encoder = CdrEncoder(little_endian=little_endian)
+    encoder.uint32(length)
+    encoder.int32(1)  # Only 4 bytes follow the length
+
+    decoder = CdrDecoder(encoder.save())
+    with pytest.raises(CdrDecodeError, match=f'String length {length} exceeds the 4 bytes'):
+        decoder.string()
+    with pytest.raises(CdrDecodeError, match=f'Sequence length {length}'):
+        CdrDecoder(encoder.save()).wstring()
+
+    schema = Schema('pkg/Name', {'name': SchemaField(String('string'))})
+    with pytest.raises(CdrDecodeError, match=f'String length {length} exceeds the 4 bytes'):
+        compile_schema(schema, {})(CdrDecoder(encoder.save()))
+
+
+@pytest.mark.parametrize('little_endian', [True, False])
+def test_decode_string_sequence_with_empty_strings(little_endian: bool) -> None:
+    # Writers encode an empty string either as length 0 or as a lone null terminator
+    order = '<' if little_endian else '>'
+    data = (
+        (b'\x00\x01' if little_endian else b'\x00\x00') + b'\x00\x00'
+        + b'\x07' + b'\x00' * 3                    # uint8 tag and padding
+        + struct.pack(f'{order}I', 4)               # Sequence length
+        + struct.pack(f'{order}I', 0)               # '' without a terminator
+        + struct.pack(f'{order}I', 1) + b'\x00'     # '' with only a terminator
+        + b'\x00' * 3 + struct.pack(f'{order}I', 2) + b'a\x00'
+        + b'\x00' * 2 + struct.pack(f'{order}I', 4) + b'bcd\x00'
+        + struct.pack(f'{order}I', 2) + b'x\x00'
+    )
+    expected = ['', '', 'a', 'bcd']
+
+    decoder = CdrDecoder(data)
+    assert decoder.uint8() == 7
+    assert decoder.sequence('string') == expected
+    assert decoder.string() == 'x'
+    assert decoder.position() == len(data)
+
+    schema = Schema('pkg/Names', {
+        'tag': SchemaField(Primitive('uint8')),
+        'names': SchemaField(Sequence(String('string'))),
+        'label': SchemaField(String('string')),
+    })
+    message = compile_schema(schema, {})(CdrDecoder(data))
+    assert message.tag == 7
+    assert message.names == expected
+    assert message.label == 'x'
+
+    # Empty strings are written back with a null terminator
+    roundtrip = CdrEncoder(little_endian=little_endian)
+    compile_serializer(schema, {})(roundtrip, message)
+    decoded = compile_schema(schema, {})(CdrDecoder(roundtrip.save()))
+    assert (decoded.tag, decoded.names, decoded.label) == (7, expected, 'x')
+
+
+def test_decode_strings_from_memoryview() -> None:
+    encoder = CdrEncoder()
+    encoder.uint8(1)
+    encoder.string('hello')
+    encoder.string('wörld')
+    data = memoryview(encoder.save())
+
+    decoder = CdrDecoder(data)
+    assert decoder.uint8() == 1
+    assert decoder.string() == 'hello'
+    assert decoder.string() == 'wörld'
+
+    schema = Schema('pkg/Names', {
+        'tag': SchemaField(Primitive('uint8')),
+        'first': SchemaField(String('string')),
+        'second': SchemaField(String('string')),
+    })
+    message = compile_schema(schema, {})(CdrDecoder(data))
+    assert (message.tag, message.first, message.second) == (1, 'hello', 'wörld')
+
+
+def test_compiled_byte_and_char_arrays() -> None:
+    schema = Schema('pkg/Buffers', {
+        'letter': SchemaField(Primitive('char')),
+        'payload': SchemaField(Sequence(Primitive('uint8'))),
+        'raw': SchemaField(Array(Primitive('byte'), 3)),
+        'raw_sequence': SchemaField(Sequence(Primitive('byte'))),
+        'name': SchemaField(Array(Primitive('char'), 4)),
+        'text': SchemaField(Sequence(Primitive('char'))),
+    })
+
+    encoder = CdrEncoder()
+    encoder.char('x')
+    encoder.sequence('uint8', [0, 1, 255])
+    encoder.array('byte', [b'\x01', b'\x02', b'\x03'])
+    encoder.sequence('byte', [b'\xff'])
+    encoder.array('char', list('ab\x00\x00'))
+    encoder.sequence('char', list('hello'))
+    data = encoder.save()
+
+    message = compile_schema(schema, {})(CdrDecoder(data))
+    assert message.letter == ord('x')
+    assert message.payload == b'\x00\x01\xff'
+    assert message.raw == b'\x01\x02\x03'
+    assert message.raw_sequence == b'\xff'
+    assert message.name == 'ab\x00\x00'
+    assert message.text == 'hello'
+
+    # The decoded bytes and str values can be written back unchanged
+    roundtrip = CdrEncoder()
+    compile_serializer(schema, {})(roundtrip, message)
+    assert roundtrip.save() == data
+
+
+@pytest.mark.parametrize('little_endian', [True, False])
+@pytest.mark.parametrize('type_name, values', [
+    ('bool', [True, False, True]),
+    ('int8', [-1, 0, 1]),
+    ('uint16', [0, 1, 65_535]),
+    ('int32', [-5, 0, 5]),
+    ('uint64', [0, 2**64 - 1]),
+    ('float32', [0.5, -0.25]),
+    ('float64', [float(i) / 8 for i in range(1000)]),
+    ('float64', []),
+])
+def test_batch_read_matches_elementwise(little_endian: bool, type_name: str, values: list) -> None:
+    # Start with a byte so the batch has to be aligned
+    encoder = CdrEncoder(little_endian=little_endian)
+    encoder.uint8(7)
+    encoder.array(type_name, values)
+    encoder.uint8(7)
+    encoder.sequence(type_name, values)
+    encoder.uint8(9)
+    data = encoder.save()
+
+    batched = CdrDecoder(data)
+    assert batched.uint8() == 7
+    assert batched.array(type_name, len(values)) == values
+    assert batched.uint8() == 7
+    assert batched.sequence(type_name) == values
+    assert batched.uint8() == 9
+
+    elementwise = CdrDecoder(data)
+    assert elementwise.uint8() == 7
+    assert [elementwise.parse(type_name) for _ in values] == values
+    assert elementwise.uint8() == 7
+    assert [elementwise.parse(type_name) for _ in range(elementwise.uint32())] == values
+    assert elementwise.uint8() == 9
+    assert elementwise._data.position == batched._data.position
+
+
+@pytest.mark.parametrize('little_endian', [True, False])
+@pytest.mark.parametrize('count', [0, 1, 100])
+def test_compiled_arrays_of_flat_structs(little_endian: bool, count: int) -> None:
+    # Structs of one primitive size are unpacked in one pass, others per element
+    point = Schema('pkg/Point', {
+        'x': SchemaField(Primitive('float64')),
+        'y': SchemaField(Primitive('float64')),
+    })
+    mixed = Schema('pkg/Mixed', {
+        'id': SchemaField(Primitive('uint8')),
+        'value': SchemaField(Primitive('float64')),
+    })
+    schema = Schema('pkg/Shapes', {
+        'tag': SchemaField(Primitive('uint8')),
+        'points': SchemaField(Sequence(Complex('pkg/Point'))),
+        'flag': SchemaField(Primitive('uint8')),
+        'corners': SchemaField(Array(Complex('pkg/Point'), 2)),
+        'mixed': SchemaField(Sequence(Complex('pkg/Mixed'))),
+        'end': SchemaField(Primitive('uint8')),
+    })
+    sub_schemas = {'pkg/Point': point, 'pkg/Mixed': mixed}
+
+    encoder = CdrEncoder(little_endian=little_endian)
+    encoder.uint8(1)
+    encoder.uint32(count)
+    for i in range(count):
+        encoder.float64(float(i))
+        encoder.float64(-float(i))
+    encoder.uint8(2)
+    for value in (0.5, 1.5, 2.5, 3.5):
+        encoder.float64(value)
+    encoder.uint32(count)
+    for i in range(count):
+        encoder.uint8(i % 256)
+        encoder.float64(i / 4)
+    encoder.uint8(3)
+    data = encoder.save()
+
+    message = compile_schema(schema, sub_schemas)(CdrDecoder(data))
+    assert [(p.x, p.y) for p in message.points] == [(float(i), -float(i)) for i in range(count)]
+    assert message.flag == 2
+    assert [(p.x, p.y) for p in message.corners] == [(0.5, 1.5), (2.5, 3.5)]
+    assert [(m.id, m.value) for m in message.mixed] == [(i % 256, i / 4) for i in range(count)]
+    assert message.end == 3
+
+    roundtrip = CdrEncoder(little_endian=little_endian)
+    compile_serializer(schema, sub_schemas)(roundtrip, message)
+    assert roundtrip.save() == data
+
+
+@pytest.mark.parametrize('little_endian', [True, False])
+@pytest.mark.parametrize('count', [0, 3])
+def test_compiled_bounded_array(little_endian: bool, count: int) -> None:
+    # float64[<=3] is written as a sequence with at most 3 elements
+    schema = Schema('pkg/Bounded', {
+        'tag': SchemaField(Primitive('uint8')),
+        'values': SchemaField(Array(Primitive('float64'), 3, is_bounded=True)),
+    })
+
+    encoder = CdrEncoder(little_endian=little_endian)
+    encoder.uint8(7)
+    encoder.sequence('float64', [float(i) for i in range(count)])
+    data = encoder.save()
+
+    message = compile_schema(schema, {})(CdrDecoder(data))
+    assert message.tag == 7
+    assert message.values == [float(i) for i in range(count)]
+
+    roundtrip = CdrEncoder(little_endian=little_endian)
+    compile_serializer(schema, {})(roundtrip, message)
+    assert roundtrip.save() == data
+
+
+def test_compiled_bounded_array_exceeds_bound() -> None:
+    schema = Schema('pkg/Bounded', {
+        'values': SchemaField(Array(Primitive('float64'), 3, is_bounded=True)),
+    })
+
+    encoder = CdrEncoder()
+    encoder.sequence('float64', [0.0, 1.0, 2.0, 3.0])
+    with pytest.raises(CdrDecodeError, match='values has 4 elements, more than 3'):
+        compile_schema(schema, {})(CdrDecoder(encoder.save()))
+
+    encoder = CdrEncoder()
+    encoder.sequence('float64', [0.0, 1.0, 2.0])
+    message = compile_schema(schema, {})(CdrDecoder(encoder.save()))
+    message.values.append(3.0)
+    with pytest.raises(ValueError, match='at most 3 elements, got 4'):
+        compile_serializer(schema, {})(CdrEncoder(), message)
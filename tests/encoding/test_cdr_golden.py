@@ -0,0 +1,308 @@
+"""Golden tests comparing CDR output against reference serializations.
+
+The Point, Header and PoseWithCovariance fixtures were captured from messages
+published with rclpy. The Imu, Odometry and PoseArray fixtures are spelled out
+field by field following the rosidl (Fast-CDR) layout, including the padding
+inserted to align each primitive relative to the start of the payload.
+"""
+import struct
+from dataclasses import asdict
+
+import pytest
+
+import pybag.ros2.humble.builtin_interfaces as builtin_interfaces
+import pybag.ros2.humble.geometry_msgs as geometry_msgs
+import pybag.ros2.humble.nav_msgs as nav_msgs
+import pybag.ros2.humble.sensor_msgs as sensor_msgs
+import pybag.ros2.humble.std_msgs as std_msgs
+from pybag.deserialize import MessageDeserializerFactory, decode_message
+from pybag.encoding.cdr import CdrDecodeError, CdrEncoder
+from pybag.mcap.records import MessageRecord, SchemaRecord
+from pybag.serialize import MessageSerializerFactory
+
+CDR_LE_HEADER = b'\x00\x01\x00\x00'
+
+POINT_BYTES = (
+    b'\x00\x01\x00\x00\x00\x00\x00\x00\x00\x00\xf0?\x00\x00\x00\x00\x00\x00\x00@'
+    b'\x00\x00\x00\x00\x00\x00\x08@'
+)
+
+HEADER_BYTES = b'\x00\x01\x00\x00\n\x00\x00\x00\xe8\x03\x00\x00\t\x00\x00\x00frame_id\x00'
+
+POSE_WITH_COVARIANCE_BYTES = (
+    CDR_LE_HEADER
+    + struct.pack('<3d', 1.0, 2.0, 3.0)
+    + struct.pack('<4d', 0.0, 0.0, 0.0, 1.0)
+    + struct.pack('<36d', *[float(i) for i in range(36)])
+)
+
+IMU_BYTES = (
+    CDR_LE_HEADER
+    + struct.pack('<iI', 1, 500)                 # header.stamp
+    + struct.pack('<I', 9) + b'imu_link\x00'     # header.frame_id (ends at 21)
+    + b'\x00' * 3                                # pad to 24 for float64
+    + struct.pack('<4d', 0.0, 0.0, 0.0, 1.0)     # orientation
+    + struct.pack('<9d', *[float(i) for i in range(9)])
+    + struct.pack('<3d', 0.5, -0.5, 0.25)        # angular_velocity
+    + struct.pack('<9d', *[float(i) for i in range(9, 18)])
+    + struct.pack('<3d', 0.0, 0.0, 9.75)         # linear_acceleration
+    + struct.pack('<9d', *[float(i) for i in range(18, 27)])
+)
+
+ODOMETRY_BYTES = (
+    CDR_LE_HEADER
+    + struct.pack('<iI', 2, 0)                   # header.stamp
+    + struct.pack('<I', 5) + b'odom\x00'         # header.frame_id (ends at 17)
+    + b'\x00' * 3                                # pad to 20 for uint32
+    + struct.pack('<I', 10) + b'base_link\x00'   # child_frame_id (ends at 34)
+    + b'\x00' * 6                                # pad to 40 for float64
+    + struct.pack('<3d', 1.0, 2.0, 3.0)          # pose.pose.position
+    + struct.pack('<4d', 0.0, 0.0, 0.0, 1.0)     # pose.pose.orientation
+    + struct.pack('<36d', *[float(i) for i in range(36)])
+    + struct.pack('<3d', 0.5, 0.0, 0.0)          # twist.twist.linear
+    + struct.pack('<3d', 0.0, 0.0, 0.125)        # twist.twist.angular
+    + struct.pack('<36d', *[float(-i) for i in range(36)])
+)
+
+POSE_ARRAY_BYTES = (
+    CDR_LE_HEADER
+    + struct.pack('<iI', 3, 4)                   # header.stamp
+    + struct.pack('<I', 4) + b'map\x00'          # header.frame_id (ends at 16)
+    + struct.pack('<I', 2)                       # poses length (ends at 20)
+    + b'\x00' * 4                                # pad to 24 for float64
+    + struct.pack('<7d', 1.0, 2.0, 3.0, 0.0, 0.0, 0.0, 1.0)
+    + struct.pack('<7d', 4.0, 5.0, 6.0, 0.0, 0.0, 1.0, 0.0)
+)
+
+
+def _header(sec: int, nanosec: int, frame_id: str) -> std_msgs.Header:
+    return std_msgs.Header(
+        stamp=builtin_interfaces.Time(sec=sec, nanosec=nanosec),
+        frame_id=frame_id,
+    )
+
+
+def _pose(position: tuple[float, float, float], orientation: tuple[float, float, float, float]) -> geometry_msgs.Pose:
+    return geometry_msgs.Pose(
+        position=geometry_msgs.Point(x=position[0], y=position[1], z=position[2]),
+        orientation=geometry_msgs.Quaternion(
+            x=orientation[0], y=orientation[1], z=orientation[2], w=orientation[3]
+        ),
+    )
+
+
+GOLDEN_MESSAGES = [
+    pytest.param(geometry_msgs.Point(x=1.0, y=2.0, z=3.0), POINT_BYTES, id="Point"),
+    pytest.param(_header(10, 1000, 'frame_id'), HEADER_BYTES, id="Header"),
+    pytest.param(
+        geometry_msgs.PoseWithCovariance(
+            pose=_pose((1.0, 2.0, 3.0), (0.0, 0.0, 0.0, 1.0)),
+            covariance=[float(i) for i in range(36)],
+        ),
+        POSE_WITH_COVARIANCE_BYTES,
+        id="PoseWithCovariance",
+    ),
+    pytest.param(
+        sensor_msgs.Imu(
+            header=_header(1, 500, 'imu_link'),
+            orientation=geometry_msgs.Quaternion(x=0.0, y=0.0, z=0.0, w=1.0),
+            orientation_covariance=[float(i) for i in range(9)],
+            angular_velocity=geometry_msgs.Vector3(x=0.5, y=-0.5, z=0.25),
+            angular_velocity_covariance=[float(i) for i in range(9, 18)],
+            linear_acceleration=geometry_msgs.Vector3(x=0.0, y=0.0, z=9.75),
+            linear_acceleration_covariance=[float(i) for i in range(18, 27)],
+        ),
+        IMU_BYTES,
+        id="Imu",
+    ),
+    pytest.param(
+        nav_msgs.Odometry(
+            header=_header(2, 0, 'odom'),
+            child_frame_id='base_link',
+            pose=geometry_msgs.PoseWithCovariance(
+                pose=_pose((1.0, 2.0, 3.0), (0.0, 0.0, 0.0, 1.0)),
+                covariance=[float(i) for i in range(36)],
+            ),
+            twist=geometry_msgs.TwistWithCovariance(
+                twist=geometry_msgs.Twist(
+                    linear=geometry_msgs.Vector3(x=0.5, y=0.0, z=0.0),
+                    angular=geometry_msgs.Vector3(x=0.0, y=0.0, z=0.125),
+                ),
+                covariance=[float(-i) for i in range(36)],
+            ),
+        ),
+        ODOMETRY_BYTES,
+        id="Odometry",
+    ),
+    pytest.param(
+        geometry_msgs.PoseArray(
+            header=_header(3, 4, 'map'),
+            poses=[
+                _pose((1.0, 2.0, 3.0), (0.0, 0.0, 0.0, 1.0)),
+                _pose((4.0, 5.0, 6.0), (0.0, 0.0, 1.0, 0.0)),
+            ],
+        ),
+        POSE_ARRAY_BYTES,
+        id="PoseArray",
+    ),
+]
+
+
+@pytest.mark.parametrize("message, expected", GOLDEN_MESSAGES)
+def test_serialize_matches_golden(message, expected: bytes) -> None:
+    serializer = MessageSerializerFactory.from_profile("ros2")
+    assert serializer is not None
+    assert serializer.serialize_message(message) == expected
+
+
+@pytest.mark.parametrize("message, expected", GOLDEN_MESSAGES)
+def test_deserialize_golden(message, expected: bytes) -> None:
+    serializer = MessageSerializerFactory.from_profile("ros2")
+    deserializer = MessageDeserializerFactory.from_profile("ros2")
+    assert serializer is not None
+    assert deserializer is not None
+
+    schema = SchemaRecord(
+        id=1,
+        name=message.__msg_name__,
+        encoding="ros2msg",
+        data=serializer.serialize_schema(type(message)),
+    )
+    record = MessageRecord(channel_id=1, sequence=0, log_time=0, publish_time=0, data=expected)
+    decoded = deserializer.deserialize_message(record, schema)
+
+    assert asdict(decoded) == asdict(message)
+
+
+def test_truncated_message_error_names_field() -> None:
+    serializer = MessageSerializerFactory.from_profile("ros2")
+    deserializer = MessageDeserializerFactory.from_profile("ros2")
+    assert serializer is not None
+    assert deserializer is not None
+
+    schema = SchemaRecord(
+        id=1,
+        name="geometry_msgs/msg/PoseArray",
+        encoding="ros2msg",
+        data=serializer.serialize_schema(geometry_msgs.PoseArray),
+    )
+    # Cut the data just before the second pose's z coordinate
+    truncated = POSE_ARRAY_BYTES[:100]
+    record = MessageRecord(channel_id=1, sequence=0, log_time=0, publish_time=0, data=truncated)
+
+    with pytest.raises(CdrDecodeError, match=r"Failed decoding poses\[1\]\.position\.z at byte 100"):
+        deserializer.deserialize_message(record, schema)
+
+    # The deserializer still decodes complete messages afterwards
+    record = MessageRecord(channel_id=1, sequence=0, log_time=0, publish_time=0, data=POSE_ARRAY_BYTES)
+    assert len(deserializer.deserialize_message(record, schema).poses) == 2
+
+
+def test_decode_message_without_file() -> None:
+    point = decode_message("geometry_msgs/msg/Point", "float64 x\nfloat64 y\nfloat64 z\n", POINT_BYTES)
+    assert (point.x, point.y, point.z) == (1.0, 2.0, 3.0)
+
+    # The compiled schema is reused for the same definition
+    again = decode_message("geometry_msgs/msg/Point", "float64 x\nfloat64 y\nfloat64 z\n", POINT_BYTES)
+    assert type(again) is type(point)
+
+    # A different definition under the same name is parsed separately
+    header = decode_message("geometry_msgs/msg/Point", "int32 sec\nuint32 nanosec\nstring frame_id\n", HEADER_BYTES)
+    assert (header.sec, header.nanosec, header.frame_id) == (10, 1000, "frame_id")
+
+
+# Odometry with nested types named with and without the /msg/ namespace
+ODOMETRY_DEFINITION = """\
+std_msgs/msg/Header header
+string child_frame_id
+geometry_msgs/PoseWithCovariance pose
+geometry_msgs/msg/TwistWithCovariance twist
+================================================================================
+MSG: std_msgs/Header
+builtin_interfaces/Time stamp
+string frame_id
+================================================================================
+MSG: builtin_interfaces/msg/Time
+int32 sec
+uint32 nanosec
+================================================================================
+MSG: geometry_msgs/msg/PoseWithCovariance
+Pose pose
+float64[36] covariance
+================================================================================
+MSG: geometry_msgs/Pose
+Point position
+Quaternion orientation
+================================================================================
+MSG: geometry_msgs/Point
+float64 x
+float64 y
+float64 z
+================================================================================
+MSG: geometry_msgs/Quaternion
+float64 x
+float64 y
+float64 z
+float64 w
+================================================================================
+MSG: geometry_msgs/TwistWithCovariance
+Twist twist
+float64[36] covariance
+================================================================================
+MSG: geometry_msgs/msg/Twist
+Vector3 linear
+Vector3 angular
+================================================================================
+MSG: geometry_msgs/Vector3
+float64 x
+float64 y
+float64 z
+"""
+
+
+def test_decode_message_field_names():
+    odometry = decode_message("nav_msgs/msg/Odometry", ODOMETRY_DEFINITION, ODOMETRY_BYTES)
+
+    # Fields keep their declared names, whichever spelling their type uses
+    decoded = asdict(odometry)
+    assert list(decoded) == ["header", "child_frame_id", "pose", "twist"]
+    assert list(decoded["header"]) == ["stamp", "frame_id"]
+    assert list(decoded["pose"]) == ["pose", "covariance"]
+    assert list(decoded["twist"]["twist"]) == ["linear", "angular"]
+    assert decoded == asdict(GOLDEN_MESSAGES[4].values[0])
+
+
+@pytest.mark.parametrize("little_endian", [True, False])
+def test_decode_message_without_header(little_endian: bool) -> None:
+    encoder = CdrEncoder(little_endian=little_endian)
+    encoder.int32(10)
+    encoder.uint32(1000)
+    encoder.string("frame_id")
+    data = encoder.save()
+    definition = "int32 sec\nuint32 nanosec\nstring frame_id\n"
+
+    with_header = decode_message("pkg/msg/Stamp", definition, data)
+    without_header = decode_message(
+        "pkg/msg/Stamp", definition, data[4:], has_header=False, little_endian=little_endian
+    )
+    assert asdict(without_header) == asdict(with_header)
+    assert (without_header.sec, without_header.nanosec, without_header.frame_id) == (10, 1000, "frame_id")
+
+    with pytest.raises(ValueError, match="encapsulation header"):
+        decode_message("pkg/Stamp", definition, data[4:], schema_encoding="ros1msg", has_header=False)
+
+
+@pytest.mark.parametrize("data", [
+    pytest.param(CDR_LE_HEADER, id="header_only"),
+    pytest.param(CDR_LE_HEADER + b"\x00", id="padding_byte"),
+])
+def test_decode_empty_message(data: bytes) -> None:
+    # std_msgs/Empty has no fields, other tools serialize it with or without a padding byte
+    empty = decode_message("std_msgs/msg/Empty", "# This message has no fields\n", data)
+    assert asdict(empty) == {}
+
+    # The generated Empty carries the padding byte as a field
+    serializer = MessageSerializerFactory.from_profile("ros2")
+    assert serializer is not None
+    assert serializer.serialize_message(std_msgs.Empty()) == CDR_LE_HEADER + b"\x00"
+
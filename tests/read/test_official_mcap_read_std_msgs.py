@@ -157,7 +157,7 @@ def test_std_msgs_byte_multi_array():
     assert messages[0].data.layout.dim[0].size == 3
     assert messages[0].data.layout.dim[0].stride == 3
     assert messages[0].data.layout.data_offset == 0
-    assert list(messages[0].data.data) == [b'\x01', b'\x02', b'\x03']
+    assert messages[0].data.data == b'\x01\x02\x03'
 
 
 def test_std_msgs_char():
@@ -182,7 +182,7 @@ def test_std_msgs_char():
     assert messages[0].publish_time == 0
     assert messages[0].sequence == 0
     assert messages[0].channel_id == 1
-    assert messages[0].data.data == 'A'
+    assert messages[0].data.data == 65
 
 
 def test_std_msgs_colorrgba():
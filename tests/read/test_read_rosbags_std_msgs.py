@@ -102,7 +102,7 @@ def test_byte_multi_array_rosbags(typestore: Typestore):
         assert messages[0].data.layout.dim[0].size == 3
         assert messages[0].data.layout.dim[0].stride == 3
         assert messages[0].data.layout.data_offset == 0
-        assert list(messages[0].data.data) == [b'\x01', b'\xfe', b'\x03']
+        assert messages[0].data.data == b'\x01\xfe\x03'
 
 
 def test_char_rosbags(typestore: Typestore):
@@ -119,8 +119,8 @@ def test_char_rosbags(typestore: Typestore):
         assert messages[0].publish_time == 0
         assert messages[0].sequence == 0
         assert messages[0].channel_id == channel_id
-        assert messages[0].data.data == 'A'
-        assert type(messages[0].data.data) == str
+        assert messages[0].data.data == 65
+        assert type(messages[0].data.data) == int
 
 
 def test_color_rgba_rosbags(typestore: Typestore):
@@ -0,0 +1,75 @@
+import pytest
+
+from pybag.schema import (
+    Array,
+    Complex,
+    Primitive,
+    Schema,
+    SchemaField,
+    Sequence,
+    String
+)
+from pybag.schema.field_path import (
+    FieldPathError,
+    check_field_path,
+    get_field,
+    parse_field_path
+)
+
+POINT = Schema("pkg/Point", {
+    "x": SchemaField(Primitive("float64")),
+    "y": SchemaField(Primitive("float64")),
+})
+PATH = Schema("pkg/Path", {
+    "name": SchemaField(String("string")),
+    "points": SchemaField(Sequence(Complex("pkg/Point"))),
+    "corners": SchemaField(Array(Complex("pkg/Point"), 2)),
+})
+SUB_SCHEMAS = {"pkg/Point": POINT}
+
+
+@pytest.mark.parametrize("path, expected", [
+    ("name", ["name"]),
+    ("points[1].x", ["points", 1, "x"]),
+    ("corners[0]", ["corners", 0]),
+    ("grid[1][2]", ["grid", 1, 2]),
+])
+def test_parse_field_path(path, expected):
+    assert parse_field_path(path) == expected
+
+
+@pytest.mark.parametrize("path", ["", "points.", "points[x]", "points[-1]", "1points", "points x"])
+def test_parse_invalid_field_path(path):
+    with pytest.raises(FieldPathError, match="Invalid field path"):
+        parse_field_path(path)
+
+
+@pytest.mark.parametrize("path", ["name", "points", "points[5].y", "corners[1].x"])
+def test_check_field_path(path):
+    check_field_path(parse_field_path(path), PATH, SUB_SCHEMAS)
+
+
+@pytest.mark.parametrize("path, match", [
+    ("missing", "has no field 'missing'"),
+    ("points[0].z", "pkg/Point has no field 'z'"),
+    ("name[0]", "non-array"),
+    ("name.x", "non-message"),
+    ("corners[2]", "out of range"),
+])
+def test_check_invalid_field_path(path, match):
+    with pytest.raises(FieldPathError, match=match):
+        check_field_path(parse_field_path(path), PATH, SUB_SCHEMAS)
+
+
+def test_get_field():
+    class Point:
+        def __init__(self, x, y):
+            self.x, self.y = x, y
+
+    class Path:
+        name = "path"
+        points = [Point(1.0, 2.0), Point(3.0, 4.0)]
+
+    assert get_field(Path(), ["name"]) == "path"
+    assert get_field(Path(), ["points", 1, "y"]) == 4.0
+    assert get_field(Path(), ["points", 2, "y"]) is None
@@ -0,0 +1,355 @@
+from dataclasses import asdict
+
+import pytest
+
+import pybag.ros2.humble.builtin_interfaces as builtin_interfaces
+import pybag.ros2.humble.geometry_msgs as geometry_msgs
+import pybag.ros2.humble.std_msgs as std_msgs
+from pybag.deserialize import MessageDeserializerFactory
+from pybag.mcap.records import MessageRecord, SchemaRecord
+from pybag.schema import (
+    Array,
+    Complex,
+    Primitive,
+    Schema,
+    SchemaConstant,
+    SchemaField,
+    Sequence,
+    String
+)
+from pybag.schema.ros2idl import Ros2IdlError, Ros2IdlSchemaDecoder
+from pybag.serialize import MessageSerializerFactory
+
+POINT_IDL = """\
+// generated from rosidl_adapter/resource/msg.idl.em
+// with input from geometry_msgs/msg/Point.msg
+
+module geometry_msgs {
+  module msg {
+    struct Point {
+      double x;
+      double y;
+      double z;
+    };
+  };
+};
+"""
+
+# As written by rosidl, with the dependencies appended like in MCAP files
+POSE_STAMPED_IDL = """\
+// generated from rosidl_adapter/resource/msg.idl.em
+// with input from geometry_msgs/msg/PoseStamped.msg
+// generated code does not contain a copyright notice
+
+#include "geometry_msgs/msg/Pose.idl"
+#include "std_msgs/msg/Header.idl"
+
+module geometry_msgs {
+  module msg {
+    @verbatim (language="comment", text=
+      "A Pose with reference coordinate frame and timestamp")
+    struct PoseStamped {
+      std_msgs::msg::Header header;
+
+      geometry_msgs::msg::Pose pose;
+    };
+  };
+};
+
+================================================================================
+IDL: std_msgs/msg/Header
+// generated from rosidl_adapter/resource/msg.idl.em
+// with input from std_msgs/msg/Header.msg
+// generated code does not contain a copyright notice
+
+#include "builtin_interfaces/msg/Time.idl"
+
+module std_msgs {
+  module msg {
+    @verbatim (language="comment", text=
+      "Standard metadata for higher-level stamped data types." "\\n"
+      "This is generally used to communicate timestamped data " "\\n"
+      "in a particular coordinate frame.")
+    struct Header {
+      @verbatim (language="comment", text=
+        "Two-integer timestamp that is expressed as seconds and nanoseconds.")
+      builtin_interfaces::msg::Time stamp;
+
+      @verbatim (language="comment", text=
+        "Transform frame with which this data is associated.")
+      string frame_id;
+    };
+  };
+};
+
+================================================================================
+IDL: builtin_interfaces/msg/Time
+// generated from rosidl_adapter/resource/msg.idl.em
+// with input from builtin_interfaces/msg/Time.msg
+// generated code does not contain a copyright notice
+
+
+module builtin_interfaces {
+  module msg {
+    @verbatim (language="comment", text=
+      "This message communicates ROS Time defined here:" "\\n"
+      "https://design.ros2.org/articles/clock_and_time.html")
+    struct Time {
+      @verbatim (language="comment", text=
+        "The seconds component, valid over all int32 values.")
+      int32 sec;
+
+      @verbatim (language="comment", text=
+        "The nanoseconds component, valid in the range [0, 10e9).")
+      uint32 nanosec;
+    };
+  };
+};
+
+================================================================================
+IDL: geometry_msgs/msg/Pose
+// generated from rosidl_adapter/resource/msg.idl.em
+// with input from geometry_msgs/msg/Pose.msg
+// generated code does not contain a copyright notice
+
+#include "geometry_msgs/msg/Point.idl"
+#include "geometry_msgs/msg/Quaternion.idl"
+
+module geometry_msgs {
+  module msg {
+    @verbatim (language="comment", text=
+      "A representation of pose in free space, composed of position and orientation.")
+    struct Pose {
+      geometry_msgs::msg::Point position;
+
+      geometry_msgs::msg::Quaternion orientation;
+    };
+  };
+};
+
+================================================================================
+IDL: geometry_msgs/msg/Point
+""" + POINT_IDL + """
+================================================================================
+IDL: geometry_msgs/msg/Quaternion
+// generated from rosidl_adapter/resource/msg.idl.em
+// with input from geometry_msgs/msg/Quaternion.msg
+// generated code does not contain a copyright notice
+
+
+module geometry_msgs {
+  module msg {
+    @verbatim (language="comment", text=
+      "This represents an orientation in free space in quaternion form.")
+    struct Quaternion {
+      @default (value=0.0)
+      double x;
+
+      @default (value=0.0)
+      double y;
+
+      @default (value=0.0)
+      double z;
+
+      @default (value=1.0)
+      double w;
+    };
+  };
+};
+"""
+
+
+def _schema(name: str, text: str) -> SchemaRecord:
+    return SchemaRecord(id=1, name=name, encoding="ros2idl", data=text.encode("utf-8"))
+
+
+def test_parse_primitive_fields():
+    schema, sub_schemas = Ros2IdlSchemaDecoder().parse_schema(
+        _schema("geometry_msgs/msg/Point", POINT_IDL)
+    )
+
+    assert schema == Schema(
+        "geometry_msgs/msg/Point",
+        {
+            "x": SchemaField(Primitive("float64")),
+            "y": SchemaField(Primitive("float64")),
+            "z": SchemaField(Primitive("float64")),
+        },
+    )
+    assert sub_schemas == {}
+
+
+def test_parse_name_without_interface_namespace():
+    schema, _ = Ros2IdlSchemaDecoder().parse_schema(_schema("geometry_msgs/Point", POINT_IDL))
+    assert schema.name == "geometry_msgs/Point"
+    assert list(schema.fields) == ["x", "y", "z"]
+
+
+def test_parse_idl_type_spellings():
+    text = """\
+module pkg {
+  module msg {
+    struct Types {
+      boolean flag;
+      octet raw;
+      float ratio;
+      double value;
+      short small;
+      unsigned short usmall;
+      long medium;
+      unsigned long umedium;
+      long long large;
+      unsigned long long ularge;
+      wchar letter;
+      sequence<unsigned long, 3> ids;
+      int64 count;
+      string name;
+      sequence<uint8> payload;
+    };
+  };
+};
+"""
+    schema, _ = Ros2IdlSchemaDecoder().parse_schema(_schema("pkg/msg/Types", text))
+    assert schema.fields == {
+        "flag": SchemaField(Primitive("bool")),
+        "raw": SchemaField(Primitive("byte")),
+        "ratio": SchemaField(Primitive("float32")),
+        "value": SchemaField(Primitive("float64")),
+        "small": SchemaField(Primitive("int16")),
+        "usmall": SchemaField(Primitive("uint16")),
+        "medium": SchemaField(Primitive("int32")),
+        "umedium": SchemaField(Primitive("uint32")),
+        "large": SchemaField(Primitive("int64")),
+        "ularge": SchemaField(Primitive("uint64")),
+        "letter": SchemaField(Primitive("uint16")),
+        "ids": SchemaField(Array(Primitive("uint32"), 3, is_bounded=True)),
+        "count": SchemaField(Primitive("int64")),
+        "name": SchemaField(String("string")),
+        "payload": SchemaField(Sequence(Primitive("uint8"))),
+    }
+
+
+def test_parse_concatenated_definitions():
+    text = """\
+module pkg {
+  module msg {
+    struct Path {
+      sequence<pkg::msg::Point> points;
+      Point origin;
+    };
+  };
+};
+
+================================================================================
+IDL: pkg/msg/Point
+module pkg {
+  module msg {
+    struct Point {
+      double x;
+      double y;
+    };
+  };
+};
+"""
+    schema, sub_schemas = Ros2IdlSchemaDecoder().parse_schema(_schema("pkg/msg/Path", text))
+    assert schema.fields == {
+        "points": SchemaField(Sequence(Complex("pkg/msg/Point"))),
+        "origin": SchemaField(Complex("pkg/msg/Point")),
+    }
+    assert list(sub_schemas) == ["pkg/msg/Point"]
+    assert list(sub_schemas["pkg/msg/Point"].fields) == ["x", "y"]
+
+
+def test_missing_main_struct_raises():
+    with pytest.raises(Ros2IdlError, match="not defined"):
+        Ros2IdlSchemaDecoder().parse_schema(_schema("geometry_msgs/msg/Vector3", POINT_IDL))
+
+
+def test_parse_pose_stamped():
+    schema, sub_schemas = Ros2IdlSchemaDecoder().parse_schema(
+        _schema("geometry_msgs/msg/PoseStamped", POSE_STAMPED_IDL)
+    )
+    assert schema.fields == {
+        "header": SchemaField(Complex("std_msgs/msg/Header")),
+        "pose": SchemaField(Complex("geometry_msgs/msg/Pose")),
+    }
+    assert set(sub_schemas) == {
+        "std_msgs/msg/Header",
+        "builtin_interfaces/msg/Time",
+        "geometry_msgs/msg/Pose",
+        "geometry_msgs/msg/Point",
+        "geometry_msgs/msg/Quaternion",
+    }
+    assert sub_schemas["std_msgs/msg/Header"].fields == {
+        "stamp": SchemaField(Complex("builtin_interfaces/msg/Time")),
+        "frame_id": SchemaField(String("string")),
+    }
+    assert sub_schemas["geometry_msgs/msg/Quaternion"].fields["w"] == SchemaField(Primitive("float64"), 1.0)
+
+
+def test_decode_pose_stamped():
+    message = geometry_msgs.PoseStamped(
+        header=std_msgs.Header(stamp=builtin_interfaces.Time(sec=1, nanosec=2), frame_id="map"),
+        pose=geometry_msgs.Pose(
+            position=geometry_msgs.Point(x=1.0, y=2.0, z=3.0),
+            orientation=geometry_msgs.Quaternion(x=0.0, y=0.0, z=0.0, w=1.0),
+        ),
+    )
+    serializer = MessageSerializerFactory.from_profile("ros2")
+    deserializer = MessageDeserializerFactory.from_profile("ros2")
+    assert serializer is not None
+    assert deserializer is not None
+
+    record = MessageRecord(
+        channel_id=1, sequence=0, log_time=0, publish_time=0,
+        data=serializer.serialize_message(message),
+    )
+    decoded = deserializer.deserialize_message(
+        record, _schema("geometry_msgs/msg/PoseStamped", POSE_STAMPED_IDL)
+    )
+    assert asdict(decoded) == asdict(message)
+
+
+def test_parse_constants_arrays_and_bounds():
+    text = """\
+module pkg {
+  module msg {
+    typedef double double__9[9];
+    module Status_Constants {
+      const uint8 OK = 0;
+      const int8 ERROR = -1;
+      const string NAME = "status // not a comment";
+      const boolean ENABLED = TRUE;
+    };
+    struct Status {
+      @key
+      uint8 level;
+      double__9 covariance;
+      int32 samples[3];
+      @default (value="idle")
+      string<16> label;
+      sequence<int32, 5> history;
+      sequence<string<8>> tags;
+    };
+  };
+};
+"""
+    schema, _ = Ros2IdlSchemaDecoder().parse_schema(_schema("pkg/msg/Status", text))
+    assert schema.fields == {
+        "OK": SchemaConstant(Primitive("uint8"), 0),
+        "ERROR": SchemaConstant(Primitive("int8"), -1),
+        "NAME": SchemaConstant(String("string"), "status // not a comment"),
+        "ENABLED": SchemaConstant(Primitive("bool"), True),
+        "level": SchemaField(Primitive("uint8")),
+        "covariance": SchemaField(Array(Primitive("float64"), 9)),
+        "samples": SchemaField(Array(Primitive("int32"), 3)),
+        "label": SchemaField(String("string", max_length=16), "idle"),
+        "history": SchemaField(Array(Primitive("int32"), 5, is_bounded=True)),
+        "tags": SchemaField(Sequence(String("string", max_length=8))),
+    }
+
+
+def test_multi_dimensional_array_raises():
+    text = "module pkg { module msg { struct Grid { int32 cells[2][2]; }; }; };"
+    with pytest.raises(Ros2IdlError, match="Multi-dimensional"):
+        Ros2IdlSchemaDecoder().parse_schema(_schema("pkg/msg/Grid", text))
@@ -1,6 +1,10 @@
+from types import SimpleNamespace
+
 import pytest
 
+from pybag.encoding.cdr import CdrDecoder, CdrEncoder
 from pybag.mcap.records import SchemaRecord
+from pybag.schema.compiler import compile_schema, compile_serializer
 from pybag.schema.ros2msg import (
     Array,
     Complex,
@@ -610,6 +614,30 @@ def test_parse_sub_message_schema():
     assert point_schema.fields["z"].type.type == "float64"
 
 
+@pytest.mark.parametrize("line_ending", ["\r\n", "\r"])
+def test_parse_windows_line_endings_and_bom(line_ending: str):
+    schema_text = (
+        "# Comment\n"
+        "int32 COUNT=3\n"
+        "string name \"default\"\n"
+        "geometry_msgs/Point[] points\n"
+        + "=" * 80
+        + "\nMSG: geometry_msgs/Point\nfloat64 x\nfloat64 y\nfloat64 z\n"
+    )
+    lf_schema = SchemaRecord(id=1, name="pkg/msg/Points", encoding="ros2msg", data=schema_text.encode("utf-8"))
+    windows_schema = SchemaRecord(
+        id=1,
+        name="pkg/msg/Points",
+        encoding="ros2msg",
+        data=schema_text.replace("\n", line_ending).encode("utf-8-sig"),
+    )
+    assert windows_schema.data.startswith(b"\xef\xbb\xbf")
+
+    expected = Ros2MsgSchemaDecoder().parse_schema(lf_schema)
+    assert Ros2MsgSchemaDecoder().parse_schema(windows_schema) == expected
+    assert list(expected[0].fields) == ["COUNT", "name", "points"]
+
+
 def test_field_with_inline_comment():
     schema_text = "int32 value # comment\n"
     schema = SchemaRecord(
@@ -761,3 +789,160 @@ def test_byte_array_default_parsing():
     field = parsed_schema.fields['data']
     assert isinstance(field, SchemaField)
     assert field.default == [1, 2, 3]
+
+
+@pytest.mark.parametrize("delimiter_length", [3, 75, 80])
+def test_sub_schema_delimiter_length(delimiter_length: int):
+    """Embedded definitions are split on any line of three or more '='."""
+    schema_text = (
+        "pkg/Inner inner\n"
+        + "=" * delimiter_length + "\n"
+        + "MSG: pkg/Inner\n"
+        + "int32 value\n"
+    )
+    schema = SchemaRecord(
+        id=1,
+        name="pkg/msg/Outer",
+        encoding="ros2msg",
+        data=schema_text.encode("utf-8"),
+    )
+    ros2_schema, sub_schemas = Ros2MsgSchemaDecoder().parse_schema(schema)
+
+    assert list(ros2_schema.fields) == ["inner"]
+    assert list(sub_schemas) == ["pkg/Inner"]
+    inner = sub_schemas["pkg/Inner"].fields["value"]
+    assert isinstance(inner, SchemaField)
+    assert inner.type == Primitive("int32")
+
+
+def test_constant_value_with_equals_is_not_a_delimiter():
+    schema_text = (
+        "string SEPARATOR=====\n"
+        "pkg/Inner inner\n"
+        + "=" * 40 + "\n"
+        + "MSG: pkg/Inner\n"
+        + "int32 value\n"
+    )
+    schema = SchemaRecord(
+        id=1,
+        name="pkg/msg/Outer",
+        encoding="ros2msg",
+        data=schema_text.encode("utf-8"),
+    )
+    ros2_schema, sub_schemas = Ros2MsgSchemaDecoder().parse_schema(schema)
+
+    separator = ros2_schema.fields["SEPARATOR"]
+    assert isinstance(separator, SchemaConstant)
+    assert separator.value == "===="
+    assert list(sub_schemas) == ["pkg/Inner"]
+
+
+@pytest.mark.parametrize("raw_type, expected", [
+    ("string<=10", String("string", max_length=10)),
+    ("string[<=5]", Array(String("string"), 5, is_bounded=True)),
+    ("string<=10[]", Sequence(String("string", max_length=10))),
+    ("string<=10[3]", Array(String("string", max_length=10), 3)),
+    ("string<=10[<=5]", Array(String("string", max_length=10), 5, is_bounded=True)),
+    ("wstring<=4[<=2]", Array(String("wstring", max_length=4), 2, is_bounded=True)),
+    ("int32[<=5]", Array(Primitive("int32"), 5, is_bounded=True)),
+])
+def test_parse_bounded_types(raw_type: str, expected):
+    """String bounds belong to the element type and T[<=N] is a bounded sequence."""
+    schema = SchemaRecord(
+        id=1,
+        name="pkg/msg/Bounded",
+        encoding="ros2msg",
+        data=f"{raw_type} value\n".encode("utf-8"),
+    )
+    ros2_schema, _ = Ros2MsgSchemaDecoder().parse_schema(schema)
+
+    field = ros2_schema.fields["value"]
+    assert isinstance(field, SchemaField)
+    assert field.type == expected
+
+
+@pytest.mark.parametrize("raw_type", ["int32<=5", "string[<=]", "int32[<=5][]", "int32[x]"])
+def test_parse_invalid_bounded_types(raw_type: str):
+    schema = SchemaRecord(
+        id=1,
+        name="pkg/msg/Bounded",
+        encoding="ros2msg",
+        data=f"{raw_type} value\n".encode("utf-8"),
+    )
+    with pytest.raises(Ros2MsgError):
+        Ros2MsgSchemaDecoder().parse_schema(schema)
+
+
+def test_bounded_strings_do_not_change_wire_format():
+    def compile_text(text: str):
+        schema = SchemaRecord(id=1, name="pkg/msg/Names", encoding="ros2msg", data=text.encode("utf-8"))
+        ros2_schema, sub_schemas = Ros2MsgSchemaDecoder().parse_schema(schema)
+        return compile_schema(ros2_schema, sub_schemas), compile_serializer(ros2_schema, sub_schemas)
+
+    bounded_decode, bounded_encode = compile_text("string<=10 name\nstring<=4[] tags\nfloat64 weight\n")
+    _, unbounded_encode = compile_text("string name\nstring[] tags\nfloat64 weight\n")
+
+    message = SimpleNamespace(name="robot", tags=["a", "bcd"], weight=1.5)
+
+    bounded, unbounded = CdrEncoder(), CdrEncoder()
+    bounded_encode(bounded, message)
+    unbounded_encode(unbounded, message)
+    assert bounded.save() == unbounded.save()
+
+    decoded = bounded_decode(CdrDecoder(bounded.save()))
+    assert (decoded.name, decoded.tags, decoded.weight) == ("robot", ["a", "bcd"], 1.5)
+
+
+SERVICE_DEFINITION = """\
+int64 a
+int64 b
+---
+int64 sum
+string message
+"""
+
+ACTION_DEFINITION = """\
+int32 order
+---
+int32[] sequence
+---
+int32[] partial_sequence
+"""
+
+
+@pytest.mark.parametrize("name, definition, expected_fields", [
+    pytest.param("pkg/srv/AddTwoInts", SERVICE_DEFINITION, ["a", "b"], id="service"),
+    pytest.param("pkg/srv/AddTwoInts_Request", SERVICE_DEFINITION, ["a", "b"], id="service_request"),
+    pytest.param("pkg/srv/AddTwoInts_Response", SERVICE_DEFINITION, ["sum", "message"], id="service_response"),
+    pytest.param("pkg/action/Fibonacci_Goal", ACTION_DEFINITION, ["order"], id="action_goal"),
+    pytest.param("pkg/action/Fibonacci_Result", ACTION_DEFINITION, ["sequence"], id="action_result"),
+    pytest.param("pkg/action/Fibonacci_Feedback", ACTION_DEFINITION, ["partial_sequence"], id="action_feedback"),
+])
+def test_parse_service_and_action_definitions(name: str, definition: str, expected_fields: list[str]):
+    schema = SchemaRecord(id=1, name=name, encoding="ros2msg", data=definition.encode("utf-8"))
+    ros2_schema, sub_schemas = Ros2MsgSchemaDecoder().parse_schema(schema)
+
+    assert list(ros2_schema.fields) == expected_fields
+    assert sub_schemas == {}
+
+
+def test_parse_service_with_empty_request():
+    schema = SchemaRecord(
+        id=1,
+        name="pkg/srv/Trigger_Response",
+        encoding="ros2msg",
+        data=b"---\nbool success\nstring message\n",
+    )
+    ros2_schema, _ = Ros2MsgSchemaDecoder().parse_schema(schema)
+    assert list(ros2_schema.fields) == ["success", "message"]
+
+
+def test_parse_too_many_definition_blocks():
+    schema = SchemaRecord(
+        id=1,
+        name="pkg/srv/Broken",
+        encoding="ros2msg",
+        data=b"int32 a\n---\nint32 b\n---\nint32 c\n---\nint32 d\n",
+    )
+    with pytest.raises(Ros2MsgError, match="4 blocks"):
+        Ros2MsgSchemaDecoder().parse_schema(schema)
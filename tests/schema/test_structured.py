@@ -0,0 +1,69 @@
+import pytest
+
+from pybag.schema import (
+    Array,
+    Complex,
+    Primitive,
+    Schema,
+    SchemaConstant,
+    SchemaField,
+    Sequence,
+    String
+)
+from pybag.schema.structured import (
+    StructuredTypeError,
+    message_record,
+    schema_dtype
+)
+
+POINT = Schema("pkg/Point", {
+    "x": SchemaField(Primitive("float64")),
+    "y": SchemaField(Primitive("float64")),
+})
+SUB_SCHEMAS = {"pkg/Point": POINT}
+
+
+def test_schema_dtype_nested_and_fixed_array():
+    schema = Schema("pkg/Shape", {
+        "MAX": SchemaConstant(Primitive("int32"), 3),
+        "id": SchemaField(Primitive("uint16")),
+        "center": SchemaField(Complex("pkg/Point")),
+        "corners": SchemaField(Array(Complex("pkg/Point"), 3)),
+        "flags": SchemaField(Array(Primitive("bool"), 2)),
+    })
+    point_dtype = [("x", "f8"), ("y", "f8")]
+    assert schema_dtype(schema, SUB_SCHEMAS) == [
+        ("id", "u2"),
+        ("center", point_dtype),
+        ("corners", point_dtype, (3,)),
+        ("flags", "?", (2,)),
+    ]
+
+
+@pytest.mark.parametrize("field_type", [
+    String("string"),
+    Sequence(Primitive("int32")),
+    Array(Primitive("int32"), 3, is_bounded=True),
+])
+def test_schema_dtype_rejects_variable_size(field_type):
+    schema = Schema("pkg/Bad", {"value": SchemaField(field_type)})
+    with pytest.raises(StructuredTypeError, match="fixed size"):
+        schema_dtype(schema, {})
+
+
+def test_message_record():
+    class Point:
+        def __init__(self, x, y):
+            self.x, self.y = x, y
+
+    class Message:
+        center = Point(1.0, 2.0)
+        data = b"\x01\x02"
+        letter = "a"
+
+    schema = Schema("pkg/Message", {
+        "center": SchemaField(Complex("pkg/Point")),
+        "data": SchemaField(Array(Primitive("uint8"), 2)),
+        "letter": SchemaField(Primitive("char")),
+    })
+    assert message_record(Message(), schema, SUB_SCHEMAS) == ((1.0, 2.0), [1, 2], 97)
@@ -0,0 +1,57 @@
+import zlib
+
+import lz4.block
+import lz4.frame
+import pytest
+import zstandard as zstd
+
+from pybag.mcap.chunk import decompress_chunk
+from pybag.mcap.error import McapUnknownCompressionError
+from pybag.mcap.records import ChunkRecord
+
+RECORDS = b'pybag chunk records ' * 32
+
+
+def _chunk(compression: str, records: bytes) -> ChunkRecord:
+    return ChunkRecord(
+        message_start_time=0,
+        message_end_time=0,
+        uncompressed_size=len(RECORDS),
+        uncompressed_crc=zlib.crc32(RECORDS),
+        compression=compression,
+        records=records,
+    )
+
+
+def test_decompress_lz4_frame():
+    chunk = _chunk('lz4', lz4.frame.compress(RECORDS))
+    assert decompress_chunk(chunk, check_crc=True) == RECORDS
+
+
+def test_decompress_lz4_block():
+    chunk = _chunk('lz4', lz4.block.compress(RECORDS, store_size=False))
+    assert decompress_chunk(chunk, check_crc=True) == RECORDS
+
+
+def test_decompress_zstd_with_dictionary():
+    dictionary = zstd.ZstdCompressionDict(b'pybag chunk records ' * 8)
+    chunk = _chunk('zstd', zstd.ZstdCompressor(dict_data=dictionary).compress(RECORDS))
+    assert decompress_chunk(chunk, check_crc=True, zstd_dictionary=dictionary.as_bytes()) == RECORDS
+
+
+@pytest.mark.parametrize('compression', ['', 'lz4', 'zstd'])
+def test_decompress_empty_chunk(compression: str):
+    chunk = ChunkRecord(
+        message_start_time=0,
+        message_end_time=0,
+        uncompressed_size=0,
+        uncompressed_crc=0,
+        compression=compression,
+        records=b'',
+    )
+    assert decompress_chunk(chunk, check_crc=True) == b''
+
+
+def test_decompress_unknown_compression():
+    with pytest.raises(McapUnknownCompressionError):
+        decompress_chunk(_chunk('bz2', RECORDS))
@@ -14,6 +14,7 @@ from pybag.mcap.crc import (
     assert_crc,
     assert_data_crc,
     assert_summary_crc,
+    compute_attachment_crc,
     compute_crc,
     compute_crc_batched,
     validate_crc,
@@ -21,10 +22,13 @@ from pybag.mcap.crc import (
     validate_summary_crc
 )
 from pybag.mcap.record_parser import (
+    DATA_END_SIZE,
     FOOTER_SIZE,
     MAGIC_BYTES_SIZE,
     McapRecordParser
 )
+from pybag.mcap.records import AttachmentRecord
+from pybag.mcap_reader import McapFileReader
 from pybag.mcap_writer import McapFileWriter
 
 
@@ -199,6 +203,30 @@ class TestFileLevelCrcValidation:
                 with pytest.raises(McapInvalidCrcError, match="Invalid CRC for data"):
                     assert_data_crc(reader)
 
+    @pytest.mark.parametrize("chunk_size", [None, 1024])
+    def test_data_section_crc_matches_computed(self, chunk_size):
+        """Test that the reader exposes the data section CRC written by the writer."""
+        with tempfile.TemporaryDirectory() as temp_dir:
+            path = Path(temp_dir) / "valid.mcap"
+            with McapFileWriter.open(path, chunk_size=chunk_size) as writer:
+                writer.write_message("/test", 1, std_msgs.String(data="hello"))
+                writer.write_message("/test", 2, std_msgs.String(data="world"))
+
+            with McapFileReader.from_file(path) as reader:
+                data_end_offset = reader.footer.summary_start - DATA_END_SIZE
+                expected = zlib.crc32(path.read_bytes()[:data_end_offset])
+                assert reader.data_section_crc == expected
+
+    def test_data_section_crc_not_computed(self):
+        """Test that a disabled data section CRC reads back as 0."""
+        with tempfile.TemporaryDirectory() as temp_dir:
+            path = Path(temp_dir) / "no_crc.mcap"
+            with McapFileWriter.open(path, compute_data_crc=False) as writer:
+                writer.write_message("/test", 1, std_msgs.String(data="hello"))
+
+            with McapFileReader.from_file(path) as reader:
+                assert reader.data_section_crc == 0
+
     def test_validate_summary_crc_valid_file(self):
         """Test validate_summary_crc returns True for valid MCAP file."""
         with tempfile.TemporaryDirectory() as temp_dir:
@@ -244,3 +272,100 @@ class TestFileLevelCrcValidation:
             with FileReader(path) as reader:
                 with pytest.raises(McapInvalidCrcError, match="Invalid CRC for summary"):
                     assert_summary_crc(reader)
+
+
+class TestAttachmentCrcValidation:
+    """Test attachment CRC validation when reading with enable_crc_check."""
+
+    @pytest.mark.parametrize("chunk_size", [None, 1024])
+    def test_valid_attachment_crc(self, chunk_size):
+        """Test that attachments with a correct CRC are read with the check enabled."""
+        with tempfile.TemporaryDirectory() as temp_dir:
+            path = Path(temp_dir) / "valid.mcap"
+            with McapFileWriter.open(path, chunk_size=chunk_size) as writer:
+                writer.write_message("/test", 1, std_msgs.String(data="hello"))
+                writer.write_attachment("calibration.yaml", b"fx: 500.0\n", media_type="text/yaml")
+
+            with McapFileReader.from_file(path, enable_crc_check=True) as reader:
+                attachments = reader.get_attachments()
+                assert len(attachments) == 1
+                assert attachments[0].data == b"fx: 500.0\n"
+
+    @pytest.mark.parametrize("chunk_size", [None, 1024])
+    def test_attachment_crc_covers_all_fields(self, chunk_size):
+        """Test that the written CRC covers every attachment field before it."""
+        with tempfile.TemporaryDirectory() as temp_dir:
+            path = Path(temp_dir) / "attachment.mcap"
+            with McapFileWriter.open(path, chunk_size=chunk_size) as writer:
+                writer.write_attachment("calibration.yaml", b"fx: 500.0\n", media_type="text/yaml", log_time=7)
+
+            with McapFileReader.from_file(path, enable_crc_check=True) as reader:
+                attachment_index = reader._reader._summary.get_attachment_indexes()["calibration.yaml"][0]
+                attachment = reader.get_attachments()[0]
+
+            # Record type (1) and length (8) come first and the CRC (4) is last
+            raw = path.read_bytes()[attachment_index.offset:attachment_index.offset + attachment_index.length]
+            assert attachment.crc == zlib.crc32(raw[9:-4])
+            assert attachment.crc == compute_attachment_crc(attachment)
+
+    @pytest.mark.parametrize("chunk_size", [None, 1024])
+    def test_legacy_attachment_crc(self, chunk_size):
+        """Test that attachments with a CRC of only their data, as earlier releases wrote, are read."""
+        with tempfile.TemporaryDirectory() as temp_dir:
+            path = Path(temp_dir) / "legacy.mcap"
+            with McapFileWriter.open(path, chunk_size=chunk_size, compute_data_crc=False) as writer:
+                writer._record_writer.write_attachment(AttachmentRecord(
+                    log_time=0,
+                    create_time=0,
+                    name="calibration.yaml",
+                    media_type="text/yaml",
+                    data=b"fx: 500.0\n",
+                    crc=compute_crc(b"fx: 500.0\n"),
+                ))
+
+            with McapFileReader.from_file(path, enable_crc_check=True) as reader:
+                attachments = reader.get_attachments()
+                assert [a.data for a in attachments] == [b"fx: 500.0\n"]
+
+    @pytest.mark.parametrize("chunk_size", [None, 1024])
+    def test_corrupted_attachment(self, chunk_size):
+        """Test that a flipped byte in an attachment is detected."""
+        with tempfile.TemporaryDirectory() as temp_dir:
+            path = Path(temp_dir) / "corrupted.mcap"
+            # Without a data section CRC so only the attachment CRC can catch it
+            with McapFileWriter.open(path, chunk_size=chunk_size, compute_data_crc=False) as writer:
+                writer.write_attachment("calibration.yaml", b"fx: 500.0\n", media_type="text/yaml")
+
+            raw = bytearray(path.read_bytes())
+            position = raw.index(b"fx: 500.0\n")
+            raw[position] ^= 0xFF
+            path.write_bytes(bytes(raw))
+
+            with McapFileReader.from_file(path, enable_crc_check=True) as reader:
+                with pytest.raises(McapInvalidCrcError, match="calibration.yaml"):
+                    reader.get_attachments()
+
+    @pytest.mark.parametrize("chunk_size", [None, 1024])
+    def test_invalid_attachment_crc(self, chunk_size):
+        """Test that a wrong attachment CRC only raises when the check is enabled."""
+        with tempfile.TemporaryDirectory() as temp_dir:
+            path = Path(temp_dir) / "invalid.mcap"
+            with McapFileWriter.open(path, chunk_size=chunk_size) as writer:
+                writer.write_message("/test", 1, std_msgs.String(data="hello"))
+                writer._record_writer.write_attachment(AttachmentRecord(
+                    log_time=0,
+                    create_time=0,
+                    name="calibration.yaml",
+                    media_type="text/yaml",
+                    data=b"fx: 500.0\n",
+                    crc=compute_crc(b"fx: 500.0\n") ^ 0xFFFFFFFF,
+                ))
+
+            with McapFileReader.from_file(path, enable_crc_check=True) as reader:
+                with pytest.raises(McapInvalidCrcError):
+                    reader.get_attachments()
+
+            with McapFileReader.from_file(path, enable_crc_check=False) as reader:
+                attachments = reader.get_attachments()
+                assert len(attachments) == 1
+                assert attachments[0].data == b"fx: 500.0\n"
@@ -1,10 +1,14 @@
 """Tests for the MCAP reader."""
+import gzip
+import io
 import logging
+import lzma
 import random
-from dataclasses import dataclass
+from collections import Counter
+from dataclasses import dataclass, replace
 from pathlib import Path
 from tempfile import TemporaryDirectory
-from typing import Literal
+from typing import Callable, Literal
 
 import pytest
 from mcap.reader import make_reader
@@ -16,11 +20,41 @@ from rosbags.typesys import Stores, get_typestore
 from rosbags.typesys.store import Typestore
 
 import pybag.ros1.noetic.std_msgs as ros1_std_msgs
+import pybag.ros2.humble.builtin_interfaces as ros2_builtin_interfaces
+import pybag.ros2.humble.diagnostic_msgs as ros2_diagnostic_msgs
+import pybag.ros2.humble.geometry_msgs as ros2_geometry_msgs
+import pybag.ros2.humble.nav_msgs as ros2_nav_msgs
+import pybag.ros2.humble.sensor_msgs as ros2_sensor_msgs
 import pybag.ros2.humble.std_msgs as ros2_std_msgs
 import pybag.types as t
-from pybag.mcap_reader import McapFileReader, McapMultipleFileReader
+from pybag.encoding.cdr import CdrEncoder
+from pybag.io.raw_writer import BytesWriter
+from pybag.mcap.error import (
+    McapConcatenatedError,
+    McapDecodeError,
+    McapMessageTooLargeError,
+    McapNoStatisticsError,
+    McapUnknownEncodingError,
+    McapUnknownTopicError
+)
+from pybag.mcap.record_parser import MalformedMCAP, McapRecordType
+from pybag.mcap.record_writer import McapRecordWriter
+from pybag.mcap.records import (
+    ChannelRecord,
+    FooterRecord,
+    MessageRecord,
+    SchemaRecord
+)
+from pybag.mcap_reader import (
+    DecodedMessage,
+    McapFileReader,
+    McapMultipleFileReader
+)
 from pybag.mcap_writer import McapFileWriter
+from pybag.schema.field_path import FieldPathError
+from pybag.schema.structured import StructuredTypeError
 from pybag.schema.ros2msg import Ros2MsgError
+from pybag.serialize import MessageSerializerFactory
 
 
 def _find_mcap_file(temp_dir: str) -> Path:
@@ -229,7 +263,7 @@ def test_glob_pattern_matching(chunk_size, in_log_time_order: bool, enable_crc_c
     two different namespaces (/sensor and /control) and validates:
     1. Glob patterns correctly match all topics under a namespace
     2. Messages are returned in the correct order (log_time or write order)
-    3. Glob patterns with no matches return an empty list without errors
+    3. Glob patterns with no matches raise McapUnknownTopicError
     """
     String = ros1_std_msgs.String if profile == 'ros1' else ros2_std_msgs.String
     with TemporaryDirectory() as temp_dir:
@@ -294,9 +328,8 @@ def test_glob_pattern_matching(chunk_size, in_log_time_order: bool, enable_crc_c
                 f"Expected data {expected_data}, got {[msg.data.data for msg in messages]}"
 
             # Test case 3: Glob pattern with no matches
-            messages = list(reader.messages("/nonexistent/*", in_log_time_order=in_log_time_order))
-            logging.info(f'pybag /nonexistent/*: number of messages: {len(messages)}')
-            assert len(messages) == 0, f"Expected 0 messages from /nonexistent/*, got {len(messages)}"
+            with pytest.raises(McapUnknownTopicError, match="/nonexistent/\\*"):
+                list(reader.messages("/nonexistent/*", in_log_time_order=in_log_time_order))
 
 
 @pytest.mark.parametrize(
@@ -556,6 +589,93 @@ def test_read_multiple_files_as_one(enable_crc_check: bool, profile) -> None:
         messages = list(reader.messages("/chatter"))
         assert [m.data.data for m in messages] == ["hello", "world", "again", "!!"]
 
+
+def test_read_split_recording() -> None:
+    """Test reading a directory of split files whose channels have different IDs."""
+    with TemporaryDirectory() as temp_dir:
+        temp_path = Path(temp_dir)
+        # Written out of order so the natural sort of the names matters
+        with McapFileWriter.open(temp_path / "bag_10.mcap") as writer:
+            writer.write_message("/b", 4, ros2_std_msgs.Int32(data=4))
+            writer.write_message("/a", 5, ros2_std_msgs.Int32(data=5))
+        with McapFileWriter.open(temp_path / "bag_2.mcap") as writer:
+            writer.write_message("/a", 1, ros2_std_msgs.Int32(data=1))
+            writer.write_message("/b", 3, ros2_std_msgs.Int32(data=3))
+            writer.write_message("/a", 6, ros2_std_msgs.Int32(data=6))
+            writer.write_message("/camera/front", 7, ros2_std_msgs.Int32(data=7))
+        (temp_path / "metadata.yaml").write_text("rosbag2_bagfile_information: {}\n")
+
+        with McapMultipleFileReader.from_directory(temp_path) as reader:
+            # IDs follow bag_2.mcap, where /a appears first
+            a_id, b_id = reader.get_channel_id("/a"), reader.get_channel_id("/b")
+            assert (a_id, b_id) == (1, 2)
+
+            messages = list(reader.messages(["/a", "/b"]))
+            assert [m.data.data for m in messages] == [1, 3, 4, 5, 6]
+            assert [m.channel_id for m in messages] == [a_id, b_id, b_id, a_id, a_id]
+
+            # Patterns only need to match a topic in one of the files
+            assert [m.data.data for m in reader.messages("/camera/*")] == [7]
+            with pytest.raises(McapUnknownTopicError, match="/lidar/"):
+                list(reader.messages("/lidar/*"))
+
+        with pytest.raises(FileNotFoundError):
+            McapMultipleFileReader.from_directory(temp_path / "missing")
+
+
+@pytest.mark.parametrize("chunk_size", [
+    pytest.param(None, id="without_chunks"),
+    pytest.param(64, id="with_chunks"),
+])
+def test_concatenated_files_are_rejected(chunk_size: int | None):
+    """Test that two MCAP files joined together raise instead of losing the first one."""
+    with TemporaryDirectory() as temp_dir:
+        temp_path = Path(temp_dir)
+        for name, topic in [("first.mcap", "/a"), ("second.mcap", "/b")]:
+            with McapFileWriter.open(temp_path / name, chunk_size=chunk_size) as writer:
+                for i in range(3):
+                    writer.write_message(topic, i, ros2_std_msgs.Int32(data=i))
+        first = (temp_path / "first.mcap").read_bytes()
+        second = (temp_path / "second.mcap").read_bytes()
+
+        path = temp_path / "concatenated.mcap"
+        path.write_bytes(first + second)
+        with pytest.raises(McapConcatenatedError, match=f"starts at offset {len(first)}"):
+            McapFileReader.from_file(path)
+        with pytest.raises(McapConcatenatedError):
+            McapFileReader.from_bytes(first + second)
+
+
+@pytest.mark.parametrize("chunk_size", [
+    pytest.param(None, id="without_chunks"),
+    pytest.param(64, id="with_chunks"),
+])
+def test_attached_mcap_is_not_concatenated(chunk_size: int | None):
+    """Test that an MCAP file stored in an attachment is not taken for a concatenated file."""
+    with TemporaryDirectory() as temp_dir:
+        temp_path = Path(temp_dir)
+        with McapFileWriter.open(temp_path / "inner.mcap", chunk_size=chunk_size) as writer:
+            writer.write_message("/a", 0, ros2_std_msgs.Int32(data=1))
+        # Two files back to back, as in a concatenated file
+        inner = (temp_path / "inner.mcap").read_bytes() * 2
+
+        path = temp_path / "outer.mcap"
+        with McapFileWriter.open(path, chunk_size=chunk_size) as writer:
+            writer.write_attachment("inner.mcap", inner)
+            writer.write_message("/b", 1, ros2_std_msgs.Int32(data=2))
+        data = path.read_bytes()
+        with McapFileReader.from_bytes(data) as reader:
+            summary_start = reader.get_layout()["summary_start"]
+
+        # Without a summary the records of the file are walked to find its end
+        footer = BytesWriter()
+        McapRecordWriter.write_footer(footer, FooterRecord(summary_start=0, summary_offset_start=0, summary_crc=0))
+        McapRecordWriter.write_magic_bytes(footer)
+        for contents in (data, data[:summary_start] + footer.as_bytes()):
+            with McapFileReader.from_bytes(contents) as reader:
+                assert [m.data.data for m in reader.messages("/b")] == [2]
+                assert [a.data for a in reader.get_attachments("inner.mcap")] == [inner]
+
 #############################################
 # Compatibility with Official MCAP Library  #
 #############################################
@@ -623,6 +743,79 @@ def test_random_ordered_messages_from_official_mcap(
             assert [msg.data.data for msg in messages] == [f"msg_{t}" for t in expected_log_times]
 
 
+@pytest.mark.parametrize(
+    "chunk_size",
+    [
+        pytest.param(None, id="without_chunks"),
+        pytest.param(64, id="with_chunks"),
+    ],
+)
+@pytest.mark.parametrize("in_reverse", [False, True])
+def test_equal_log_times_order(chunk_size, in_reverse: bool):
+    """Test that equal log times are ordered by channel id and then sequence number."""
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "ties.mcap"
+        with McapFileWriter.open(path, chunk_size=chunk_size) as writer:
+            b_id = writer.add_channel("/b", schema=ros2_std_msgs.String)
+            a_id = writer.add_channel("/a", schema=ros2_std_msgs.String)
+            writer.write_message("/a", 5, ros2_std_msgs.String(data="a0"))
+            writer.write_message("/b", 5, ros2_std_msgs.String(data="b0"))
+            writer.write_message("/a", 1, ros2_std_msgs.String(data="a1"))
+            # Sequence numbers written out of order on the same channel
+            serializer = MessageSerializerFactory.from_profile("ros2")
+            assert serializer is not None
+            for sequence, data in [(9, "b9"), (7, "b7")]:
+                writer._record_writer.write_message(MessageRecord(
+                    channel_id=b_id,
+                    sequence=sequence,
+                    log_time=5,
+                    publish_time=5,
+                    data=serializer.serialize_message(ros2_std_msgs.String(data=data)),
+                ))
+
+        # Log time first, then channel id (/b was added first), then sequence
+        expected = ["a1", "b0", "b7", "b9", "a0"]
+        assert b_id < a_id
+        with McapFileReader.from_file(path) as reader:
+            messages = list(reader.messages(["/a", "/b"], in_reverse=in_reverse))
+            assert [m.data.data for m in messages] == (expected[::-1] if in_reverse else expected)
+
+
+@pytest.mark.parametrize("in_reverse", [False, True])
+def test_overlapping_chunks(in_reverse: bool):
+    """Test log time order across chunks whose time ranges overlap."""
+    # Each chunk holds (log_time, topic) pairs, flushed explicitly
+    chunks = [
+        [(0, "/a"), (2, "/a"), (4, "/b"), (6, "/a")],
+        [(1, "/b"), (2, "/b"), (4, "/a"), (5, "/b")],
+        [(0, "/b"), (2, "/a"), (3, "/a")],  # Starts before the second chunk
+    ]
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "overlapping.mcap"
+        with McapFileWriter.open(path, chunk_size=1024 * 1024) as writer:
+            for i, chunk in enumerate(chunks):
+                for log_time, topic in chunk:
+                    writer.write_message(topic, log_time, ros2_std_msgs.String(data=f"{i}:{log_time}"))
+                writer.flush_chunk()
+
+        written = [f"{i}:{log_time}" for i, chunk in enumerate(chunks) for log_time, _ in chunk]
+        if in_reverse:
+            written = written[::-1]
+
+        with McapFileReader.from_file(path) as reader:
+            chunk_indexes = reader._reader.get_chunk_indexes()
+            assert len(chunk_indexes) == 3
+            assert reader._reader._has_overlapping_chunks(chunk_indexes)
+
+            messages = list(reader.messages(["/a", "/b"], in_reverse=in_reverse))
+            keys = [(m.log_time, m.channel_id, m.sequence) for m in messages]
+            assert keys == sorted(keys, reverse=in_reverse)
+            assert sorted(m.data.data for m in messages) == sorted(written)
+
+            messages = list(reader.messages(["/a", "/b"], in_log_time_order=False, in_reverse=in_reverse))
+            assert [m.data.data for m in messages] == written
+
+
 @pytest.mark.parametrize(
     "chunk_size",
     [
@@ -843,7 +1036,7 @@ def test_reverse_iteration_multiple_files(enable_crc_check: bool, profile):
 ####################################
 
 def test_mcap_ros2_char_roundtrip():
-    """Test that ROS 2 char type is correctly written and read as a string."""
+    """Test that ROS 2 char type is read back as an unsigned 8-bit integer."""
     with TemporaryDirectory() as temp_dir:
         path = Path(temp_dir) / "char_test.mcap"
 
@@ -856,7 +1049,7 @@ def test_mcap_ros2_char_roundtrip():
         with McapFileReader.from_file(path) as reader:
             messages = list(reader.messages("/char"))
             assert len(messages) == 1
-            assert messages[0].data.data == 'A'  # Should be a string, not an integer
+            assert messages[0].data.data == 65  # ASCII 'A', as rclpy decodes it
 
 
 def test_mcap_ros2_char_multiple_values():
@@ -870,15 +1063,17 @@ def test_mcap_ros2_char_multiple_values():
             writer.write_message("/char", 2000, ros2_std_msgs.Char(data='z'))
             writer.write_message("/char", 3000, ros2_std_msgs.Char(data='0'))
             writer.write_message("/char", 4000, ros2_std_msgs.Char(data=' '))
+            writer.write_message("/char", 5000, ros2_std_msgs.Char(data=200))
 
         # Read with pybag
         with McapFileReader.from_file(path) as reader:
             messages = list(reader.messages("/char"))
-            assert len(messages) == 4
-            assert messages[0].data.data == 'A'
-            assert messages[1].data.data == 'z'
-            assert messages[2].data.data == '0'
-            assert messages[3].data.data == ' '
+            assert len(messages) == 5
+            assert messages[0].data.data == 65
+            assert messages[1].data.data == 122
+            assert messages[2].data.data == 48
+            assert messages[3].data.data == 32
+            assert messages[4].data.data == 200
 
 
 #########################################
@@ -918,3 +1113,1446 @@ def test_mcap_writer_ros2_profile_rejects_ros1_duration_type():
             with McapFileWriter.open(path, profile="ros2") as writer:
                 msg = Ros1DurationMessage(elapsed=t.ros1.Duration(secs=100, nsecs=500000000))
                 writer.write_message("/duration", 1000, msg)
+
+
+##########################
+#  ROS 2 IDL Schema Test #
+##########################
+
+def test_read_ros2idl_schema():
+    """Test that channels whose schema uses the ros2idl encoding are decoded."""
+    idl = (
+        "module geometry_msgs {\n"
+        "  module msg {\n"
+        "    struct Point {\n"
+        "      double x;\n"
+        "      double y;\n"
+        "      double z;\n"
+        "    };\n"
+        "  };\n"
+        "};\n"
+    )
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "idl.mcap"
+        with McapFileWriter.open(path, profile="ros2") as writer:
+            record_writer = writer._record_writer
+            schema_id = writer._summary.next_schema_id()
+            record_writer.write_schema(SchemaRecord(
+                id=schema_id,
+                name="geometry_msgs/msg/Point",
+                encoding="ros2idl",
+                data=idl.encode("utf-8"),
+            ))
+            channel_id = writer._summary.next_channel_id()
+            record_writer.write_channel(ChannelRecord(
+                id=channel_id,
+                schema_id=schema_id,
+                topic="/point",
+                message_encoding="cdr",
+                metadata={},
+            ))
+            encoder = CdrEncoder(little_endian=True)
+            encoder.float64(1.0)
+            encoder.float64(2.0)
+            encoder.float64(3.0)
+            record_writer.write_message(MessageRecord(
+                channel_id=channel_id,
+                sequence=writer._summary.next_sequence_id(channel_id),
+                log_time=1000,
+                publish_time=1000,
+                data=encoder.save(),
+            ))
+
+        with McapFileReader.from_file(path) as reader:
+            messages = list(reader.messages("/point"))
+            assert len(messages) == 1
+            assert messages[0].msg_type == "geometry_msgs/msg/Point"
+            point = messages[0].data
+            assert (point.x, point.y, point.z) == (1.0, 2.0, 3.0)
+
+
+def test_message_encodings():
+    """Test that JSON channels are parsed and unknown encodings are returned as bytes."""
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "encodings.mcap"
+        with McapFileWriter.open(path, profile="ros2") as writer:
+            writer.write_message("/cdr", 1, ros2_std_msgs.String(data="cdr"))
+            record_writer = writer._record_writer
+            for topic, schema_encoding, message_encoding, data in [
+                ("/json", "jsonschema", "json", b'{"data": "json", "values": [1, 2]}'),
+                ("/proto", "protobuf", "protobuf", b"\x0a\x05proto"),
+            ]:
+                schema_id = writer._summary.next_schema_id()
+                record_writer.write_schema(SchemaRecord(
+                    id=schema_id,
+                    name="example.Message",
+                    encoding=schema_encoding,
+                    data=b"",
+                ))
+                channel_id = writer._summary.next_channel_id()
+                record_writer.write_channel(ChannelRecord(
+                    id=channel_id,
+                    schema_id=schema_id,
+                    topic=topic,
+                    message_encoding=message_encoding,
+                    metadata={},
+                ))
+                record_writer.write_message(MessageRecord(
+                    channel_id=channel_id,
+                    sequence=writer._summary.next_sequence_id(channel_id),
+                    log_time=2,
+                    publish_time=2,
+                    data=data,
+                ))
+
+        with McapFileReader.from_file(path) as reader:
+            messages = {m.topic: m for m in reader.messages(["/cdr", "/json", "/proto"])}
+            assert messages["/cdr"].message_encoding == "cdr"
+            assert messages["/cdr"].data.data == "cdr"
+            assert messages["/json"].message_encoding == "json"
+            assert messages["/json"].data == {"data": "json", "values": [1, 2]}
+            assert messages["/proto"].message_encoding == "protobuf"
+            assert messages["/proto"].data == b"\x0a\x05proto"
+
+            assert reader.get_message_at("/json", 2).data == {"data": "json", "values": [1, 2]}
+            with pytest.raises(FieldPathError):
+                list(reader.messages("/json", field_paths=["data"]))
+
+
+def test_protobuf_channel(monkeypatch):
+    """Test that protobuf messages are returned as bytes with their schema metadata."""
+    descriptor_set = b"\x0a\x10\x0a\x0eexample.proto"  # FileDescriptorSet with one file
+    payload = b"\x0a\x05hello"
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "protobuf.mcap"
+        with McapFileWriter.open(path, profile="ros2") as writer:
+            writer.write_message("/cdr", 1, ros2_std_msgs.String(data="cdr"))
+            schema_id = writer._summary.next_schema_id()
+            writer._record_writer.write_schema(SchemaRecord(
+                id=schema_id,
+                name="example.Message",
+                encoding="protobuf",
+                data=descriptor_set,
+            ))
+            channel_id = writer._summary.next_channel_id()
+            writer._record_writer.write_channel(ChannelRecord(
+                id=channel_id,
+                schema_id=schema_id,
+                topic="/proto",
+                message_encoding="protobuf",
+                metadata={},
+            ))
+            writer._record_writer.write_message(MessageRecord(
+                channel_id=channel_id,
+                sequence=writer._summary.next_sequence_id(channel_id),
+                log_time=2,
+                publish_time=2,
+                data=payload,
+            ))
+
+        with McapFileReader.from_file(path) as reader:
+            class NoDecoding:
+                def deserialize_message(self, *args, **kwargs):
+                    raise AssertionError("protobuf messages should not be decoded")
+            monkeypatch.setattr(reader, "_message_deserializer", NoDecoding())
+
+            messages = list(reader.messages("/proto"))
+            assert len(messages) == 1
+            assert messages[0].data == payload
+            assert messages[0].decode_error is None
+            assert messages[0].message_encoding == "protobuf"
+            assert messages[0].schema_name == "example.Message"
+            assert messages[0].schema_encoding == "protobuf"
+            assert reader.get_schema("/proto").data == descriptor_set
+
+        with McapFileReader.from_file(path) as reader:
+            message = next(reader.messages("/cdr"))
+            assert message.schema_name == "std_msgs/msg/String"
+            assert message.schema_encoding == "ros2msg"
+
+
+def test_decoded_message_positional_fields():
+    """Test that the encodings added to DecodedMessage keep its positional fields in place."""
+    message = DecodedMessage("/chatter", "std_msgs/msg/String", 1, 2, 3, 4, "data")
+    assert (message.channel_id, message.sequence, message.log_time, message.publish_time) == (1, 2, 3, 4)
+    assert message.data == "data"
+    assert (message.schema_encoding, message.message_encoding) == ("", "")
+
+
+@pytest.mark.parametrize("chunk_size", [
+    pytest.param(None, id="without_chunks"),
+    pytest.param(64, id="with_chunks"),
+])
+def test_schemaless_channels(chunk_size: int | None):
+    """Test that channels without a schema (schema id 0) are read rather than skipped."""
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "schemaless.mcap"
+        with McapFileWriter.open(path, profile="ros2", chunk_size=chunk_size) as writer:
+            writer.write_message("/cdr", 1, ros2_std_msgs.String(data="cdr"))
+            record_writer = writer._record_writer
+            for topic, message_encoding, data in [
+                ("/json", "json", b'{"data": "json"}'),
+                ("/raw", "cbor", b"\xa1\x61\x61\x01"),
+            ]:
+                channel_id = writer._summary.next_channel_id()
+                record_writer.write_channel(ChannelRecord(
+                    id=channel_id,
+                    schema_id=0,
+                    topic=topic,
+                    message_encoding=message_encoding,
+                    metadata={},
+                ))
+                record_writer.write_message(MessageRecord(
+                    channel_id=channel_id,
+                    sequence=writer._summary.next_sequence_id(channel_id),
+                    log_time=2,
+                    publish_time=2,
+                    data=data,
+                ))
+
+        with McapFileReader.from_file(path) as reader:
+            assert reader.get_schema("/json") is None
+            messages = {m.topic: m for m in reader.messages(["/cdr", "/json", "/raw"])}
+            assert messages["/cdr"].data.data == "cdr"
+            assert messages["/json"].msg_type == ""
+            assert messages["/json"].data == {"data": "json"}
+            assert messages["/raw"].msg_type == ""
+            assert messages["/raw"].message_encoding == "cbor"
+            assert messages["/raw"].data == b"\xa1\x61\x61\x01"
+
+            assert reader.get_message_at("/raw", 2).data == b"\xa1\x61\x61\x01"
+
+
+####################################
+#  Registered Message Types Tests  #
+####################################
+
+def test_register_message_type():
+    """Test that registered constructors are used for matching message types."""
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "registered.mcap"
+        with McapFileWriter.open(path, profile="ros2") as writer:
+            pose = ros2_geometry_msgs.Pose(
+                position=ros2_geometry_msgs.Point(x=1.0, y=2.0, z=3.0),
+                orientation=ros2_geometry_msgs.Quaternion(x=0.0, y=0.0, z=0.0, w=1.0),
+            )
+            writer.write_message("/pose", 1000, pose)
+
+        with McapFileReader.from_file(path) as reader:
+            reader.register_message_type("geometry_msgs/msg/Pose", ros2_geometry_msgs.Pose)
+            # Names without the interface namespace match as well
+            reader.register_message_type("geometry_msgs/Point", ros2_geometry_msgs.Point)
+            message = next(reader.messages("/pose"))
+            assert isinstance(message.data, ros2_geometry_msgs.Pose)
+            assert message.data.position == pose.position
+            # Unregistered types still use the generated dataclasses
+            assert not isinstance(message.data.orientation, ros2_geometry_msgs.Quaternion)
+            assert message.data.orientation.w == 1.0
+
+
+@pytest.mark.parametrize("profile", ["ros1", "ros2"])
+def test_register_message_type_with_callable(profile):
+    """Test that any callable accepting the fields as keyword arguments can be registered."""
+    String = ros1_std_msgs.String if profile == 'ros1' else ros2_std_msgs.String
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "registered.mcap"
+        with McapFileWriter.open(path, profile=profile) as writer:
+            writer.write_message("/chatter", 1000, String(data="hello"))
+
+        with McapFileReader.from_file(path) as reader:
+            assert next(reader.messages("/chatter")).data.data == "hello"
+            reader.register_message_type(String.__msg_name__, lambda **fields: fields)
+            assert next(reader.messages("/chatter")).data == {"data": "hello"}
+
+
+###################################
+#  Maximum Message Size Filtering #
+###################################
+
+@pytest.mark.parametrize(
+    "chunk_size",
+    [
+        pytest.param(None, id="without_chunks"),
+        pytest.param(64, id="with_chunks"),
+    ]
+)
+def test_max_message_bytes(chunk_size):
+    """Test that messages larger than max_message_bytes are skipped or rejected."""
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "sizes.mcap"
+        with McapFileWriter.open(path, chunk_size=chunk_size) as writer:
+            writer.write_message("/data", 1, ros2_std_msgs.String(data="small"))
+            writer.write_message("/data", 2, ros2_std_msgs.String(data="x" * 1000))
+            writer.write_message("/data", 3, ros2_std_msgs.String(data="tiny"))
+
+        with McapFileReader.from_file(path) as reader:
+            messages = list(reader.messages("/data", max_message_bytes=100))
+            assert [m.data.data for m in messages] == ["small", "tiny"]
+            assert [m.log_time for m in messages] == [1, 3]
+
+            assert len(list(reader.messages("/data"))) == 3
+
+            with pytest.raises(McapMessageTooLargeError, match="limit 100"):
+                list(reader.messages("/data", max_message_bytes=100, on_oversized="error"))
+
+
+#################################
+#  Sequence Number Selection    #
+#################################
+
+@pytest.mark.parametrize(
+    "chunk_size",
+    [
+        pytest.param(None, id="without_chunks"),
+        pytest.param(64, id="with_chunks"),
+    ]
+)
+def test_messages_by_sequence(chunk_size):
+    """Test selecting specific messages by their sequence numbers."""
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "sequences.mcap"
+        with McapFileWriter.open(path, chunk_size=chunk_size) as writer:
+            for i in range(20):
+                writer.write_message("/a", i, ros2_std_msgs.String(data=f"a_{i}"))
+                writer.write_message("/b", i, ros2_std_msgs.String(data=f"b_{i}"))
+
+        with McapFileReader.from_file(path) as reader:
+            wanted = {3, 11, 17}
+            messages = list(reader.messages("/a", sequences=wanted))
+            assert [m.sequence for m in messages] == [3, 11, 17]
+            assert [m.data.data for m in messages] == [f"a_{s - 1}" for s in [3, 11, 17]]
+
+            # Sequence numbers are per channel
+            messages = list(reader.messages(["/a", "/b"], sequences=[5]))
+            assert sorted(m.topic for m in messages) == ["/a", "/b"]
+
+            assert list(reader.messages("/a", sequences=[])) == []
+
+
+#################################
+#  Message Limit                #
+#################################
+
+@pytest.mark.parametrize(
+    "chunk_size",
+    [
+        pytest.param(None, id="without_chunks"),
+        pytest.param(64, id="with_chunks"),
+    ]
+)
+def test_messages_limit(chunk_size):
+    """Test returning only the first messages of a topic."""
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "limit.mcap"
+        with McapFileWriter.open(path, chunk_size=chunk_size) as writer:
+            for i in range(100):
+                writer.write_message("/a", i, ros2_std_msgs.String(data=f"a_{i}"))
+                writer.write_message("/b", i, ros2_std_msgs.String(data=f"b_{i}"))
+
+        with McapFileReader.from_file(path) as reader:
+            messages = list(reader.messages("/a", limit=5))
+            assert [m.data.data for m in messages] == [f"a_{i}" for i in range(5)]
+
+            messages = list(reader.messages("/a", limit=5, in_reverse=True))
+            assert [m.data.data for m in messages] == [f"a_{i}" for i in range(99, 94, -1)]
+
+            messages = list(reader.messages("/a", limit=5, in_log_time_order=False))
+            assert [m.data.data for m in messages] == [f"a_{i}" for i in range(5)]
+
+            # The limit counts the messages passing the filter, and reading stops there
+            seen = []
+            def even(msg):
+                seen.append(msg.log_time)
+                return msg.log_time % 2 == 0
+            messages = list(reader.messages("/a", filter=even, limit=5))
+            assert [m.log_time for m in messages] == [0, 2, 4, 6, 8]
+            assert seen == list(range(9))
+
+            assert len(list(reader.messages(["/a", "/b"], limit=150))) == 150
+            assert len(list(reader.messages("/a", limit=500))) == 100
+            assert list(reader.messages("/a", limit=0)) == []
+            with pytest.raises(ValueError, match="limit must not be negative"):
+                list(reader.messages("/a", limit=-1))
+
+
+#################################
+#  Nearest Message Lookup       #
+#################################
+
+@pytest.mark.parametrize(
+    "chunk_size",
+    [
+        pytest.param(None, id="without_chunks"),
+        pytest.param(64, id="with_chunks"),
+    ]
+)
+def test_get_message_at(chunk_size):
+    """Test seeking to the message nearest a timestamp."""
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "seek.mcap"
+        with McapFileWriter.open(path, chunk_size=chunk_size) as writer:
+            for i in range(20):
+                writer.write_message("/a", i * 100, ros2_std_msgs.String(data=f"a_{i}"))
+                writer.write_message("/b", i * 100 + 50, ros2_std_msgs.String(data=f"b_{i}"))
+
+        with McapFileReader.from_file(path) as reader:
+            # Exact matches at the start, middle and end
+            assert reader.get_message_at("/a", 0).data.data == "a_0"
+            assert reader.get_message_at("/a", 1000).data.data == "a_10"
+            assert reader.get_message_at("/a", 1900).data.data == "a_19"
+
+            # Between messages the nearest one wins, ties go to the earlier one
+            assert reader.get_message_at("/a", 1040).data.data == "a_10"
+            assert reader.get_message_at("/a", 1060).data.data == "a_11"
+            assert reader.get_message_at("/a", 1050).data.data == "a_10"
+
+            # Outside the recorded range
+            assert reader.get_message_at("/a", -500).log_time == 0
+            assert reader.get_message_at("/a", 10_000).log_time == 1900
+            assert reader.get_message_at("/b", 0).data.data == "b_0"
+
+            with pytest.raises(McapUnknownTopicError):
+                reader.get_message_at("/unknown", 0)
+
+
+#################################
+#  Relative Timestamps          #
+#################################
+
+@pytest.mark.parametrize(
+    "chunk_size",
+    [
+        pytest.param(None, id="without_chunks"),
+        pytest.param(64, id="with_chunks"),
+    ]
+)
+def test_messages_relative_time(chunk_size):
+    """Test returning timestamps relative to the start of the file."""
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "relative.mcap"
+        with McapFileWriter.open(path, chunk_size=chunk_size) as writer:
+            for i in range(5):
+                writer.write_message("/a", 1_000 + i * 10, ros2_std_msgs.String(data="a"), publish_time=1_005 + i * 10)
+            writer.write_message("/b", 500, ros2_std_msgs.String(data="b"))
+
+        with McapFileReader.from_file(path) as reader:
+            messages = list(reader.messages("/a", relative_time=True))
+            assert [m.log_time for m in messages] == [500, 510, 520, 530, 540]
+            assert [m.publish_time for m in messages] == [505, 515, 525, 535, 545]
+
+            messages = list(reader.messages(["/a", "/b"], relative_time=True))
+            assert messages[0].log_time == 0
+            assert messages[0].topic == "/b"
+
+            # Absolute timestamps by default
+            assert next(reader.messages("/a")).log_time == 1_000
+
+
+def test_messages_relative_time_without_statistics(monkeypatch):
+    """Test that the earliest message is used when there are no statistics."""
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "relative.mcap"
+        with McapFileWriter.open(path, chunk_size=64) as writer:
+            writer.write_message("/a", 300, ros2_std_msgs.String(data="a"))
+            writer.write_message("/b", 200, ros2_std_msgs.String(data="b"))
+
+        with McapFileReader.from_file(path) as reader:
+            def no_statistics():
+                raise McapNoStatisticsError("No statistics")
+            monkeypatch.setattr(reader._reader, "get_statistics", no_statistics)
+
+            messages = list(reader.messages("/a", relative_time=True))
+            assert [m.log_time for m in messages] == [100]
+
+
+@pytest.mark.parametrize("chunk_size", [
+    pytest.param(None, id="without_chunks"),
+    pytest.param(64, id="with_chunks"),
+])
+@pytest.mark.parametrize("has_statistics", [True, False])
+def test_get_time_range(monkeypatch, chunk_size: int | None, has_statistics: bool):
+    """Test the time range with and without a statistics record."""
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "time_range.mcap"
+        with McapFileWriter.open(path, chunk_size=chunk_size) as writer:
+            writer.write_message("/a", 300, ros2_std_msgs.String(data="a"))
+            writer.write_message("/b", 200, ros2_std_msgs.String(data="b"))
+            writer.write_message("/a", 1_000, ros2_std_msgs.String(data="c"))
+        empty_path = Path(temp_dir) / "empty.mcap"
+        with McapFileWriter.open(empty_path, chunk_size=chunk_size):
+            pass
+
+        for file_path, expected in [(path, (200, 1_000, 800)), (empty_path, None)]:
+            with McapFileReader.from_file(file_path) as reader:
+                if not has_statistics:
+                    def no_statistics():
+                        raise McapNoStatisticsError("No statistics")
+                    monkeypatch.setattr(reader._reader, "get_statistics", no_statistics)
+                assert reader.get_time_range() == expected
+
+
+@pytest.mark.parametrize("chunk_size", [
+    pytest.param(None, id="without_chunks"),
+    pytest.param(64, id="with_chunks"),
+])
+def test_messages_combine_time_fields(chunk_size: int | None):
+    """Test that Time and Duration fields gain a combined nanoseconds value."""
+    odometry = ros2_nav_msgs.Odometry(
+        header=ros2_std_msgs.Header(
+            stamp=ros2_builtin_interfaces.Time(sec=12, nanosec=345),
+            frame_id="odom",
+        ),
+        child_frame_id="base_link",
+        pose=ros2_geometry_msgs.PoseWithCovariance(
+            pose=ros2_geometry_msgs.Pose(
+                position=ros2_geometry_msgs.Point(x=0.0, y=0.0, z=0.0),
+                orientation=ros2_geometry_msgs.Quaternion(x=0.0, y=0.0, z=0.0, w=1.0),
+            ),
+            covariance=[0.0] * 36,
+        ),
+        twist=ros2_geometry_msgs.TwistWithCovariance(
+            twist=ros2_geometry_msgs.Twist(
+                linear=ros2_geometry_msgs.Vector3(x=0.0, y=0.0, z=0.0),
+                angular=ros2_geometry_msgs.Vector3(x=0.0, y=0.0, z=0.0),
+            ),
+            covariance=[0.0] * 36,
+        ),
+    )
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "time.mcap"
+        with McapFileWriter.open(path, chunk_size=chunk_size) as writer:
+            writer.write_message("/odom", 1, odometry)
+            writer.write_message("/duration", 2, ros2_builtin_interfaces.Duration(sec=-1, nanosec=500))
+
+        with McapFileReader.from_file(path) as reader:
+            odom, duration = list(reader.messages(["/odom", "/duration"], combine_time_fields=True))
+            stamp = odom.data.header.stamp
+            assert stamp.__nanoseconds__ == 12_000_000_345
+            assert (stamp.sec, stamp.nanosec) == (12, 345)
+            assert duration.data.__nanoseconds__ == -999_999_500
+
+            odom = next(reader.messages("/odom"))
+            assert not hasattr(odom.data.header.stamp, "__nanoseconds__")
+
+
+@pytest.mark.parametrize("chunk_size", [
+    pytest.param(None, id="without_chunks"),
+    pytest.param(64, id="with_chunks"),
+])
+def test_messages_by_publish_time(chunk_size: int | None):
+    """Test filtering and ordering messages by publish time instead of log time."""
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "publish.mcap"
+        with McapFileWriter.open(path, chunk_size=chunk_size) as writer:
+            # Publish times run opposite to log times
+            for i in range(5):
+                writer.write_message("/a", 10 + i, ros2_std_msgs.Int32(data=i), publish_time=100 - i)
+            writer.write_message("/b", 20, ros2_std_msgs.Int32(data=5), publish_time=97)
+
+        with McapFileReader.from_file(path) as reader:
+            by_log = [m.data.data for m in reader.messages(["/a", "/b"])]
+            assert by_log == [0, 1, 2, 3, 4, 5]
+
+            by_publish = [m.data.data for m in reader.messages(["/a", "/b"], time_field="publish")]
+            assert by_publish == [4, 3, 5, 2, 1, 0]
+
+            in_range = reader.messages("/a", start_time=97, end_time=98, time_field="publish")
+            assert [(m.log_time, m.publish_time) for m in in_range] == [(13, 97), (12, 98)]
+
+            # Log time bounds would match nothing
+            assert list(reader.messages("/a", start_time=97, end_time=98)) == []
+
+            reverse = reader.messages(["/a", "/b"], time_field="publish", in_reverse=True)
+            assert [m.data.data for m in reverse] == [0, 1, 2, 5, 3, 4]
+
+            write_order = reader.messages(["/a", "/b"], time_field="publish", in_log_time_order=False)
+            assert [m.data.data for m in write_order] == [0, 1, 2, 3, 4, 5]
+
+            with pytest.raises(ValueError, match="time_field"):
+                list(reader.messages("/a", time_field="receive"))
+
+
+@pytest.mark.parametrize("has_statistics", [True, False])
+def test_messages_progress(monkeypatch, has_statistics: bool):
+    """Test that the progress callback reports messages read against the total."""
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "progress.mcap"
+        with McapFileWriter.open(path, chunk_size=64) as writer:
+            for i in range(25):
+                writer.write_message("/a", i, ros2_std_msgs.Int32(data=i))
+                writer.write_message("/b", i, ros2_std_msgs.Int32(data=i))
+
+        with McapFileReader.from_file(path) as reader:
+            if not has_statistics:
+                def no_statistics():
+                    raise McapNoStatisticsError("No statistics")
+                monkeypatch.setattr(reader._reader, "get_statistics", no_statistics)
+
+            calls = []
+            messages = list(reader.messages("/a", progress=lambda *args: calls.append(args), progress_interval=10))
+            assert len(messages) == 25
+            total = 25 if has_statistics else None
+            assert calls == [(10, total), (20, total), (25, total)]
+
+            # Exceptions from the callback stop iteration
+            def stop(processed, total):
+                raise RuntimeError("cancelled")
+            iterator = reader.messages(["/a", "/b"], progress=stop, progress_interval=5)
+            assert len([next(iterator) for _ in range(4)]) == 4
+            with pytest.raises(RuntimeError, match="cancelled"):
+                next(iterator)
+
+            with pytest.raises(ValueError, match="progress_interval"):
+                list(reader.messages("/a", progress=stop, progress_interval=0))
+
+
+#################################
+#  Topics By Type               #
+#################################
+
+def test_get_topics_of_type():
+    """Test finding all topics carrying a given message type."""
+    image = ros2_sensor_msgs.Image(
+        header=ros2_std_msgs.Header(stamp=ros2_builtin_interfaces.Time(sec=0, nanosec=0), frame_id="camera"),
+        height=1,
+        width=2,
+        encoding="mono8",
+        is_bigendian=0,
+        step=2,
+        data=[0, 255],
+    )
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "images.mcap"
+        with McapFileWriter.open(path) as writer:
+            writer.write_message("/camera/left", 1, image)
+            writer.write_message("/camera/right", 1, image)
+            writer.write_message("/chatter", 1, ros2_std_msgs.String(data="hi"))
+
+        with McapFileReader.from_file(path) as reader:
+            expected = ["/camera/left", "/camera/right"]
+            assert sorted(reader.get_topics_of_type("sensor_msgs/msg/Image")) == expected
+            assert sorted(reader.get_topics_of_type("sensor_msgs/Image")) == expected
+            assert reader.get_topics_of_type("std_msgs/String") == ["/chatter"]
+            assert reader.get_topics_of_type("sensor_msgs/msg/CompressedImage") == []
+
+
+#################################
+#  Decode Error Limits          #
+#################################
+
+@pytest.mark.parametrize(
+    "chunk_size",
+    [
+        pytest.param(None, id="without_chunks"),
+        pytest.param(64, id="with_chunks"),
+    ]
+)
+def test_max_decode_errors(chunk_size):
+    """Test tolerating a limited number of messages that fail to decode."""
+    corrupt = b"\x00\x01\x00\x00\x05"  # Truncated string length
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "corrupt.mcap"
+        with McapFileWriter.open(path, chunk_size=chunk_size) as writer:
+            writer.write_message("/data", 1, ros2_std_msgs.String(data="good"))
+            channel_id = writer._summary.get_channel_id("/data")
+            for log_time in (2, 3):
+                writer._record_writer.write_message(MessageRecord(
+                    channel_id=channel_id,
+                    sequence=writer._summary.next_sequence_id(channel_id),
+                    log_time=log_time,
+                    publish_time=log_time,
+                    data=corrupt,
+                ))
+            writer.write_message("/data", 4, ros2_std_msgs.String(data="also good"))
+
+        with McapFileReader.from_file(path) as reader:
+            messages = list(reader.messages("/data", max_decode_errors=2))
+            assert [m.log_time for m in messages] == [1, 2, 3, 4]
+            assert messages[0].data.data == "good"
+            assert messages[0].decode_error is None
+            assert messages[1].data == corrupt
+            assert messages[1].decode_error is not None
+            assert messages[3].data.data == "also good"
+
+            with pytest.raises(McapDecodeError, match="limit 1"):
+                list(reader.messages("/data", max_decode_errors=1))
+
+            # Without a limit the first failure is raised as is
+            with pytest.raises(Exception):
+                list(reader.messages("/data"))
+
+
+def test_strict_decoding():
+    """Test that strict mode raises where lenient decoding returns raw bytes."""
+    truncated = b"\x00\x01\x00\x00\x05\x00\x00\x00ab"  # String cut off after two characters
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "strict.mcap"
+        with McapFileWriter.open(path) as writer:
+            writer.write_message("/data", 1, ros2_std_msgs.String(data="good"))
+            channel_id = writer._summary.get_channel_id("/data")
+            writer._record_writer.write_message(MessageRecord(
+                channel_id=channel_id,
+                sequence=writer._summary.next_sequence_id(channel_id),
+                log_time=2,
+                publish_time=2,
+                data=truncated,
+            ))
+            raw_channel_id = writer._summary.next_channel_id()
+            writer._record_writer.write_channel(ChannelRecord(
+                id=raw_channel_id,
+                schema_id=0,
+                topic="/raw",
+                message_encoding="cbor",
+                metadata={},
+            ))
+
+        with McapFileReader.from_file(path) as reader:
+            # Lenient decoding returns the raw bytes with the error
+            messages = list(reader.messages("/data", max_decode_errors=1))
+            assert messages[0].data.data == "good"
+            assert messages[1].data == truncated
+            assert "data" in messages[1].decode_error
+
+            with pytest.raises(McapDecodeError, match=r"/data at 2: Failed decoding data at byte"):
+                list(reader.messages("/data", strict=True))
+
+            # Only the valid message is returned before the failure
+            strict_messages = reader.messages("/data", strict=True)
+            assert next(strict_messages).data.data == "good"
+            with pytest.raises(McapDecodeError):
+                next(strict_messages)
+
+            # Channels whose messages cannot be decoded are rejected up front
+            assert list(reader.messages("/raw")) == []
+            with pytest.raises(McapUnknownEncodingError, match="cbor"):
+                list(reader.messages("/raw", strict=True))
+
+            with pytest.raises(ValueError, match="max_decode_errors"):
+                list(reader.messages("/data", strict=True, max_decode_errors=1))
+
+
+#################################
+#  In-Memory Reading            #
+#################################
+
+@pytest.mark.parametrize(
+    "chunk_size",
+    [
+        pytest.param(None, id="without_chunks"),
+        pytest.param(64, id="with_chunks"),
+    ]
+)
+@pytest.mark.parametrize("enable_crc_check", [False, True])
+def test_read_from_bytes(chunk_size, enable_crc_check: bool):
+    """Test reading an MCAP file held entirely in memory."""
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "in_memory.mcap"
+        with McapFileWriter.open(path, chunk_size=chunk_size) as writer:
+            for i in range(5):
+                writer.write_message("/a", i, ros2_std_msgs.String(data=f"a_{i}"))
+                writer.write_message("/b", i, ros2_std_msgs.String(data=f"b_{i}"))
+        data = path.read_bytes()
+
+    with McapFileReader.from_bytes(data, enable_crc_check=enable_crc_check) as reader:
+        assert sorted(reader.get_topics()) == ["/a", "/b"]
+        assert reader.get_message_count("/a") == 5
+        assert [m.data.data for m in reader.messages("/b")] == [f"b_{i}" for i in range(5)]
+
+
+@pytest.mark.parametrize(
+    "chunk_size",
+    [
+        pytest.param(None, id="without_chunks"),
+        pytest.param(64, id="with_chunks"),
+    ]
+)
+def test_read_from_stream(chunk_size):
+    """Test that reading from a seekable stream matches reading from bytes."""
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "stream.mcap"
+        with McapFileWriter.open(path, chunk_size=chunk_size) as writer:
+            for i in range(5):
+                writer.write_message("/a", i, ros2_std_msgs.String(data=f"a_{i}"))
+                writer.write_message("/b", i, ros2_std_msgs.String(data=f"b_{i}"))
+        data = path.read_bytes()
+
+    with McapFileReader.from_bytes(data) as reader:
+        expected = [(m.topic, m.log_time, m.data.data) for m in reader.messages(["/a", "/b"])]
+
+    stream = io.BytesIO(data)
+    with McapFileReader.from_stream(stream, enable_crc_check=True) as reader:
+        assert sorted(reader.get_topics()) == ["/a", "/b"]
+        assert [(m.topic, m.log_time, m.data.data) for m in reader.messages(["/a", "/b"])] == expected
+    assert stream.closed
+
+
+#################################
+#  Topic Patterns               #
+#################################
+
+def test_topic_glob_and_regex():
+    """Test selecting topics by plain name, glob pattern and regular expression."""
+    topics = ["/sensors/front/imu", "/sensors/rear/imu", "/sensors/front/gps", "/cmd_vel"]
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "patterns.mcap"
+        with McapFileWriter.open(path) as writer:
+            for i, topic in enumerate(topics):
+                writer.write_message(topic, i, ros2_std_msgs.String(data=topic))
+
+        with McapFileReader.from_file(path) as reader:
+            # Plain topic names are matched exactly
+            assert [m.topic for m in reader.messages("/cmd_vel")] == ["/cmd_vel"]
+            assert list(reader.messages("/sensors")) == []
+
+            messages = list(reader.messages("/sensors/*/imu"))
+            assert [m.topic for m in messages] == ["/sensors/front/imu", "/sensors/rear/imu"]
+
+            messages = list(reader.messages(topic_regex=r"/sensors/(front|rear)/imu"))
+            assert [m.topic for m in messages] == ["/sensors/front/imu", "/sensors/rear/imu"]
+
+            # The regex must match the whole topic name
+            messages = list(reader.messages(topic_regex=r".*/front/.*"))
+            assert [m.topic for m in messages] == ["/sensors/front/imu", "/sensors/front/gps"]
+
+            with pytest.raises(McapUnknownTopicError, match="No topic matches"):
+                list(reader.messages(topic_regex=r"/sensors/front"))
+
+            # Glob patterns must match a topic too
+            with pytest.raises(McapUnknownTopicError, match="No topic matches"):
+                list(reader.messages("/sensors/*/lidar"))
+
+            # Patterns resolve to a sorted list of topics
+            assert reader._expand_topics(["/sensors/*", "/cmd_vel"]) == [
+                "/cmd_vel", "/sensors/front/gps", "/sensors/front/imu", "/sensors/rear/imu"
+            ]
+
+            with pytest.raises(ValueError, match="topic_regex"):
+                list(reader.messages())
+
+
+#################################
+#  Structured Arrays            #
+#################################
+
+def test_read_structured():
+    """Test reading a fixed-layout topic into a numpy structured array."""
+    import numpy as np
+
+    messages = [
+        ros2_geometry_msgs.PoseWithCovariance(
+            pose=ros2_geometry_msgs.Pose(
+                position=ros2_geometry_msgs.Point(x=float(i), y=2.0 * i, z=-1.0),
+                orientation=ros2_geometry_msgs.Quaternion(x=0.0, y=0.0, z=0.0, w=1.0),
+            ),
+            covariance=[float(i + j) for j in range(36)],
+        )
+        for i in range(5)
+    ]
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "structured.mcap"
+        with McapFileWriter.open(path, chunk_size=256) as writer:
+            for i, message in enumerate(messages):
+                writer.write_message("/pose", i, message)
+            writer.write_message("/chatter", 0, ros2_std_msgs.String(data="hi"))
+
+        with McapFileReader.from_file(path) as reader:
+            array = reader.read_structured("/pose")
+            assert array.shape == (5,)
+            assert array.dtype["covariance"].shape == (36,)
+
+            decoded = [m.data for m in reader.messages("/pose")]
+            for row, message in zip(array, decoded):
+                assert row["pose"]["position"]["x"] == message.pose.position.x
+                assert row["pose"]["position"]["y"] == message.pose.position.y
+                assert row["pose"]["orientation"]["w"] == message.pose.orientation.w
+                assert list(row["covariance"]) == message.covariance
+            np.testing.assert_array_equal(array["pose"]["position"]["x"], np.arange(5.0))
+
+            with pytest.raises(StructuredTypeError):
+                reader.read_structured("/chatter")
+
+
+#################################
+#  Statistics                   #
+#################################
+
+@pytest.mark.parametrize("chunk_size", [
+    pytest.param(None, id="without_chunks"),
+    pytest.param(64, id="with_chunks"),
+])
+def test_get_statistics(chunk_size: int | None):
+    """Test that statistics report message counts keyed by topic."""
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "statistics.mcap"
+        with McapFileWriter.open(path, chunk_size=chunk_size) as writer:
+            writer.write_message("/a", 100, ros2_std_msgs.String(data="a"))
+            writer.write_message("/b", 200, ros2_std_msgs.Int32(data=1))
+            writer.write_message("/a", 300, ros2_std_msgs.String(data="c"))
+
+        with McapFileReader.from_file(path) as reader:
+            statistics = reader.get_statistics()
+            assert statistics is not None
+            assert statistics.message_count == 3
+            assert statistics.schema_count == 2
+            assert statistics.channel_count == 2
+            assert statistics.message_start_time == 100
+            assert statistics.message_end_time == 300
+            assert statistics.channel_message_counts == {"/a": 2, "/b": 1}
+            if chunk_size is None:
+                assert statistics.chunk_count == 0
+            else:
+                assert statistics.chunk_count > 0
+
+
+def test_get_statistics_missing(monkeypatch):
+    """Test that None is returned when the file has no statistics record."""
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "statistics.mcap"
+        with McapFileWriter.open(path) as writer:
+            writer.write_message("/a", 100, ros2_std_msgs.String(data="a"))
+
+        with McapFileReader.from_file(path) as reader:
+            def no_statistics():
+                raise McapNoStatisticsError("No statistics")
+            monkeypatch.setattr(reader._reader, "get_statistics", no_statistics)
+            assert reader.get_statistics() is None
+
+
+@pytest.mark.parametrize("chunk_size", [
+    pytest.param(None, id="without_chunks"),
+    pytest.param(64, id="with_chunks"),
+])
+@pytest.mark.parametrize("has_summary_offsets", [True, False])
+def test_read_statistics(chunk_size: int | None, has_summary_offsets: bool):
+    """Test reading only the statistics record, with and without summary offsets."""
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "statistics.mcap"
+        with McapFileWriter.open(path, chunk_size=chunk_size) as writer:
+            for i in range(10):
+                writer.write_message("/a", i, ros2_std_msgs.String(data="a"))
+            writer.write_message("/b", 20, ros2_std_msgs.Int32(data=1))
+
+        if not has_summary_offsets:
+            # Clear the footer's summary_offset_start (before the summary CRC and magic)
+            data = bytearray(path.read_bytes())
+            data[-20:-12] = bytes(8)
+            path.write_bytes(bytes(data))
+
+        with McapFileReader.from_file(path) as reader:
+            offsets = reader.get_summary_offsets()
+            expected = reader._reader.get_statistics()
+
+        if has_summary_offsets:
+            opcodes = {offset.group_opcode for offset in offsets}
+            assert {McapRecordType.SCHEMA, McapRecordType.CHANNEL, McapRecordType.STATISTICS} <= opcodes
+        else:
+            assert offsets == []
+        statistics = McapFileReader.read_statistics(path)
+        assert statistics == expected
+        assert statistics is not None
+        assert statistics.message_count == 11
+
+
+@pytest.mark.parametrize("chunk_size", [
+    pytest.param(None, id="without_chunks"),
+    pytest.param(64, id="with_chunks"),
+])
+@pytest.mark.parametrize("has_statistics", [True, False])
+def test_message_count_by_channel(monkeypatch, chunk_size: int | None, has_statistics: bool):
+    """Test counting messages per topic with and without a statistics record."""
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "counts.mcap"
+        with McapFileWriter.open(path, chunk_size=chunk_size) as writer:
+            for i in range(5):
+                writer.write_message("/a", i, ros2_std_msgs.String(data="a"))
+            writer.write_message("/b", 10, ros2_std_msgs.Int32(data=1))
+            writer.add_channel("/empty", schema=ros2_std_msgs.String)
+
+        with McapFileReader.from_file(path) as reader:
+            if not has_statistics:
+                def no_statistics():
+                    raise McapNoStatisticsError("No statistics")
+                monkeypatch.setattr(reader._reader, "get_statistics", no_statistics)
+            assert reader.message_count_by_channel() == {"/a": 5, "/b": 1, "/empty": 0}
+
+
+def test_chunks_for_topic():
+    """Test that only the chunks holding a topic's messages are returned."""
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "chunks.mcap"
+        with McapFileWriter.open(path, chunk_size=1) as writer:
+            writer.write_message("/a", 0, ros2_std_msgs.String(data="a"))
+            writer.write_message("/b", 1, ros2_std_msgs.Int32(data=1))
+            writer.write_message("/a", 2, ros2_std_msgs.String(data="a"))
+            writer.add_channel("/empty", schema=ros2_std_msgs.String)
+
+        with McapFileReader.from_file(path) as reader:
+            chunk_indexes = reader._reader.get_chunk_indexes()
+            assert len(chunk_indexes) == 3
+
+            chunks = reader.chunks_for_topic("/a")
+            assert [(start, end) for start, end, _, _ in chunks] == [(0, 0), (2, 2)]
+            assert [offset for _, _, offset, _ in chunks] == [
+                chunk_indexes[0].chunk_start_offset,
+                chunk_indexes[2].chunk_start_offset,
+            ]
+            assert [size for _, _, _, size in chunks] == [
+                chunk_indexes[0].compressed_size,
+                chunk_indexes[2].compressed_size,
+            ]
+
+            assert [(start, end) for start, end, _, _ in reader.chunks_for_topic("/b")] == [(1, 1)]
+            assert reader.chunks_for_topic("/empty") == []
+            with pytest.raises(McapUnknownTopicError):
+                reader.chunks_for_topic("/missing")
+
+
+@pytest.mark.parametrize("file_compression, compress", [
+    pytest.param("gzip", gzip.compress, id="gzip"),
+    pytest.param("xz", lzma.compress, id="xz"),
+])
+def test_read_compressed_file(file_compression: str, compress: Callable[[bytes], bytes]):
+    """Test that whole-file gzip and xz compressed MCAP files are read transparently."""
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "data.mcap"
+        with McapFileWriter.open(path, chunk_size=64) as writer:
+            for i in range(5):
+                writer.write_message("/a", i, ros2_std_msgs.Int32(data=i))
+        compressed_path = Path(temp_dir) / f"data.mcap.{'gz' if file_compression == 'gzip' else 'xz'}"
+        compressed_path.write_bytes(compress(path.read_bytes()))
+
+        with McapFileReader.from_file(compressed_path) as reader:
+            assert [m.data.data for m in reader.messages("/a")] == [0, 1, 2, 3, 4]
+
+        with McapFileReader.from_file(compressed_path, file_compression=file_compression) as reader:
+            assert reader.get_message_count("/a") == 5
+
+        # Detection can be turned off
+        with pytest.raises(MalformedMCAP):
+            McapFileReader.from_file(compressed_path, file_compression="none")
+
+        # An uncompressed file is read from disk as before
+        with McapFileReader.from_file(path, file_compression="none") as reader:
+            assert reader.get_message_count("/a") == 5
+
+
+@pytest.mark.parametrize("chunk_size", [
+    pytest.param(None, id="without_chunks"),
+    pytest.param(1024, id="with_chunks"),
+])
+def test_get_layout(chunk_size: int | None):
+    """Test that the section sizes add up to the file size."""
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "layout.mcap"
+        with McapFileWriter.open(path, chunk_size=chunk_size, chunk_compression="zstd") as writer:
+            for i in range(10):
+                writer.write_message("/a", i, ros2_std_msgs.String(data="a" * 50))
+
+        with McapFileReader.from_file(path) as reader:
+            layout = reader.get_layout()
+            chunk_indexes = reader._reader.get_chunk_indexes()
+
+        footer_size = 29 + 8  # Footer record and magic bytes
+        assert layout["file_size"] == path.stat().st_size
+        assert layout["data_section_start"] == 8
+        assert layout["summary_start"] == layout["data_section_end"]
+        assert (
+            layout["data_section_end"] + layout["summary_size"] + footer_size
+            == layout["file_size"]
+        )
+        assert layout["chunk_compressed_size"] == sum(c.compressed_size for c in chunk_indexes)
+        assert layout["chunk_uncompressed_size"] == sum(c.uncompressed_size for c in chunk_indexes)
+        if chunk_size is None:
+            assert layout["chunk_compressed_size"] == 0
+        else:
+            assert 0 < layout["chunk_compressed_size"] < layout["chunk_uncompressed_size"]
+            assert layout["chunk_compressed_size"] < layout["data_section_end"] - layout["data_section_start"]
+
+
+#################################
+#  Field Selection              #
+#################################
+
+@pytest.mark.parametrize("chunk_size", [
+    pytest.param(None, id="without_chunks"),
+    pytest.param(64, id="with_chunks"),
+])
+def test_messages_field_paths(chunk_size: int | None):
+    """Test returning only selected fields, including ones after skipped strings."""
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "fields.mcap"
+        with McapFileWriter.open(path, chunk_size=chunk_size) as writer:
+            for i in range(3):
+                pose_array = ros2_geometry_msgs.PoseArray(
+                    header=ros2_std_msgs.Header(
+                        stamp=ros2_builtin_interfaces.Time(sec=i, nanosec=10 * i),
+                        frame_id="odd" * i,
+                    ),
+                    poses=[
+                        ros2_geometry_msgs.Pose(
+                            position=ros2_geometry_msgs.Point(x=float(i + j), y=0.0, z=0.0),
+                            orientation=ros2_geometry_msgs.Quaternion(x=0.0, y=0.0, z=0.0, w=1.0),
+                        )
+                        for j in range(i)
+                    ],
+                )
+                writer.write_message("/poses", i, pose_array)
+
+        with McapFileReader.from_file(path) as reader:
+            messages = list(reader.messages(
+                "/poses",
+                field_paths=["header.stamp.sec", "poses[1].position.x"],
+                filter=lambda m: m.data.header.stamp.nanosec > 0,
+            ))
+            assert [m.data for m in messages] == [
+                {"header.stamp.sec": 1, "poses[1].position.x": None},
+                {"header.stamp.sec": 2, "poses[1].position.x": 3.0},
+            ]
+
+            with pytest.raises(FieldPathError, match="has no field 'missing'"):
+                list(reader.messages("/poses", field_paths=["header.missing"]))
+
+
+@pytest.mark.parametrize("chunk_size", [
+    pytest.param(None, id="without_chunks"),
+    pytest.param(64, id="with_chunks"),
+])
+def test_messages_field_map(chunk_size: int | None):
+    """Test renaming fields of messages returned as dicts."""
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "field_map.mcap"
+        with McapFileWriter.open(path, chunk_size=chunk_size) as writer:
+            for i in range(2):
+                pose = ros2_geometry_msgs.PoseStamped(
+                    header=ros2_std_msgs.Header(
+                        stamp=ros2_builtin_interfaces.Time(sec=i, nanosec=0),
+                        frame_id="map",
+                    ),
+                    pose=ros2_geometry_msgs.Pose(
+                        position=ros2_geometry_msgs.Point(x=float(i), y=2.0, z=3.0),
+                        orientation=ros2_geometry_msgs.Quaternion(x=0.0, y=0.0, z=0.0, w=1.0),
+                    ),
+                )
+                writer.write_message("/pose", i, pose)
+
+        with McapFileReader.from_file(path) as reader:
+            messages = list(reader.messages(
+                "/pose",
+                field_map={"pose.position.x": "px"},
+                filter=lambda m: m.data.pose.position.x > 0,
+            ))
+            assert len(messages) == 1
+            assert messages[0].data["pose"]["position"] == {"px": 1.0, "y": 2.0, "z": 3.0}
+            assert messages[0].data["header"] == {"stamp": {"sec": 1, "nanosec": 0}, "frame_id": "map"}
+
+            # Without a field map messages are still decoded to dataclasses
+            unmapped = list(reader.messages("/pose"))
+            assert [m.data.pose.position.x for m in unmapped] == [0.0, 1.0]
+
+            with pytest.raises(FieldPathError, match="has no field 'w'"):
+                list(reader.messages("/pose", field_map={"pose.position.w": "pw"}))
+            with pytest.raises(ValueError, match="cannot be combined"):
+                list(reader.messages("/pose", field_paths=["pose"], field_map={"pose": "p"}))
+
+
+@pytest.mark.parametrize(
+    "chunk_size",
+    [
+        pytest.param(None, id="without_chunks"),
+        pytest.param(64, id="with_chunks"),
+    ]
+)
+def test_scan(chunk_size: int | None):
+    """Test iterating over undecoded messages."""
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "scan.mcap"
+        with McapFileWriter.open(path, chunk_size=chunk_size) as writer:
+            writer.write_message("/a", 3, ros2_std_msgs.Int32(data=1))
+            writer.write_message("/b", 1, ros2_std_msgs.Int32(data=2))
+            writer.write_message("/a", 2, ros2_std_msgs.Int32(data=3))
+
+        with McapFileReader.from_file(path) as reader:
+            a_id = reader._reader.get_channel_id("/a")
+            b_id = reader._reader.get_channel_id("/b")
+            scanned = list(reader.scan())
+            assert [(c, t) for c, t, _ in scanned] == [(a_id, 3), (b_id, 1), (a_id, 2)]
+            assert all(isinstance(data, bytes) for _, _, data in scanned)
+            assert scanned[0][2] == b"\x00\x01\x00\x00\x01\x00\x00\x00"
+
+            assert [t for _, t, _ in reader.scan("/a")] == [3, 2]
+            assert list(reader.scan([])) == []
+
+
+@pytest.mark.parametrize("chunk_size", [
+    pytest.param(None, id="without_chunks"),
+    pytest.param(64, id="with_chunks"),
+])
+def test_export(chunk_size: int | None):
+    """Test exporting one topic and a time range into a new file."""
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "source.mcap"
+        with McapFileWriter.open(path, chunk_size=chunk_size) as writer:
+            for i in range(10):
+                writer.write_message("/a", i, ros2_std_msgs.String(data=f"a_{i}"))
+                writer.write_message("/b", i, ros2_std_msgs.Int32(data=i))
+
+        export_path = Path(temp_dir) / "export.mcap"
+        with McapFileReader.from_file(path) as reader:
+            assert reader.export(export_path, "/b", start_time=3, end_time=6, chunk_size=chunk_size) == 4
+            original = [m for m in reader.messages("/b") if 3 <= m.log_time <= 6]
+
+        with McapFileReader.from_file(export_path, enable_crc_check=True) as reader:
+            assert reader.get_topics() == ["/b"]
+            exported = list(reader.messages("/b"))
+            assert [m.data.data for m in exported] == [3, 4, 5, 6]
+            assert [(m.channel_id, m.sequence) for m in exported] == [
+                (m.channel_id, m.sequence) for m in original
+            ]
+            assert reader.get_message_count("/b") == 4
+
+
+def test_get_defaults_and_constants():
+    """Test reading the defaults and constants declared in a topic's schema."""
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "defaults.mcap"
+        with McapFileWriter.open(path) as writer:
+            writer.write_message("/diagnostics", 1, ros2_diagnostic_msgs.DiagnosticStatus(
+                level=b"\x00", name="motor", message="ok", hardware_id="m1", values=[],
+            ))
+            writer.add_channel("/config", schema=t.SchemaText(
+                name="example_msgs/msg/Config",
+                text="int32 MAX_RETRIES=3\nint32 retries 1\nstring label \"default\"\nfloat64 gain\n",
+            ))
+
+        with McapFileReader.from_file(path) as reader:
+            assert reader.get_constants("/diagnostics") == {"OK": 0, "WARN": 1, "ERROR": 2, "STALE": 3}
+            assert reader.get_defaults("/diagnostics") == {}
+
+            assert reader.get_constants("/config") == {"MAX_RETRIES": 3}
+            assert reader.get_defaults("/config") == {"retries": 1, "label": "default"}
+
+            with pytest.raises(McapUnknownTopicError):
+                reader.get_defaults("/missing")
+
+
+#################################
+#  Record Iteration             #
+#################################
+
+@pytest.mark.parametrize("chunk_size", [
+    pytest.param(None, id="without_chunks"),
+    pytest.param(1024, id="with_chunks"),
+])
+def test_iter_records(chunk_size: int | None):
+    """Test that every record is returned, including those inside chunks."""
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "records.mcap"
+        with McapFileWriter.open(path, chunk_size=chunk_size, chunk_compression="zstd") as writer:
+            writer.write_message("/a", 1, ros2_std_msgs.String(data="a"))
+            writer.write_message("/b", 2, ros2_std_msgs.Int32(data=1))
+            writer.write_message("/a", 3, ros2_std_msgs.String(data="c"))
+            writer.write_attachment("calibration.yaml", b"k: v")
+            writer.write_metadata("session", {"k": "v"})
+
+        with McapFileReader.from_file(path) as reader:
+            records = list(reader.iter_records())
+
+    expected = {
+        "header": 1,
+        "schema": 4,  # In the data and summary sections
+        "channel": 4,
+        "message": 3,
+        "attachment": 1,
+        "metadata": 1,
+        "data_end": 1,
+        "attachment_index": 1,
+        "metadata_index": 1,
+        "statistics": 1,
+        "summary_offset": 5,
+        "footer": 1,
+    }
+    if chunk_size is not None:
+        expected.update({"chunk": 1, "message_index": 2, "chunk_index": 1, "summary_offset": 6})
+    assert Counter(record["type"] for record in records) == expected
+
+    assert records[0] == {"type": "header", "profile": "ros2", "library": records[0]["library"]}
+    assert records[-1]["type"] == "footer"
+    messages = [record for record in records if record["type"] == "message"]
+    assert [m["log_time"] for m in messages] == [1, 2, 3]
+    attachment = next(record for record in records if record["type"] == "attachment")
+    assert (attachment["name"], attachment["data"]) == ("calibration.yaml", b"k: v")
+
+
+#################################
+#  Validation                   #
+#################################
+
+def _write_valid_mcap(path: Path, chunk_size: int | None) -> None:
+    with McapFileWriter.open(path, chunk_size=chunk_size) as writer:
+        writer.write_message("/a", 100, ros2_std_msgs.String(data="a"))
+        writer.write_message("/b", 200, ros2_std_msgs.Int32(data=1))
+        writer.write_message("/a", 300, ros2_std_msgs.String(data="c"))
+
+
+@pytest.mark.parametrize("chunk_size", [
+    pytest.param(None, id="without_chunks"),
+    pytest.param(64, id="with_chunks"),
+])
+def test_validate_valid_file(chunk_size: int | None):
+    """Test that a file written by pybag has no validation issues."""
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "valid.mcap"
+        _write_valid_mcap(path, chunk_size)
+        with McapFileReader.from_file(path) as reader:
+            assert reader.validate() == []
+
+
+def test_validate_missing_end_magic():
+    """Test that a file without the trailing magic bytes is reported."""
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "magic.mcap"
+        _write_valid_mcap(path, 64)
+        path.write_bytes(path.read_bytes()[:-8] + b"\x00" * 8)
+        with McapFileReader.from_file(path) as reader:
+            issues = reader.validate()
+            assert len(issues) == 1
+            assert issues[0]["severity"] == "error"
+            assert "end of the file" in issues[0]["message"]
+
+
+def test_validate_unknown_schema():
+    """Test that a channel referencing a missing schema is reported."""
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "schema.mcap"
+        with McapFileWriter.open(path) as writer:
+            writer.write_message("/a", 100, ros2_std_msgs.String(data="a"))
+            writer._record_writer.write_channel(ChannelRecord(
+                id=writer._summary.next_channel_id(),
+                schema_id=42,
+                topic="/orphan",
+                message_encoding="cdr",
+                metadata={},
+            ))
+        with McapFileReader.from_file(path) as reader:
+            issues = reader.validate()
+            assert [i["severity"] for i in issues] == ["error"]
+            assert "unknown schema 42" in issues[0]["message"]
+
+
+def test_validate_chunk_index_offset(monkeypatch):
+    """Test that a chunk index not pointing at a chunk record is reported."""
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "chunks.mcap"
+        _write_valid_mcap(path, 64)
+        with McapFileReader.from_file(path) as reader:
+            chunk_indexes = reader._reader.get_chunk_indexes()
+            bad_index = replace(chunk_indexes[0], chunk_start_offset=chunk_indexes[0].chunk_start_offset + 1)
+            monkeypatch.setattr(reader._reader, "get_chunk_indexes", lambda: [bad_index, *chunk_indexes[1:]])
+            issues = reader.validate()
+            assert [i["severity"] for i in issues] == ["error"]
+            assert f"offset {bad_index.chunk_start_offset} points at opcode" in issues[0]["message"]
+
+
+@pytest.mark.parametrize("chunk_size", [
+    pytest.param(None, id="without_chunks"),
+    pytest.param(64, id="with_chunks"),
+])
+def test_validate_statistics_message_count(monkeypatch, chunk_size: int | None):
+    """Test that a statistics message count not matching the channel counts is reported."""
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "statistics.mcap"
+        _write_valid_mcap(path, chunk_size)
+        with McapFileReader.from_file(path) as reader:
+            statistics = replace(reader._reader.get_statistics(), message_count=5)
+            monkeypatch.setattr(reader._reader, "get_statistics", lambda: statistics)
+            issues = reader.validate()
+            assert [i["severity"] for i in issues] == ["error"]
+            assert "message count 5" in issues[0]["message"]
+
+
+def test_validate_time_bounds(monkeypatch):
+    """Test that inconsistent statistics and chunk time bounds are reported."""
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "times.mcap"
+        _write_valid_mcap(path, 64)
+        with McapFileReader.from_file(path) as reader:
+            statistics = replace(reader._reader.get_statistics(), message_start_time=200)
+            monkeypatch.setattr(reader._reader, "get_statistics", lambda: statistics)
+            issues = reader.validate()
+            assert issues == [{
+                "severity": "error",
+                "message": f"Chunk at offset {reader._reader.get_chunk_indexes()[0].chunk_start_offset} "
+                           "is outside the statistics time range",
+            }]
+
+            statistics = replace(statistics, message_start_time=400)
+            monkeypatch.setattr(reader._reader, "get_statistics", lambda: statistics)
+            issues = reader.validate()
+            assert "start time is after the end time" in issues[0]["message"]
+
+
+def test_validate_missing_statistics(monkeypatch):
+    """Test that a missing statistics record is reported as a warning."""
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "statistics.mcap"
+        _write_valid_mcap(path, None)
+        with McapFileReader.from_file(path) as reader:
+            def no_statistics():
+                raise McapNoStatisticsError("No statistics")
+            monkeypatch.setattr(reader._reader, "get_statistics", no_statistics)
+            assert reader.validate() == [{"severity": "warning", "message": "No statistics record"}]
+
+
+#################################
+#  Empty Files and Chunks       #
+#################################
+
+@pytest.mark.parametrize("chunk_size", [
+    pytest.param(None, id="without_chunks"),
+    pytest.param(64, id="with_chunks"),
+])
+def test_read_file_without_messages(chunk_size: int | None):
+    """Test reading a file with no channels and a file with a channel but no messages."""
+    with TemporaryDirectory() as temp_dir:
+        empty_path = Path(temp_dir) / "empty.mcap"
+        with McapFileWriter.open(empty_path, chunk_size=chunk_size):
+            pass
+        channel_path = Path(temp_dir) / "channel.mcap"
+        with McapFileWriter.open(channel_path, chunk_size=chunk_size) as writer:
+            writer.add_channel("/a", schema=ros2_std_msgs.String)
+
+        with McapFileReader.from_file(empty_path) as reader:
+            assert reader.get_topics() == []
+            assert list(reader.scan()) == []
+            assert reader.get_time_range() is None
+            assert reader.validate() == []
+
+        with McapFileReader.from_file(channel_path) as reader:
+            assert reader.get_topics() == ["/a"]
+            assert reader.get_message_count("/a") == 0
+            assert list(reader.messages("/a")) == []
+            assert list(reader.scan("/a")) == []
+            assert reader.get_time_range() is None
+
+
+@pytest.mark.parametrize("chunk_compression", ["none", "lz4", "zstd"])
+def test_read_empty_chunk(monkeypatch, chunk_compression: str):
+    """Test that a chunk without any records yields no messages."""
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / "empty_chunk.mcap"
+        with McapFileWriter.open(path, chunk_size=1024, chunk_compression=chunk_compression) as writer:
+            writer.write_message("/a", 100, ros2_std_msgs.String(data="a"))
+            writer.flush_chunk()
+            writer._record_writer._flush_chunk()  # Writes a chunk with no records
+            writer.write_message("/a", 200, ros2_std_msgs.String(data="b"))
+
+        with McapFileReader.from_file(path, enable_crc_check=True) as reader:
+            assert len(reader._reader.get_chunk_indexes()) == 3
+            assert [m.data.data for m in reader.messages("/a")] == ["a", "b"]
+            assert [m.data.data for m in reader.messages("/a", in_reverse=True)] == ["b", "a"]
+            assert reader.validate() == []
+
+            def no_statistics():
+                raise McapNoStatisticsError("No statistics")
+            monkeypatch.setattr(reader._reader, "get_statistics", no_statistics)
+            assert reader.get_time_range() == (100, 200, 100)
@@ -1,6 +1,17 @@
-from pybag.io.raw_reader import BytesReader
+import os
+import random
+import struct
+from pathlib import Path
+
+import pytest
+
+from pybag.io.raw_reader import BytesReader, FileReader
 from pybag.io.raw_writer import BytesWriter
-from pybag.mcap.record_parser import McapRecordParser
+from pybag.mcap.record_parser import (
+    MalformedMCAP,
+    McapRecordParser,
+    McapUnexpectedEofError
+)
 from pybag.mcap.record_writer import McapRecordWriter
 from pybag.mcap.records import (
     AttachmentIndexRecord,
@@ -16,10 +27,28 @@ from pybag.mcap.records import (
     MetadataIndexRecord,
     MetadataRecord,
     SchemaRecord,
+    RecordType,
     StatisticsRecord,
     SummaryOffsetRecord
 )
 
+# Records whose length is only known from the length prefix
+VARIABLE_LENGTH_RECORDS = [
+    RecordType.HEADER,
+    RecordType.SCHEMA,
+    RecordType.CHANNEL,
+    RecordType.MESSAGE,
+    RecordType.CHUNK,
+    RecordType.MESSAGE_INDEX,
+    RecordType.CHUNK_INDEX,
+    RecordType.ATTACHMENT,
+    RecordType.ATTACHMENT_INDEX,
+    RecordType.STATISTICS,
+    RecordType.METADATA,
+    RecordType.METADATA_INDEX,
+    RecordType.SUMMARY_OFFSET,
+]
+
 
 def test_header_encode_decode():
     record = HeaderRecord(profile="test_profile", library="pybag")
@@ -196,3 +225,89 @@ def test_summary_offset_encode_decode():
     reader = BytesReader(writer.as_bytes())
     parsed = McapRecordParser.parse_summary_offset(reader)
     assert parsed == record
+
+
+@pytest.mark.parametrize("record_type", VARIABLE_LENGTH_RECORDS, ids=lambda r: r.name.lower())
+def test_record_length_exceeds_file(tmp_path: Path, record_type: RecordType):
+    path = tmp_path / "truncated.mcap"
+    path.write_bytes(struct.pack('<BQ', record_type, 1 << 62) + b'\x00' * 16)
+
+    with FileReader(path) as reader:
+        with pytest.raises(MalformedMCAP, match=f"{record_type.name} record length"):
+            McapRecordParser._parse_record(record_type, reader)
+
+
+def _records_bytes() -> bytes:
+    writer = BytesWriter()
+    McapRecordWriter.write_header(writer, HeaderRecord(profile="ros2", library="pybag"))
+    for i in range(3):
+        McapRecordWriter.write_message(
+            writer, MessageRecord(channel_id=1, sequence=i, log_time=i, publish_time=i, data=b"msg")
+        )
+    McapRecordWriter.write_data_end(writer, DataEndRecord(data_section_crc=0))
+    return writer.as_bytes()
+
+
+def test_parse_records_until_end_of_data():
+    reader = BytesReader(_records_bytes())
+    records = [record for _, record in McapRecordParser.parse_record(reader)]
+    assert [type(record) for record in records] == [HeaderRecord, *[MessageRecord] * 3, DataEndRecord]
+    assert McapRecordParser.peek_record(reader) == 0
+
+    # Peeking past the end of the data also finds no record
+    reader.seek_from_end(0)
+    assert McapRecordParser.peek_record(reader) == 0
+
+
+def test_parse_records_from_file_reads_size_once(tmp_path: Path, monkeypatch):
+    path = tmp_path / "records.mcap"
+    path.write_bytes(_records_bytes())
+
+    with FileReader(path) as reader:
+        def no_fstat(*args):
+            raise AssertionError("The file size should be cached when the file is opened")
+        monkeypatch.setattr(os, "fstat", no_fstat)
+        records = [record for _, record in McapRecordParser.parse_record(reader)]
+        assert len(records) == 5
+        assert reader.size() == path.stat().st_size
+
+
+@pytest.mark.parametrize("cut", [
+    pytest.param(3, id="record_length"),
+    pytest.param(20, id="record_body"),
+])
+def test_truncated_record_raises_unexpected_eof(cut: int):
+    data = _records_bytes()
+    # The second message starts after the header and one message
+    header_end = len(data) - 13 - 3 * (9 + 22 + 3)
+    second_message = header_end + 9 + 22 + 3
+    truncated = data[:second_message + cut]
+
+    reader = BytesReader(truncated)
+    with pytest.raises(McapUnexpectedEofError):
+        list(McapRecordParser.parse_record(reader))
+
+    reader = BytesReader(truncated)
+    reader.seek_from_start(second_message)
+    with pytest.raises(McapUnexpectedEofError):
+        McapRecordParser.skip_record(reader)
+
+
+def test_random_records_fail_gracefully(tmp_path: Path):
+    rng = random.Random(0)
+    path = tmp_path / "random.mcap"
+    for _ in range(500):
+        record_type = rng.choice(VARIABLE_LENGTH_RECORDS)
+        record_length = rng.choice([
+            rng.randrange(64),
+            rng.randrange(1 << 32, 1 << 64),
+            (1 << 64) - 1,
+        ])
+        payload = rng.randbytes(rng.randrange(64))
+        path.write_bytes(struct.pack('<BQ', record_type, record_length) + payload)
+
+        with FileReader(path) as reader:
+            try:
+                McapRecordParser._parse_record(record_type, reader)
+            except (MalformedMCAP, struct.error, UnicodeDecodeError):
+                pass
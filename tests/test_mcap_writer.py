@@ -5,6 +5,7 @@ from pathlib import Path
 from typing import Literal
 
 import pytest
+import zstandard as zstd
 from mcap.reader import make_reader
 from mcap_ros1.decoder import DecoderFactory as Ros1DecoderFactory
 from mcap_ros2.decoder import DecoderFactory as Ros2DecoderFactory
@@ -17,7 +18,13 @@ from pybag.encoding.cdr import CdrDecoder
 from pybag.encoding.rosmsg import RosMsgDecoder
 from pybag.io.raw_reader import BytesReader, CrcReader, FileReader
 from pybag.mcap.crc import assert_data_crc
-from pybag.mcap.record_parser import McapRecordParser
+from pybag.mcap.error import McapNonMonotonicError
+from pybag.mcap.record_parser import (
+    DATA_END_SIZE,
+    FOOTER_SIZE,
+    MAGIC_BYTES_SIZE,
+    McapRecordParser
+)
 from pybag.mcap.record_reader import McapChunkedReader
 from pybag.mcap.records import RecordType
 from pybag.mcap_reader import McapFileReader
@@ -721,3 +728,421 @@ def test_append_mode_file_not_exists(tmp_path: Path):
     file_path = tmp_path / "nonexistent.mcap"
     with pytest.raises(FileNotFoundError):
         McapFileWriter.open(file_path, mode="a")
+
+
+@pytest.mark.parametrize("chunk_size", [None, 1024])
+def test_big_endian_roundtrip(tmp_path: Path, chunk_size: int | None):
+    """Test writing big-endian CDR messages and reading them back."""
+    file_path = tmp_path / "big_endian.mcap"
+    message = ExampleMessage(
+        integer=-42,
+        text="hello",
+        fixed=[1, 2, 3],
+        dynamic=[4, 5],
+        sub=SubMessage(7),
+        sub_array=[SubMessage(1), SubMessage(2), SubMessage(3)],
+    )
+    with McapFileWriter.open(file_path, chunk_size=chunk_size, little_endian=False) as writer:
+        writer.write_message("/example", 1, message)
+
+    with McapFileReader.from_file(file_path) as reader:
+        raw = next(reader._reader.get_messages([1]))
+        assert raw.data[:4] == b"\x00\x00\x00\x00"  # Big-endian CDR header
+
+        decoder = CdrDecoder(raw.data)
+        assert decoder.int32() == -42
+        assert decoder.string() == "hello"
+
+        decoded = next(reader.messages("/example")).data
+        assert decoded.integer == -42
+        assert decoded.text == "hello"
+        assert decoded.fixed == [1, 2, 3]
+        assert decoded.dynamic == [4, 5]
+        assert decoded.sub.value == 7
+        assert [s.value for s in decoded.sub_array] == [1, 2, 3]
+
+
+def test_big_endian_rejected_for_ros1(tmp_path: Path):
+    """Test that big-endian output is rejected for the ros1 profile."""
+    with pytest.raises(ValueError, match="little endian"):
+        McapFileWriter.open(tmp_path / "ros1.mcap", profile="ros1", little_endian=False)
+
+
+@pytest.mark.parametrize("chunk_size", [None, 1024])
+@pytest.mark.parametrize("profile", ["ros1", "ros2"])
+def test_reader_header_and_footer(tmp_path: Path, chunk_size: int | None, profile: str):
+    """Test that the reader exposes the header and footer written by the writer."""
+    String = ros1_std_msgs.String if profile == "ros1" else ros2_std_msgs.String
+    file_path = tmp_path / "header_footer.mcap"
+    with McapFileWriter.open(file_path, chunk_size=chunk_size, profile=profile) as writer:
+        writer.write_message("/test", 1, String(data="hello"))
+
+    with McapFileReader.from_file(file_path) as reader:
+        assert reader.header.profile == profile
+        assert reader.header.library == f"pybag {__version__}"
+        assert reader.library == f"pybag {__version__}"
+        assert reader.footer.summary_start != 0
+        assert reader.footer.summary_offset_start != 0
+        assert reader.footer.summary_crc != 0
+
+
+@pytest.mark.parametrize("chunk_size", [None, 1024])
+def test_custom_library(tmp_path: Path, chunk_size: int | None):
+    """Test that the library written to the header can be customized."""
+    file_path = tmp_path / "library.mcap"
+    with McapFileWriter.open(file_path, chunk_size=chunk_size, library="my_tool 1.2.3") as writer:
+        writer.write_message("/test", 1, ros2_std_msgs.String(data="hello"))
+
+    with McapFileReader.from_file(file_path) as reader:
+        assert reader.header.profile == "ros2"
+        assert reader.header.library == "my_tool 1.2.3"
+
+    # Appending keeps the library of the existing file
+    with McapFileWriter.open(file_path, mode="a", chunk_size=chunk_size, library="other") as writer:
+        writer.write_message("/test", 2, ros2_std_msgs.String(data="world"))
+
+    with McapFileReader.from_file(file_path) as reader:
+        assert reader.library == "my_tool 1.2.3"
+        assert [m.data.data for m in reader.messages("/test")] == ["hello", "world"]
+
+
+@pytest.mark.parametrize("chunk_size", [None, 1024])
+def test_reader_schema_text(tmp_path: Path, chunk_size: int | None):
+    """Test that the schema definitions written can be read back."""
+    file_path = tmp_path / "schemas.mcap"
+    with McapFileWriter.open(file_path, chunk_size=chunk_size) as writer:
+        writer.write_message("/simple", 1, SimpleMessage(value=1, text="a"))
+        writer.write_message("/simple_again", 2, SimpleMessage(value=2, text="b"))
+        writer.write_message("/string", 3, ros2_std_msgs.String(data="c"))
+
+    with McapFileReader.from_file(file_path) as reader:
+        schema = reader.get_schema("/simple")
+        assert schema is not None
+        assert schema.name == "test_msgs/SimpleMessage"
+        assert schema.encoding == "ros2msg"
+        assert schema.data.decode() == "int32 value\nstring text\n"
+
+        schemas = reader.get_schemas()
+        assert sorted(schemas) == ["std_msgs/msg/String", "test_msgs/SimpleMessage"]
+        assert schemas["test_msgs/SimpleMessage"] == schema
+        assert schemas["std_msgs/msg/String"].data.decode() == "string data\n"
+
+
+@pytest.mark.parametrize("chunk_size", [None, 1024])
+def test_sequences_are_per_channel(tmp_path: Path, chunk_size: int | None):
+    """Test that the writer numbers messages per channel without gaps."""
+    file_path = tmp_path / "sequences.mcap"
+    with McapFileWriter.open(file_path, chunk_size=chunk_size) as writer:
+        for i in range(3):
+            writer.write_message("/a", i, ros2_std_msgs.String(data="a"))
+            writer.write_message("/b", i, ros2_std_msgs.String(data="b"))
+        writer.write_message("/a", 3, ros2_std_msgs.String(data="a"))
+
+    with McapFileReader.from_file(file_path) as reader:
+        assert [m.sequence for m in reader.messages("/a")] == [1, 2, 3, 4]
+        assert [m.sequence for m in reader.messages("/b")] == [1, 2, 3]
+
+
+@pytest.mark.parametrize("chunk_size", [None, 1024])
+def test_add_schema_and_channel_ids(tmp_path: Path, chunk_size: int | None):
+    """Test that schema and channel IDs increase and identical schemas are reused."""
+    file_path = tmp_path / "ids.mcap"
+    with McapFileWriter.open(file_path, chunk_size=chunk_size) as writer:
+        proto_id = writer.add_schema("example.Message", "protobuf", b"descriptor")
+        assert writer.add_schema("example.Message", "protobuf", b"descriptor") == proto_id
+        other_id = writer.add_schema("example.Message", "protobuf", b"other descriptor")
+        assert other_id == proto_id + 1
+
+        # The schema generated for /chatter reuses this ID
+        string_id = writer.add_schema("std_msgs/msg/String", "ros2msg", b"string data\n")
+        assert string_id == other_id + 1
+        channel_ids = [
+            writer.add_channel("/proto", schema=proto_id, message_encoding="protobuf", metadata={"k": "v"}),
+            writer.add_channel("/raw", schema=0, message_encoding="cbor"),
+            writer.add_channel("/string", schema=string_id),
+            writer.add_channel("/chatter", schema=ros2_std_msgs.String),
+        ]
+        assert channel_ids == sorted(channel_ids)
+        assert len(set(channel_ids)) == 4
+        assert writer.add_channel("/proto", schema=other_id) == channel_ids[0]
+
+        writer.write_message("/string", 1, ros2_std_msgs.String(data="hello"))
+        writer.write_message("/chatter", 2, ros2_std_msgs.String(data="world"))
+
+        with pytest.raises(ValueError, match="Unknown schema ID"):
+            writer.add_channel("/missing", schema=100)
+
+    with McapFileReader.from_file(file_path) as reader:
+        channels = {c.topic: c for c in reader.get_channels()}
+        assert channels["/proto"].schema_id == proto_id
+        assert channels["/proto"].message_encoding == "protobuf"
+        assert channels["/proto"].metadata == {"k": "v"}
+        assert channels["/raw"].schema_id == 0
+        assert channels["/string"].schema_id == string_id
+        assert channels["/chatter"].schema_id == string_id
+        assert len(reader._reader.get_schemas()) == 3
+        assert [m.data.data for m in reader.messages(["/string", "/chatter"])] == ["hello", "world"]
+
+
+@pytest.mark.parametrize("chunk_size", [None, 1024])
+def test_in_memory_writer(tmp_path: Path, chunk_size: int | None):
+    """Test writing a file to memory and reading it back."""
+    with McapFileWriter.in_memory(chunk_size=chunk_size, chunk_compression="zstd") as writer:
+        for i in range(10):
+            writer.write_message("/chatter", i, ros2_std_msgs.String(data=f"hello {i}"))
+        writer.write_metadata("info", {"key": "value"})
+    data = writer.to_bytes()
+
+    with McapFileReader.from_bytes(data) as reader:
+        assert [m.data.data for m in reader.messages("/chatter")] == [f"hello {i}" for i in range(10)]
+        assert reader.get_metadata("info")[0].metadata == {"key": "value"}
+
+    # Writing the same messages to a file gives the same bytes
+    file_path = tmp_path / "file.mcap"
+    with McapFileWriter.open(file_path, chunk_size=chunk_size, chunk_compression="zstd") as file_writer:
+        for i in range(10):
+            file_writer.write_message("/chatter", i, ros2_std_msgs.String(data=f"hello {i}"))
+        file_writer.write_metadata("info", {"key": "value"})
+        with pytest.raises(ValueError, match="in_memory"):
+            file_writer.to_bytes()
+    assert file_path.read_bytes() == data
+
+
+@pytest.mark.parametrize(
+    "options",
+    [
+        pytest.param({}, id="defaults"),
+        pytest.param({"chunk_size": 256, "chunk_compression": "zstd", "compression_level": 10}, id="zstd_level"),
+        pytest.param({"chunk_size": 256, "chunk_compression": "none", "compute_data_crc": False}, id="no_crc"),
+        pytest.param({"profile": "ros1", "chunk_size": 256}, id="ros1"),
+        pytest.param({"little_endian": False, "enforce_monotonic": "global"}, id="big_endian_monotonic"),
+    ]
+)
+def test_writer_option_combinations(tmp_path: Path, options: dict):
+    """Test that file and in-memory writers accept the same options and write the same bytes."""
+    String = ros1_std_msgs.String if options.get("profile") == "ros1" else ros2_std_msgs.String
+
+    with McapFileWriter.in_memory(**options) as writer:
+        for i in range(10):
+            writer.write_message("/chatter", i, String(data=f"hello {i}"))
+    data = writer.to_bytes()
+
+    file_path = tmp_path / "options.mcap"
+    with McapFileWriter.open(file_path, **options) as file_writer:
+        for i in range(10):
+            file_writer.write_message("/chatter", i, String(data=f"hello {i}"))
+    assert file_path.read_bytes() == data
+
+    with McapFileReader.from_bytes(data, enable_crc_check=True) as reader:
+        assert reader.profile == options.get("profile", "ros2")
+        assert [m.data.data for m in reader.messages("/chatter")] == [f"hello {i}" for i in range(10)]
+
+
+@pytest.mark.parametrize("chunk_size", [None, 1024])
+def test_copy_encoded_messages(tmp_path: Path, chunk_size: int | None):
+    """Test that encoded messages are copied between files without re-encoding."""
+    source_path = tmp_path / "source.mcap"
+    with McapFileWriter.open(source_path, chunk_size=chunk_size) as writer:
+        proto_id = writer.add_schema("example.Message", "protobuf", b"descriptor")
+        writer.add_channel("/proto", schema=proto_id, message_encoding="protobuf")
+        for i in range(3):
+            writer.write_message("/chatter", i, ros2_std_msgs.String(data=f"hello {i}"))
+            writer.write_message("/proto", i, bytes([i, 1, 2]), publish_time=i + 10)
+
+    copy_path = tmp_path / "copy.mcap"
+    with McapFileReader.from_file(source_path) as reader, \
+            McapFileWriter.open(copy_path, chunk_size=chunk_size) as writer:
+        with pytest.raises(ValueError, match="before calling add_channel"):
+            writer.write_message("/chatter", 0, b"\x00\x01\x00\x00")
+
+        topics = {}
+        for channel in reader.get_channels():
+            schema = reader.get_schema(channel.topic)
+            assert schema is not None
+            schema_id = writer.add_schema(schema.name, schema.encoding, schema.data)
+            writer.add_channel(channel.topic, schema=schema_id, message_encoding=channel.message_encoding)
+            topics[channel.id] = channel.topic
+        for channel_id, log_time, data in reader.scan():
+            writer.write_message(topics[channel_id], log_time, data)
+        expected = list(reader.scan())
+
+    with McapFileReader.from_file(copy_path) as reader:
+        assert list(reader.scan()) == expected
+        assert [m.data.data for m in reader.messages("/chatter")] == ["hello 0", "hello 1", "hello 2"]
+        assert [m.data for m in reader.messages("/proto")] == [bytes([i, 1, 2]) for i in range(3)]
+
+
+@pytest.mark.parametrize("enforce_monotonic", ["channel", "global"])
+def test_enforce_monotonic_accepts_ordered(tmp_path: Path, enforce_monotonic: Literal["channel", "global"]):
+    """Test that ordered messages, including equal log times, are accepted."""
+    file_path = tmp_path / "monotonic.mcap"
+    with McapFileWriter.open(file_path, enforce_monotonic=enforce_monotonic) as writer:
+        writer.write_message("/a", 1, ros2_std_msgs.String(data="a"))
+        writer.write_message("/b", 2, ros2_std_msgs.String(data="b"))
+        writer.write_message("/a", 2, ros2_std_msgs.String(data="a"))
+
+    with McapFileReader.from_file(file_path) as reader:
+        assert [m.log_time for m in reader.messages(["/a", "/b"])] == [1, 2, 2]
+
+
+def test_enforce_monotonic_per_channel(tmp_path: Path):
+    """Test that only earlier messages on the same channel are rejected."""
+    with McapFileWriter.open(tmp_path / "monotonic.mcap", enforce_monotonic="channel") as writer:
+        writer.write_message("/a", 5, ros2_std_msgs.String(data="a"))
+        writer.write_message("/b", 1, ros2_std_msgs.String(data="b"))
+        with pytest.raises(McapNonMonotonicError, match="earlier than the previous message at 5"):
+            writer.write_message("/a", 4, ros2_std_msgs.String(data="a"))
+
+
+def test_enforce_monotonic_global(tmp_path: Path):
+    """Test that earlier messages on any channel are rejected."""
+    with McapFileWriter.open(tmp_path / "monotonic.mcap", enforce_monotonic="global") as writer:
+        writer.write_message("/a", 5, ros2_std_msgs.String(data="a"))
+        with pytest.raises(McapNonMonotonicError):
+            writer.write_message("/b", 1, ros2_std_msgs.String(data="b"))
+
+
+@pytest.mark.parametrize("chunk_size", [None, 1024])
+@pytest.mark.parametrize("compute_data_crc", [True, False])
+def test_data_section_crc(tmp_path: Path, chunk_size: int | None, compute_data_crc: bool):
+    """Test that the data section CRC is written unless disabled."""
+    file_path = tmp_path / "data_crc.mcap"
+    with McapFileWriter.open(file_path, chunk_size=chunk_size, compute_data_crc=compute_data_crc) as writer:
+        for i in range(10):
+            writer.write_message("/test", i, ros2_std_msgs.String(data=f"msg_{i}"))
+
+    with FileReader(file_path) as reader:
+        _ = reader.seek_from_end(FOOTER_SIZE + MAGIC_BYTES_SIZE)
+        footer = McapRecordParser.parse_footer(reader)
+        _ = reader.seek_from_start(footer.summary_start - DATA_END_SIZE)
+        data_end = McapRecordParser.parse_data_end(reader)
+        assert (data_end.data_section_crc != 0) == compute_data_crc
+        assert footer.summary_crc != 0
+        assert_data_crc(reader)
+
+    with McapFileReader.from_file(file_path, enable_crc_check=True) as reader:
+        assert [m.data.data for m in reader.messages("/test")] == [f"msg_{i}" for i in range(10)]
+
+
+def test_zstd_compression_level(tmp_path: Path):
+    """Test that higher zstd levels produce smaller files on compressible data."""
+    words = ["alpha", "bravo", "charlie", "delta", "echo", "foxtrot", "golf", "hotel"]
+    texts = [" ".join(words[(i * 7 + j * 3) % len(words)] for j in range(i % 50 + 20)) for i in range(500)]
+
+    sizes = {}
+    for level in [1, 19]:
+        file_path = tmp_path / f"zstd_{level}.mcap"
+        with McapFileWriter.open(
+            file_path,
+            chunk_size=1024 * 1024,
+            chunk_compression="zstd",
+            compression_level=level,
+        ) as writer:
+            for i, text in enumerate(texts):
+                writer.write_message("/text", i, ros2_std_msgs.String(data=text))
+        sizes[level] = file_path.stat().st_size
+
+        with McapFileReader.from_file(file_path) as reader:
+            assert [m.data.data for m in reader.messages("/text")] == texts
+
+    assert sizes[19] < sizes[1]
+
+
+@pytest.mark.parametrize("level", [23, -(1 << 17) - 1])
+def test_zstd_compression_level_out_of_range(tmp_path: Path, level: int):
+    """Test that invalid zstd levels are rejected."""
+    with pytest.raises(ValueError, match="compression level"):
+        McapFileWriter.open(
+            tmp_path / "invalid.mcap",
+            chunk_size=1024,
+            chunk_compression="zstd",
+            compression_level=level,
+        )
+
+
+def test_zstd_dictionary(tmp_path: Path):
+    """Test that a shared zstd dictionary shrinks many small chunks and round-trips."""
+    words = ["alpha", "bravo", "charlie", "delta", "echo", "foxtrot", "golf", "hotel"]
+    texts = [
+        f"robot_{i % 4}/sensor " + " ".join(words[(i * 5 + j) % len(words)] for j in range(12))
+        for i in range(400)
+    ]
+    serializer = MessageSerializerFactory.from_profile("ros2")
+    assert serializer is not None
+    samples = [serializer.serialize_message(ros2_std_msgs.String(data=text)) for text in texts]
+    dictionary = zstd.train_dictionary(2048, samples).as_bytes()
+
+    sizes = {}
+    for name, zstd_dictionary in [("plain", None), ("dictionary", dictionary)]:
+        file_path = tmp_path / f"{name}.mcap"
+        with McapFileWriter.open(
+            file_path,
+            chunk_size=256,
+            chunk_compression="zstd",
+            zstd_dictionary=zstd_dictionary,
+        ) as writer:
+            for i, text in enumerate(texts):
+                writer.write_message("/text", i, ros2_std_msgs.String(data=text))
+        with McapFileReader.from_file(file_path) as reader:
+            assert [m.data.data for m in reader.messages("/text")] == texts
+        with McapChunkedReader.from_file(file_path) as chunked_reader:
+            sizes[name] = sum(chunk_index.compressed_size for chunk_index in chunked_reader.get_chunk_indexes())
+
+    assert sizes["dictionary"] < sizes["plain"] * 0.75
+
+    with McapFileReader.from_file(tmp_path / "dictionary.mcap") as reader:
+        attachment, = reader.get_attachments("pybag/zstd_dictionary")
+        assert attachment.data == dictionary
+
+
+def test_zstd_dictionary_requires_zstd(tmp_path: Path):
+    """Test that a dictionary is rejected without zstd chunks."""
+    with pytest.raises(ValueError, match="zstd chunk compression"):
+        McapFileWriter.open(tmp_path / "lz4.mcap", chunk_size=1024, chunk_compression="lz4", zstd_dictionary=b"dict")
+    with pytest.raises(ValueError, match="zstd chunk compression"):
+        McapFileWriter.open(tmp_path / "unchunked.mcap", chunk_compression="zstd", zstd_dictionary=b"dict")
+
+
+@pytest.mark.parametrize("chunk_size", [None, 1024])
+def test_summary_offsets_point_at_groups(tmp_path: Path, chunk_size: int | None):
+    """Test that every summary offset spans exactly one group of records."""
+    file_path = tmp_path / "summary_offsets.mcap"
+    with McapFileWriter.open(file_path, chunk_size=chunk_size) as writer:
+        for i in range(10):
+            writer.write_message("/string", i, ros2_std_msgs.String(data=f"msg_{i}"))
+            writer.write_message("/int", i, ros2_std_msgs.Int32(data=i))
+        writer.write_attachment("calibration.yaml", b"fx: 1.0")
+        writer.write_metadata("device", {"serial": "1234"})
+
+    with FileReader(file_path) as reader:
+        _ = reader.seek_from_end(FOOTER_SIZE + MAGIC_BYTES_SIZE)
+        footer = McapRecordParser.parse_footer(reader)
+        assert footer.summary_offset_start != 0
+
+        _ = reader.seek_from_start(footer.summary_offset_start)
+        summary_offsets = []
+        while McapRecordParser.peek_record(reader) == RecordType.SUMMARY_OFFSET:
+            summary_offsets.append(McapRecordParser.parse_summary_offset(reader))
+
+        expected_opcodes = {
+            RecordType.SCHEMA,
+            RecordType.CHANNEL,
+            RecordType.ATTACHMENT_INDEX,
+            RecordType.METADATA_INDEX,
+            RecordType.STATISTICS,
+        }
+        if chunk_size is not None:
+            expected_opcodes.add(RecordType.CHUNK_INDEX)
+        assert {offset.group_opcode for offset in summary_offsets} == expected_opcodes
+
+        # Groups are contiguous and cover the summary section up to the offsets
+        group_start = footer.summary_start
+        for offset in summary_offsets:
+            assert offset.group_start == group_start
+            _ = reader.seek_from_start(offset.group_start)
+            while reader.tell() < offset.group_start + offset.group_length:
+                assert McapRecordParser.peek_record(reader) == offset.group_opcode
+                McapRecordParser.skip_record(reader)
+            assert reader.tell() == offset.group_start + offset.group_length
+            group_start = reader.tell()
+        assert group_start == footer.summary_offset_start
@@ -0,0 +1,76 @@
+"""Tests for seeking and positioned writes in the binary writers."""
+from pathlib import Path
+from tempfile import TemporaryDirectory
+
+import pytest
+
+from pybag.io.raw_writer import BytesWriter, CrcWriter, FileWriter
+
+
+def test_bytes_writer_write_at():
+    writer = BytesWriter()
+    writer.write(b'header__payload')
+    assert writer.write_at(6, b'XY') == 2
+    assert writer.tell() == 15
+    writer.write(b'!')
+    assert writer.as_bytes() == b'headerXYpayload!'
+
+
+def test_bytes_writer_seek_read_and_truncate():
+    writer = BytesWriter()
+    writer.write(b'0123456789')
+
+    assert writer.seek_from_start(2) == 2
+    assert writer.read(3) == b'234'
+    assert writer.seek_from_current(-1) == 4
+    writer.write(b'ab')
+    assert writer.tell() == 6
+    assert writer.seek_from_end(2) == 8
+    assert writer.read() == b'89'
+
+    writer.seek_from_start(12 - 4)
+    writer.write(b'xyzw')  # Overwrites the end and extends the buffer
+    assert writer.as_bytes() == b'0123ab67xyzw'
+
+    writer.seek_from_start(4)
+    writer.truncate()
+    assert writer.as_bytes() == b'0123'
+
+    with pytest.raises(ValueError, match='Cannot seek'):
+        writer.seek_from_start(5)
+    with pytest.raises(ValueError, match='Cannot seek'):
+        writer.seek_from_current(-5)
+
+
+def test_bytes_writer_keeps_bytes_after_close():
+    writer = BytesWriter()
+    writer.write(b'data')
+    writer.close()
+    assert writer.as_bytes() == b'data'
+
+    writer.clear()
+    assert writer.as_bytes() == b''
+
+
+def test_file_writer_write_at():
+    with TemporaryDirectory() as temp_dir:
+        path = Path(temp_dir) / 'data.bin'
+        writer = FileWriter(path)
+        writer.write(b'footer: 00000000')
+        writer.write_at(8, b'12345678')
+        assert writer.tell() == 16
+        writer.write(b'.')
+        writer.close()
+        assert path.read_bytes() == b'footer: 12345678.'
+
+
+def test_crc_writer_write_at_skips_crc():
+    expected = CrcWriter(BytesWriter())
+    expected.write(b'abcd')
+
+    buffer = BytesWriter()
+    writer = CrcWriter(buffer)
+    writer.write(b'abcd')
+    writer.write_at(0, b'zz')
+    assert buffer.as_bytes() == b'zzcd'
+    assert writer.get_crc() == expected.get_crc()
@@ -1,6 +1,8 @@
 """Tests for the unified Reader class."""
 
+import gzip
 from dataclasses import dataclass
+from datetime import datetime, timezone
 from pathlib import Path
 from tempfile import TemporaryDirectory
 
@@ -10,7 +12,7 @@ import pybag.ros2.humble.std_msgs as std_msgs
 import pybag.types as t
 from pybag.bag_writer import BagFileWriter
 from pybag.mcap_writer import McapFileWriter
-from pybag.reader import DecodedMessage, Reader
+from pybag.reader import DecodedMessage, Reader, nanoseconds_to_datetime
 
 
 # Message types for bag files (ROS 1 style with pybag type annotations)
@@ -51,6 +53,19 @@ class TestFormatDetection:
             with Reader.from_file(path) as reader:
                 assert reader.format == 'bag'
 
+    def test_detect_compressed_mcap_extension(self):
+        """Test that a whole-file gzip compressed .mcap.gz is read as MCAP."""
+        with TemporaryDirectory() as temp_dir:
+            path = Path(temp_dir) / "test.mcap"
+            with McapFileWriter.open(path) as writer:
+                writer.write_message("/test", 1000, std_msgs.String(data="hello"))
+            compressed_path = Path(temp_dir) / "test.mcap.gz"
+            compressed_path.write_bytes(gzip.compress(path.read_bytes()))
+
+            with Reader.from_file(compressed_path) as reader:
+                assert reader.format == 'mcap'
+                assert [m.data.data for m in reader.messages("/test")] == ["hello"]
+
     def test_unknown_extension_raises_error(self):
         """Test that unknown extensions raise ValueError."""
         with pytest.raises(ValueError, match="Unknown file extension"):
@@ -106,6 +121,24 @@ class TestBasicReading:
             assert reader.start_time == 1000
             assert reader.end_time == 2500
 
+    @pytest.mark.parametrize("file_fixture", ["mcap_file", "bag_file"])
+    def test_get_time_range(self, file_fixture, request):
+        """Test the time range spans the first and last messages."""
+        path = request.getfixturevalue(file_fixture)
+        with Reader.from_file(path) as reader:
+            assert reader.get_time_range() == (1000, 2500, 1500)
+
+    @pytest.mark.parametrize("suffix", ["mcap", "bag"])
+    def test_get_time_range_empty(self, tmp_path, suffix):
+        """Test that a file without messages has no time range."""
+        path = tmp_path / f"empty.{suffix}"
+        writer_class = McapFileWriter if suffix == "mcap" else BagFileWriter
+        with writer_class.open(path):
+            pass
+
+        with Reader.from_file(path) as reader:
+            assert reader.get_time_range() is None
+
     @pytest.mark.parametrize("file_fixture", ["mcap_file", "bag_file"])
     def test_read_single_topic(self, file_fixture, request):
         """Test reading messages from a single topic."""
@@ -302,3 +335,11 @@ class TestGlobPatterns:
             assert len(messages) == 2
             topics = {msg.topic for msg in messages}
             assert topics == {"/sensor/camera", "/sensor/lidar"}
+
+
+def test_nanoseconds_to_datetime():
+    """Test converting nanosecond timestamps to UTC datetimes."""
+    assert nanoseconds_to_datetime(0) == datetime(1970, 1, 1, tzinfo=timezone.utc)
+    converted = nanoseconds_to_datetime(1_700_000_000_123_456_789)
+    assert converted == datetime(2023, 11, 14, 22, 13, 20, 123456, tzinfo=timezone.utc)
+    assert converted.tzinfo is timezone.utc
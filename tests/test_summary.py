@@ -1,3 +1,4 @@
+import io
 from enum import Enum
 from pathlib import Path
 
@@ -12,9 +13,12 @@ from pybag.mcap.record_parser import (
     MAGIC_BYTES_SIZE,
     McapRecordParser
 )
+from pybag.mcap.record_reader import McapRecordReaderFactory
 from pybag.mcap.record_writer import McapRecordWriter
 from pybag.mcap.records import FooterRecord
 from pybag.mcap_reader import McapFileReader
+from pybag.mcap_writer import McapFileWriter
+from pybag.ros2.humble import std_msgs
 
 
 def _read_footer(path: Path) -> FooterRecord:
@@ -168,3 +172,50 @@ def test_read_messages_across_writer_configs(
         assert [message.log_time for message in actual] == expected_time
         assert [message.publish_time for message in actual] == expected_time
         assert [message.data.data for message in actual] == expected_data
+
+
+@pytest.mark.parametrize('chunk_size', [
+    pytest.param(None, id='without_chunks'),
+    pytest.param(64, id='with_chunks'),
+])
+def test_reconstruct_missing_statistics(tmp_path: Path, chunk_size: int | None) -> None:
+    path = tmp_path / 'test.mcap'
+    with McapFileWriter.open(path, chunk_size=chunk_size) as writer:
+        for index in range(5):
+            writer.write_message('/a', 10 + index, std_msgs.String(data=f'msg_{index}'))
+        writer.write_message('/b', 5, std_msgs.Int32(data=1))
+        writer.write_attachment('calibration.yaml', b'fx: 1.0')
+        # Leave the statistics record out of the summary section
+        writer._record_writer._summary._cached_statistics = None
+
+    with McapFileReader.from_file(path) as reader:
+        assert reader.get_message_count('/a') == 5
+        assert reader.get_message_count('/b') == 1
+        assert (reader.start_time, reader.end_time) == (5, 14)
+
+        statistics = reader.get_statistics()
+        assert statistics is not None
+        assert statistics.message_count == 6
+        assert statistics.schema_count == 2
+        assert statistics.channel_count == 2
+        assert statistics.channel_message_counts == {'/a': 5, '/b': 1}
+        if chunk_size is not None:
+            assert statistics.chunk_count > 1
+
+
+@pytest.mark.parametrize('chunk_size', [
+    pytest.param(None, id='without_chunks'),
+    pytest.param(64, id='with_chunks'),
+])
+def test_missing_summary_without_reconstruction(tmp_path: Path, chunk_size: int | None) -> None:
+    path = tmp_path / 'test.mcap'
+    with McapFileWriter.open(path, chunk_size=chunk_size) as writer:
+        for index in range(5):
+            writer.write_message('/a', index, std_msgs.String(data=f'msg_{index}'))
+    _strip_summary(path)
+    data = path.read_bytes()
+
+    with pytest.raises(NotImplementedError, match='reconstruct summaries'):
+        McapRecordReaderFactory.from_bytes(data, enable_summary_reconstruction='never')
+    with pytest.raises(NotImplementedError, match='reconstruct summaries'):
+        McapRecordReaderFactory.from_stream(io.BytesIO(data), enable_summary_reconstruction='never')
@@ -1,7 +1,7 @@
 """Tests to verify type compatibility issues when converting between bag and mcap formats.
 
 These tests document the differences between ROS1 and ROS2 message formats:
-1. `char` type: uint8 in ROS1 vs single-character string in ROS2
+1. `char` type: uint8 in both ROS1 and ROS2
 2. `time`/`duration` types: primitive types in ROS1 (secs/nsecs) vs message types in ROS2 (sec/nanosec)
 """
 
@@ -40,9 +40,9 @@ class CharMessageRos1:
 
 @dataclass(kw_only=True)
 class CharMessageRos2:
-    """Message with a char field (ROS2 style - single char string)."""
+    """Message with a char field (ROS2 style - uint8)."""
     __msg_name__ = 'test_msgs/CharMessage'
-    value: t.ros2.char  # ROS2 char is str
+    value: t.ros2.char  # ROS2 char is also uint8
 
 
 @dataclass(kw_only=True)